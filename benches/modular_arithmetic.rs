@@ -1,5 +1,11 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use modularity::{ModularInt, MontgomeryArithmetic, MontgomeryContext};
+use modularity::field::{ExtFieldContext, Fp};
+use modularity::gf2n::{self, Gf2p8};
+use modularity::ntt::{self, NttPlan};
+use modularity::tower::{Fp12, Fp2, Fp6};
+use modularity::{
+    ModularInt, ModularStorage, MontgomeryArithmetic, MontgomeryContext, PowStrategy, U256,
+};
 
 fn bench_modular_addition(c: &mut Criterion) {
     let mut group = c.benchmark_group("ModularAddition");
@@ -12,10 +18,10 @@ fn bench_modular_addition(c: &mut Criterion) {
     });
 
     // Benchmark for large modulus
-    let large_prime = 0xFFFFFFFFFFFFFFFBu64; // 2^64 - 5
+    let large_prime = 0xFFFF_FFFF_FFFF_FFFBu64; // 2^64 - 5
     group.bench_function("large_modulus", |b| {
-        let a = ModularInt::<u64>::new(0xABCDEF0123456789u64, large_prime);
-        let rhs = ModularInt::<u64>::new(0x123456789ABCDEFu64, large_prime);
+        let a = ModularInt::<u64>::new(0xABCD_EF01_2345_6789u64, large_prime);
+        let rhs = ModularInt::<u64>::new(0x0123_4567_89AB_CDEFu64, large_prime);
         b.iter(|| black_box(a.add_mod(&rhs)));
     });
 
@@ -33,10 +39,10 @@ fn bench_modular_multiplication(c: &mut Criterion) {
     });
 
     // Benchmark for large modulus
-    let large_prime = 0xFFFFFFFFFFFFFFFBu64; // 2^64 - 5
+    let large_prime = 0xFFFF_FFFF_FFFF_FFFBu64; // 2^64 - 5
     group.bench_function("large_modulus", |b| {
-        let a = ModularInt::<u64>::new(0xABCDEF0123456789u64, large_prime);
-        let rhs = ModularInt::<u64>::new(0x123456789ABCDEFu64, large_prime);
+        let a = ModularInt::<u64>::new(0xABCD_EF01_2345_6789u64, large_prime);
+        let rhs = ModularInt::<u64>::new(0x0123_4567_89AB_CDEFu64, large_prime);
         b.iter(|| black_box(a.mul_mod(&rhs)));
     });
 
@@ -56,21 +62,269 @@ fn bench_montgomery_multiplication(c: &mut Criterion) {
     });
 
     // Benchmark for large modulus
-    let large_prime = 0xFFFFFFFFFFFFFFFBu64; // 2^64 - 5
+    let large_prime = 0xFFFF_FFFF_FFFF_FFFBu64; // 2^64 - 5
     group.bench_function("large_modulus", |b| {
         let ctx = MontgomeryContext::new(large_prime);
-        let a = ModularInt::<u64>::new(0xABCDEF0123456789u64, large_prime).to_montgomery(&ctx);
-        let rhs = ModularInt::<u64>::new(0x123456789ABCDEFu64, large_prime).to_montgomery(&ctx);
+        let a = ModularInt::<u64>::new(0xABCD_EF01_2345_6789u64, large_prime).to_montgomery(&ctx);
+        let rhs = ModularInt::<u64>::new(0x0123_4567_89AB_CDEFu64, large_prime).to_montgomery(&ctx);
         b.iter(|| black_box(a.montgomery_mul(&rhs, &ctx)));
     });
 
+    // Benchmark for a 256-bit modulus, the P-256 field prime
+    group.bench_function("u256_modulus", |b| {
+        let modulus =
+            U256::from_be_hex("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff");
+        let ctx = MontgomeryContext::new(modulus);
+        let a =
+            U256::from_be_hex("6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296");
+        let rhs =
+            U256::from_be_hex("4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5");
+        b.iter(|| black_box(ctx.mul_mod(&a, &rhs)));
+    });
+
+    group.finish();
+}
+
+fn bench_modular_exponentiation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ModularExponentiation");
+
+    let large_prime = 0xFFFF_FFFF_FFFF_FFC5u64; // 2^64 - 59
+    let a = ModularInt::<u64>::new(0xABCD_EF01_2345_6789u64, large_prime);
+    let exponent = [0x0123_4567_89AB_CDEFu64, 0xFEDC_BA98_7654_3210, u64::MAX, 1];
+    for (name, strategy) in [
+        ("binary", PowStrategy::Binary),
+        ("fixed_window", PowStrategy::FixedWindow(5)),
+        ("sliding_window", PowStrategy::SlidingWindow(5)),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| black_box(a.pow_with(black_box(&exponent), strategy)));
+        });
+    }
+
+    // Short exponents around MONTGOMERY_MIN_EXPONENT_BITS, where setting up the
+    // Montgomery context has to pay for itself
+    group.bench_function("montgomery_context_new", |b| {
+        b.iter(|| black_box(MontgomeryContext::new(black_box(large_prime))));
+    });
+    for bits in [8u32, 16, 32] {
+        let exponent = (1u64 << bits) - 1;
+        group.bench_function(format!("pow_mod_{bits}_bit_exponent"), |b| {
+            b.iter(|| {
+                black_box(u64::pow_mod(
+                    black_box(0xABCD_EF01_2345_6789),
+                    &exponent,
+                    large_prime,
+                    PowStrategy::Auto,
+                ))
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_ntt(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Ntt");
+
+    for (name, modulus) in [
+        ("p998244353", ntt::P998244353),
+        ("goldilocks", ntt::GOLDILOCKS),
+    ] {
+        let plan = NttPlan::new(modulus, 1 << 12);
+        let mut data: Vec<u64> = (0..1u64 << 12).map(|i| i * i % modulus).collect();
+        group.bench_function(format!("forward_4096_{name}"), |b| {
+            b.iter(|| plan.forward(black_box(&mut data)));
+        });
+        let other: Vec<u64> = data.iter().map(|x| x.rotate_left(7) % modulus).collect();
+        group.bench_function(format!("cyclic_convolution_4096_{name}"), |b| {
+            b.iter(|| black_box(plan.cyclic_convolution(black_box(&data), black_box(&other))));
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_batch_multiplication(c: &mut Criterion) {
+    let mut group = c.benchmark_group("BatchMultiplication");
+
+    for (name, modulus) in [
+        ("p998244353", ntt::P998244353),
+        ("goldilocks", ntt::GOLDILOCKS),
+    ] {
+        let ctx = MontgomeryContext::new(modulus);
+        let a: Vec<u64> = (0..1u64 << 12).map(|i| i * i % modulus).collect();
+        let rhs: Vec<u64> = (0..1u64 << 12).map(|i| (i ^ 0x5555) % modulus).collect();
+        let mut data = a.clone();
+        group.bench_function(format!("mul_slices_4096_{name}"), |b| {
+            b.iter(|| ctx.mul_slices(black_box(&mut data), black_box(&rhs)));
+        });
+        group.bench_function(format!("dot_product_4096_{name}"), |b| {
+            b.iter(|| black_box(ctx.dot_product(black_box(&a), black_box(&rhs))));
+        });
+    }
+
+    group.finish();
+}
+
+#[cfg(feature = "hardware-acceleration")]
+fn bench_accelerated(c: &mut Criterion) {
+    use modularity::intrinsics::ModularArithmeticAccelerated;
+    use modularity::U1024;
+
+    let mut group = c.benchmark_group("Accelerated");
+
+    let modulus = 0xFFFF_FFFF_FFFF_FFC5u64; // 2^64 - 59
+    let ctx = MontgomeryContext::new(modulus);
+    let small_ctx = MontgomeryContext::new(ntt::P998244353);
+    let a: Vec<u64> = (0..1u64 << 12)
+        .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15) % modulus)
+        .collect();
+    let rhs: Vec<u64> = a.iter().map(|x| x.rotate_left(17) % modulus).collect();
+    // Multi-limb residues for the CIOS kernels: the P-256 prime and a 1024-bit modulus
+    let u256_ctx = MontgomeryContext::new(U256::from_be_hex(
+        "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+    ));
+    let u256_a = u256_ctx.reduce(&U256::from_words([a[1], a[2], a[3], a[4]]));
+    let u256_rhs = u256_ctx.reduce(&U256::from_words([a[5], a[6], a[7], a[8]]));
+    let u1024_ctx = MontgomeryContext::new(U1024::from_words(std::array::from_fn(|i| {
+        a[i] | 1 << 63 | 1
+    })));
+    let u1024_a = u1024_ctx.reduce(&U1024::from_words(std::array::from_fn(|i| rhs[i])));
+    let u1024_rhs = u1024_ctx.reduce(&U1024::from_words(std::array::from_fn(|i| a[i + 16])));
+    for (name, accel) in [
+        ("portable", ModularArithmeticAccelerated::portable()),
+        ("detected", ModularArithmeticAccelerated::new()),
+    ] {
+        group.bench_function(format!("mul_mod_chain_{name}"), |b| {
+            b.iter(|| {
+                let mut x = a[1];
+                for &y in &rhs[..256] {
+                    x = accel.mul_mod(x, y, modulus);
+                }
+                black_box(x)
+            });
+        });
+        group.bench_function(format!("mul_mod_montgomery_chain_{name}"), |b| {
+            b.iter(|| {
+                let mut x = a[1];
+                for &y in &rhs[..256] {
+                    x = accel.mul_mod_montgomery(x, y, &ctx);
+                }
+                black_box(x)
+            });
+        });
+        let mut data = a.clone();
+        group.bench_function(format!("montgomery_mul_slices_4096_{name}"), |b| {
+            b.iter(|| accel.montgomery_mul_slices(black_box(&mut data), black_box(&rhs), &ctx));
+        });
+        group.bench_function(format!("montgomery_mul_u256_chain_{name}"), |b| {
+            b.iter(|| {
+                let mut x = u256_a;
+                for _ in 0..64 {
+                    x = accel.montgomery_mul_uint(&x, &u256_rhs, &u256_ctx);
+                }
+                black_box(x)
+            });
+        });
+        group.bench_function(format!("montgomery_mul_u1024_chain_{name}"), |b| {
+            b.iter(|| {
+                let mut x = u1024_a;
+                for _ in 0..64 {
+                    x = accel.montgomery_mul_uint(&x, &u1024_rhs, &u1024_ctx);
+                }
+                black_box(x)
+            });
+        });
+        // Small enough for the vector kernels
+        let mut data: Vec<u64> = a.iter().map(|x| x % ntt::P998244353).collect();
+        let small_rhs: Vec<u64> = rhs.iter().map(|x| x % ntt::P998244353).collect();
+        group.bench_function(
+            format!("montgomery_mul_slices_4096_p998244353_{name}"),
+            |b| {
+                b.iter(|| {
+                    accel.montgomery_mul_slices(
+                        black_box(&mut data),
+                        black_box(&small_rhs),
+                        &small_ctx,
+                    );
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn bench_gf2n(c: &mut Criterion) {
+    let mut group = c.benchmark_group("BinaryFields");
+    let blocks: Vec<[u8; 16]> = (0..256u128)
+        .map(|i| {
+            i.wrapping_mul(0x2360_ED05_1FC6_5DA4_4385_DF64_9FCC_F645)
+                .to_le_bytes()
+        })
+        .collect();
+    let h = [0x42; 16];
+
+    group.bench_function("ghash_4096_bytes", |b| {
+        b.iter(|| gf2n::ghash(black_box(h), black_box(&blocks)));
+    });
+    group.bench_function("polyval_4096_bytes", |b| {
+        b.iter(|| gf2n::polyval(black_box(h), black_box(&blocks)));
+    });
+    group.bench_function("gf2p8_inverse", |b| {
+        b.iter(|| black_box(Gf2p8::new(0x53)).inverse());
+    });
+
+    group.finish();
+}
+
+fn bench_extension_fields(c: &mut Criterion) {
+    // 2^63 - 25, with u² = -1 and v³ = 3 + u
+    const P: u64 = 9_223_372_036_854_775_783;
+    type F12 = Fp12<P, { P - 1 }, 3, 1>;
+
+    let mut group = c.benchmark_group("ExtensionFields");
+    let coefficient = |i: u64| Fp2::new(Fp::new(i * 0x9E37_79B9), Fp::new(i + 7));
+    let sextic = |i: u64| Fp6::new(coefficient(i), coefficient(i + 1), coefficient(i + 2));
+    let lhs = F12::new(sextic(1), sextic(4));
+    let rhs = F12::new(sextic(7), sextic(10));
+
+    group.bench_function("fp12_mul", |bench| {
+        bench.iter(|| black_box(lhs) * black_box(rhs));
+    });
+    group.bench_function("fp12_square", |bench| {
+        bench.iter(|| black_box(lhs).square());
+    });
+    group.bench_function("fp12_inverse", |bench| {
+        bench.iter(|| black_box(lhs).inverse());
+    });
+
+    let ctx = ExtFieldContext::<P, 12>::new(&[7, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    let left = ctx.from_coefficients(&std::array::from_fn(|i| i as u64 * 0x9E37_79B9));
+    let right = ctx.from_coefficients(&std::array::from_fn(|i| i as u64 + 7));
+    group.bench_function("ext_field_12_mul", |bench| {
+        bench.iter(|| black_box(left) * black_box(right));
+    });
+    group.bench_function("ext_field_12_inverse", |bench| {
+        bench.iter(|| black_box(left).inverse());
+    });
+
     group.finish();
 }
 
+#[cfg(not(feature = "hardware-acceleration"))]
+const fn bench_accelerated(_: &mut Criterion) {}
+
 criterion_group!(
     benches,
     bench_modular_addition,
     bench_modular_multiplication,
-    bench_montgomery_multiplication
+    bench_montgomery_multiplication,
+    bench_modular_exponentiation,
+    bench_ntt,
+    bench_batch_multiplication,
+    bench_gf2n,
+    bench_extension_fields,
+    bench_accelerated
 );
 criterion_main!(benches);
@@ -7,38 +7,35 @@ fn main() {
     let b = ModularInt::<u64>::new(7u64, modulus);
 
     // Basic operations
-    let c = a.add_mod(&b);
-    println!("5 + 7 mod 17 = {}", c.value()); // Output: 12
-
-    let d = a.sub_mod(&b);
-    println!("5 - 7 mod 17 = {}", d.value()); // Output: 15
-
-    let e = a.mul_mod(&b);
-    println!("5 * 7 mod 17 = {}", e.value()); // Output: 1
-
-    // Montgomery multiplication (requires specific implementation in MontgomeryContext)
-    // This will panic with todo!() until implemented
-    if false {
-        // Guard to prevent panic
-        let mont_ctx = MontgomeryContext::new(modulus);
-        let a_mont = a.to_montgomery(&mont_ctx);
-        let b_mont = b.to_montgomery(&mont_ctx);
-        let c_mont = a_mont.montgomery_mul(&b_mont, &mont_ctx);
-        let result = c_mont.from_montgomery(&mont_ctx);
-        println!("5 * 7 mod 17 using Montgomery = {}", result.value()); // Should be 1
-    }
+    let sum = a + b;
+    println!("5 + 7 mod 17 = {}", sum.value()); // Output: 12
 
-    // Large modulus example
-    let large_prime = 0xFFFFFFFFFFFFFFFBu64; // 2^64 - 5
-    let x = ModularInt::<u64>::new(0xABCDEF0123456789u64, large_prime);
-    let y = ModularInt::<u64>::new(0x123456789ABCDEFu64, large_prime);
+    let difference = a - b;
+    println!("5 - 7 mod 17 = {}", difference.value()); // Output: 15
+
+    let product = a * b;
+    println!("5 * 7 mod 17 = {}", product.value()); // Output: 1
+
+    let negation = -a;
+    println!("-5 mod 17 = {}", negation.value()); // Output: 12
 
-    let z = x.add_mod(&y);
-    println!("x + y mod p = {:#x}", z.value());
+    // Montgomery multiplication
+    let mont_ctx = MontgomeryContext::new(modulus);
+    let a_mont = a.to_montgomery(&mont_ctx);
+    let b_mont = b.to_montgomery(&mont_ctx);
+    let c_mont = a_mont.montgomery_mul(&b_mont, &mont_ctx);
+    let result = c_mont.from_montgomery(&mont_ctx);
+    println!("5 * 7 mod 17 using Montgomery = {}", result.value()); // Output: 1
 
-    let w = x.mul_mod(&y);
-    println!("x * y mod p = {:#x}", w.value());
+    // Operators work for every primitive width
+    let small = ModularInt::<u8>::new(200, 251) * ModularInt::<u8>::new(100, 251);
+    println!("200 * 100 mod 251 = {}", small.value()); // Output: 171
+
+    // Large modulus example
+    let large_prime = 0xFFFF_FFFF_FFFF_FFFBu64; // 2^64 - 5
+    let x = ModularInt::<u64>::new(0xABCD_EF01_2345_6789u64, large_prime);
+    let y = ModularInt::<u64>::new(0x0123_4567_89AB_CDEFu64, large_prime);
 
-    println!("\nThis example shows basic functionality of the modularity library.");
-    println!("Note that many advanced features are marked with todo!() and need implementation.");
+    println!("x + y mod p = {:#x}", (x + y).value());
+    println!("x * y mod p = {:#x}", (x * y).value());
 }
@@ -1,7 +1,10 @@
 #[cfg(feature = "hardware-acceleration")]
 use modularity::intrinsics::ModularArithmeticAccelerated;
 use modularity::ModularInt;
-use std::time::{Duration, Instant};
+#[cfg(feature = "hardware-acceleration")]
+use std::hint::black_box;
+#[cfg(feature = "hardware-acceleration")]
+use std::time::Instant;
 
 fn main() {
     // This example demonstrates the use of hardware acceleration for modular arithmetic
@@ -15,56 +18,55 @@ fn main() {
         // Check if hardware acceleration is available
         let accel = ModularArithmeticAccelerated::new();
         if accel.use_acceleration {
-            println!("Hardware acceleration is available and will be used.");
+            println!(
+                "Hardware acceleration is available; using the {:?} backend.",
+                accel.backend()
+            );
         } else {
             println!("Hardware acceleration is not available on this system.");
             println!("Falling back to software implementation.");
         }
+        let portable = ModularArithmeticAccelerated::portable();
 
         // Define test parameters
-        let modulus = 0xFFFFFFFFFFFFFFFBu64; // 2^64 - 5, a large prime
-        let a = 0xABCDEF0123456789u64;
-        let b = 0x123456789ABCDEFu64;
+        let modulus = 0xFFFF_FFFF_FFFF_FFFBu64; // 2^64 - 5, a large prime
+        let a = 0xABCD_EF01_2345_6789u64;
+        let b = 0x0123_4567_89AB_CDEFu64;
 
         // Benchmark hardware-accelerated multiplication
         println!("\nBenchmarking hardware-accelerated multiplication...");
         let start = Instant::now();
-        let mut result = 0;
+        let mut result = a;
         for _ in 0..1_000_000 {
-            result = accel.mul_mod(a, b, modulus);
+            result = accel.mul_mod(black_box(result), b, modulus);
         }
         let hw_duration = start.elapsed();
-        println!("Result: {:#x}", result);
-        println!("Time: {:?}", hw_duration);
+        println!("Result: {result:#x}");
+        println!("Time: {hw_duration:?}");
 
-        // Compare with standard implementation
-        println!("\nBenchmarking standard multiplication...");
+        // Compare with the portable implementation
+        println!("\nBenchmarking portable multiplication...");
         let start = Instant::now();
-        let mut std_result = 0;
+        let mut std_result = a;
         for _ in 0..1_000_000 {
-            std_result = ((a as u128 * b as u128) % modulus as u128) as u64;
+            std_result = portable.mul_mod(black_box(std_result), b, modulus);
         }
         let std_duration = start.elapsed();
-        println!("Result: {:#x}", std_result);
-        println!("Time: {:?}", std_duration);
+        println!("Result: {std_result:#x}");
+        println!("Time: {std_duration:?}");
 
         // Compare results
         assert_eq!(result, std_result, "Results must match!");
 
         // Print speedup
-        if hw_duration < std_duration {
-            let speedup = std_duration.as_nanos() as f64 / hw_duration.as_nanos() as f64;
-            println!(
-                "\nHardware acceleration provides a {:.2}x speedup.",
-                speedup
-            );
+        let ratio = std_duration.as_secs_f64() / hw_duration.as_secs_f64();
+        if ratio >= 1.0 {
+            println!("\nHardware acceleration provides a {ratio:.2}x speedup.");
         } else {
-            let slowdown = hw_duration.as_nanos() as f64 / std_duration.as_nanos() as f64;
             println!(
-                "\nHardware acceleration is {:.2}x slower than standard implementation.",
-                slowdown
+                "\nHardware acceleration is {:.2}x slower than the portable implementation.",
+                ratio.recip()
             );
-            println!("This might be due to overhead of feature detection or other factors.");
         }
     }
 
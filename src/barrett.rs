@@ -1,9 +1,12 @@
 // Barrett reduction implementation
 
-use crate::ModularInt;
+use crate::wide::{add_wide_u128, div_rem_wide_u128, mul_hi_u128, mul_wide_u128, sub_wide_u128};
+use crate::{pow_with, Exponent, ModularError, ModularInt, ModularStorage, PowStrategy};
+#[cfg(feature = "arbitrary-precision")]
+use num_bigint::BigUint;
+#[cfg(feature = "arbitrary-precision")]
 use num_traits::{One, Zero};
 use std::fmt::Debug;
-use std::ops::{Add, Div, Mul, Sub};
 
 /// Barrett context for efficient modular reduction.
 ///
@@ -11,146 +14,337 @@ use std::ops::{Add, Div, Mul, Sub};
 /// without performing division by the modulus. This context stores precomputed values
 /// needed for Barrett reduction.
 #[derive(Debug, Clone)]
-pub struct BarrettContext<T> {
+pub struct BarrettContext<T: BarrettWord> {
     modulus: T,
-    mu: T, // Precomputed value for Barrett reduction
+    mu: T::Reciprocal, // Precomputed value for Barrett reduction
 }
 
 // Extension trait for ModularInt to use Barrett reduction
-pub trait BarrettReduction<T> {
+pub trait BarrettReduction<T: BarrettWord> {
     fn barrett_reduce(&self, ctx: &BarrettContext<T>) -> ModularInt<T>;
     fn barrett_mul(&self, other: &ModularInt<T>, ctx: &BarrettContext<T>) -> ModularInt<T>;
 }
 
-// Generic implementation with placeholder methods
-impl<T> BarrettContext<T>
-where
-    T: Copy
-        + PartialEq
-        + PartialOrd
-        + Eq
-        + Zero
-        + One
-        + Add<Output = T>
-        + Sub<Output = T>
-        + Mul<Output = T>
-        + Div<Output = T>
-        + Debug,
-{
+// Accessors shared by every primitive word type
+impl<T: BarrettWord + ModularStorage> BarrettContext<T> {
     /// Returns the modulus used in this Barrett context.
-    pub fn modulus(&self) -> T {
+    #[must_use]
+    pub const fn modulus(&self) -> T {
         self.modulus
     }
 }
 
-// Implementation for u64
-impl BarrettContext<u64> {
-    /// Creates a new Barrett context for a u64 modulus.
+/// Integer types for which a `BarrettContext` can be built.
+pub trait BarrettWord: Sized {
+    /// The precomputed reciprocal mu, sized to the word: twice its width for the
+    /// fixed-width types and a single bignum for `BigUint`.
+    type Reciprocal: Debug + Clone;
+
+    /// Validates the modulus and precomputes the constants for Barrett reduction.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::ZeroModulus` if the modulus is zero.
+    fn barrett_context(modulus: Self) -> Result<BarrettContext<Self>, ModularError>;
+
+    /// Computes (a * b) mod N for operands below the modulus.
+    fn barrett_product(ctx: &BarrettContext<Self>, a: &Self, b: &Self) -> Self;
+}
+
+// Construction shared by every supported word type
+impl<T: BarrettWord> BarrettContext<T> {
+    /// Creates a new Barrett context for the given modulus.
     ///
     /// Precomputes values needed for efficient Barrett reduction.
-    pub fn new(modulus: u64) -> Self {
-        assert!(modulus > 0, "Modulus must be positive");
+    ///
+    /// # Panics
+    ///
+    /// Panics if the modulus is zero.
+    #[must_use]
+    pub fn new(modulus: T) -> Self {
+        match Self::try_new(modulus) {
+            Ok(ctx) => ctx,
+            Err(err) => panic!("{err}"),
+        }
+    }
 
-        // Compute mu = floor(2^128 / modulus)
-        // This is used to estimate division by the modulus
-        let mu = if modulus == 1 {
-            0 // Special case, though not very useful
-        } else {
-            // Calculate floor(2^128 / modulus)
-            // To avoid 128-bit division, compute it as (2^128 - 1) / modulus
-            let max_u128 = u128::MAX;
-            ((max_u128 / modulus as u128) + 1) as u64
-        };
+    /// Creates a new Barrett context, returning an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::ZeroModulus` if the modulus is zero.
+    pub fn try_new(modulus: T) -> Result<Self, ModularError> {
+        T::barrett_context(modulus)
+    }
+}
 
-        Self { modulus, mu }
+// Exponentiation shared by every supported word type
+impl<T: BarrettWord + ModularStorage> BarrettContext<T> {
+    /// Computes base^exponent mod N with Barrett multiplication.
+    #[must_use]
+    pub fn pow_mod<E: Exponent>(&self, base: T, exponent: E) -> T {
+        let base = T::reduce(base, self.modulus());
+        pow_with(self, &base, &exponent, PowStrategy::Auto)
     }
+}
+
+// Implementation for u64
+impl BarrettWord for u64 {
+    type Reciprocal = u128;
+
+    fn barrett_context(modulus: Self) -> Result<BarrettContext<Self>, ModularError> {
+        if modulus == 0 {
+            return Err(ModularError::ZeroModulus);
+        }
 
+        // Compute mu = floor((2^128 - 1) / modulus), a full 128-bit reciprocal.
+        // Truncating it to 64 bits loses the quotient for every modulus above 1.
+        let mu = u128::MAX / u128::from(modulus);
+
+        Ok(BarrettContext { modulus, mu })
+    }
+
+    fn barrett_product(ctx: &BarrettContext<Self>, a: &Self, b: &Self) -> Self {
+        ctx.mul_mod_u64(*a, *b)
+    }
+}
+
+impl BarrettContext<u64> {
     /// Performs Barrett reduction on the given value.
     ///
     /// This efficiently computes value % modulus without using the expensive
     /// modulo operation.
-    pub fn reduce_u64(&self, value: u64) -> u64 {
+    #[must_use]
+    pub const fn reduce_u64(&self, value: u64) -> u64 {
         // If value < modulus, no reduction needed
         if value < self.modulus {
             return value;
         }
 
-        // For small values, direct modulo is faster
-        if value < self.modulus * 2 {
-            return value - self.modulus;
+        self.reduce_u128(value as u128)
+    }
+
+    /// Performs Barrett reduction on an arbitrary 128-bit value.
+    #[must_use]
+    pub const fn reduce_u128(&self, value: u128) -> u64 {
+        Self::barrett_reduce_u128(value, self.modulus, self.mu)
+    }
+
+    /// Performs efficient modular multiplication using Barrett reduction.
+    #[must_use]
+    pub const fn mul_mod_u64(&self, a: u64, b: u64) -> u64 {
+        self.reduce_u128(a as u128 * b as u128)
+    }
+
+    /// Barrett reduction with explicit constants, usable in `const` contexts.
+    ///
+    /// With mu = floor((2^128 - 1) / modulus), the estimate q = floor(value * mu / 2^128)
+    /// never exceeds floor(value / modulus) and falls short of it by at most two:
+    /// mu > 2^128 / modulus - 1 - 1 / modulus, so value * mu / 2^128 > value / modulus - 2
+    /// for every value < 2^128. The remainder therefore needs at most two correction
+    /// steps and stays below 3 * modulus, so it never overflows.
+    #[allow(clippy::cast_possible_truncation)] // r < modulus after the corrections
+    pub(crate) const fn barrett_reduce_u128(value: u128, modulus: u64, mu: u128) -> u64 {
+        // 1. q = floor(value * mu / 2^128), from the exact 256-bit product
+        let q = mul_hi_u128(value, mu);
+
+        // 2. r = value - q * modulus; q <= value / modulus, so this cannot underflow
+        let mut r = value - q * modulus as u128;
+
+        // 3. At most two corrections
+        while r >= modulus as u128 {
+            r -= modulus as u128;
         }
 
-        // Barrett reduction algorithm
-        // 1. q = floor(value * mu / 2^64)
-        // 2. r = value - q * modulus
-        // 3. if r >= modulus, r = r - modulus
-        let q = ((value as u128 * self.mu as u128) >> 64) as u64;
-        let mut r = value.wrapping_sub(q.wrapping_mul(self.modulus));
+        r as u64
+    }
+}
+
+// Specific implementation of Barrett reduction for ModularInt<u64>
+impl BarrettReduction<u64> for ModularInt<u64> {
+    fn barrett_reduce(&self, ctx: &BarrettContext<u64>) -> Self {
+        assert_eq!(self.modulus(), ctx.modulus(), "Modulus mismatch");
+        Self::new(ctx.reduce_u64(self.value()), self.modulus())
+    }
+
+    fn barrett_mul(&self, other: &Self, ctx: &BarrettContext<u64>) -> Self {
+        assert_eq!(self.modulus(), ctx.modulus(), "Modulus mismatch for self");
+        assert_eq!(other.modulus(), ctx.modulus(), "Modulus mismatch for other");
+        Self::new(ctx.mul_mod_u64(self.value(), other.value()), self.modulus())
+    }
+}
+
+// Implementation for u128
+impl BarrettWord for u128 {
+    type Reciprocal = [Self; 2]; // (low, high) words
 
-        // Final correction step
-        if r >= self.modulus {
-            r -= self.modulus;
+    fn barrett_context(modulus: Self) -> Result<BarrettContext<Self>, ModularError> {
+        if modulus == 0 {
+            return Err(ModularError::ZeroModulus);
         }
 
-        r
+        // Compute mu = floor((2^256 - 1) / modulus) by long division, one 128-bit word at a time
+        let mu_hi = Self::MAX / modulus;
+        let (mu_lo, _) = div_rem_wide_u128(Self::MAX % modulus, Self::MAX, modulus);
+
+        Ok(BarrettContext {
+            modulus,
+            mu: [mu_lo, mu_hi],
+        })
+    }
+
+    fn barrett_product(ctx: &BarrettContext<Self>, a: &Self, b: &Self) -> Self {
+        ctx.mul_mod_u128(*a, *b)
+    }
+}
+
+impl BarrettContext<u128> {
+    /// Performs Barrett reduction on the given value.
+    #[must_use]
+    pub const fn reduce_u128(&self, value: u128) -> u128 {
+        self.reduce_u256(0, value)
+    }
+
+    /// Performs Barrett reduction on the 256-bit value high * 2^128 + low.
+    ///
+    /// Computes q = floor(x * mu / 2^256) exactly from the four 128-bit partial products.
+    /// Because mu underestimates 2^256 / modulus by less than one, q is at most two below
+    /// floor(x / modulus), so the remainder needs at most two correction steps.
+    #[must_use]
+    pub const fn reduce_u256(&self, high: u128, low: u128) -> u128 {
+        // The estimate below needs x < modulus * 2^128, so fold the high word in first
+        let high = if high >= self.modulus {
+            self.reduce_u256(0, high)
+        } else {
+            high
+        };
+        let [mu_lo, mu_hi] = self.mu;
+
+        // Middle column of x * mu: everything that lands in bits 128..384, kept as 256 bits
+        let (middle, _) = add_wide_u128(mul_wide_u128(high, mu_lo), mul_wide_u128(low, mu_hi));
+        let (middle, _) = add_wide_u128(middle, (0, mul_hi_u128(low, mu_lo)));
+
+        // q < 2^128, so wrapping arithmetic on the top column is exact
+        let q = high.wrapping_mul(mu_hi).wrapping_add(middle.0);
+
+        // r = x - q * modulus, then at most two corrections
+        let mut r = sub_wide_u128((high, low), mul_wide_u128(q, self.modulus));
+        while r.0 != 0 || r.1 >= self.modulus {
+            r = sub_wide_u128(r, (0, self.modulus));
+        }
+        r.1
     }
 
     /// Performs efficient modular multiplication using Barrett reduction.
-    pub fn mul_mod_u64(&self, a: u64, b: u64) -> u64 {
-        let product = a as u128 * b as u128;
-        if product < self.modulus as u128 {
-            return product as u64;
+    #[must_use]
+    pub const fn mul_mod_u128(&self, a: u128, b: u128) -> u128 {
+        let (high, low) = mul_wide_u128(a, b);
+        self.reduce_u256(high, low)
+    }
+}
+
+// Specific implementation of Barrett reduction for ModularInt<u128>
+impl BarrettReduction<u128> for ModularInt<u128> {
+    fn barrett_reduce(&self, ctx: &BarrettContext<u128>) -> Self {
+        assert_eq!(self.modulus(), ctx.modulus(), "Modulus mismatch");
+        Self::new(ctx.reduce_u128(self.value()), self.modulus())
+    }
+
+    fn barrett_mul(&self, other: &Self, ctx: &BarrettContext<u128>) -> Self {
+        assert_eq!(self.modulus(), ctx.modulus(), "Modulus mismatch for self");
+        assert_eq!(other.modulus(), ctx.modulus(), "Modulus mismatch for other");
+        Self::new(
+            ctx.mul_mod_u128(self.value(), other.value()),
+            self.modulus(),
+        )
+    }
+}
+
+// Implementation for BigUint
+#[cfg(feature = "arbitrary-precision")]
+impl BarrettWord for BigUint {
+    type Reciprocal = Self;
+
+    fn barrett_context(modulus: Self) -> Result<BarrettContext<Self>, ModularError> {
+        if modulus.is_zero() {
+            return Err(ModularError::ZeroModulus);
         }
 
-        // For products that fit in u128, we can do reduction directly
-        let product_hi = (product >> 64) as u64;
-        let product_lo = product as u64;
+        // Compute mu = floor(4^k / modulus) for a k-bit modulus
+        let mu = (Self::one() << (2 * modulus.bits())) / &modulus;
 
-        // Barrett reduction for the product
-        // 1. q = floor(product * mu / 2^64)
-        // 2. r = product - q * modulus
-        // 3. if r >= modulus, r = r - modulus
+        Ok(BarrettContext { modulus, mu })
+    }
 
-        // Compute q for the product
-        let q_hi = ((product_hi as u128 * self.mu as u128) >> 64) as u64;
-        let q_lo_part1 = ((product_lo as u128 * self.mu as u128) >> 64) as u64;
-        let q_lo_part2 = (product_lo as u128 * self.mu as u128) as u64;
+    fn barrett_product(ctx: &BarrettContext<Self>, a: &Self, b: &Self) -> Self {
+        ctx.reduce(&(a * b))
+    }
+}
 
-        // q = q_hi * 2^64 + q_lo
-        // We only need q_hi and q_lo_part1 for the computation
+#[cfg(feature = "arbitrary-precision")]
+impl BarrettContext<BigUint> {
+    /// Returns the modulus used in this Barrett context.
+    #[must_use]
+    pub const fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
 
-        // Compute r = product - q * modulus
-        let qm = q_hi
-            .wrapping_mul(self.modulus)
-            .wrapping_add(q_lo_part1.wrapping_mul(self.modulus));
-        let mut r = product_lo.wrapping_sub(qm);
+    /// Performs Barrett reduction on the given value.
+    ///
+    /// Values of up to twice the modulus's bit length, such as products of two
+    /// residues, take the Barrett path; anything larger falls back to division.
+    #[must_use]
+    pub fn reduce(&self, value: &BigUint) -> BigUint {
+        let k = self.modulus.bits();
+        if value.bits() > 2 * k {
+            return value % &self.modulus;
+        }
 
-        // Final correction
-        if r >= self.modulus {
-            r = r.wrapping_sub(self.modulus);
+        // q = floor(floor(value / 2^(k-1)) * mu / 2^(k+1)) undershoots the true
+        // quotient by at most 2 (HAC 14.42)
+        let quotient = ((value >> (k - 1)) * &self.mu) >> (k + 1);
+        let mut remainder = value - quotient * &self.modulus;
+        while remainder >= self.modulus {
+            remainder -= &self.modulus;
         }
+        remainder
+    }
+
+    /// Computes (a * b) mod N with Barrett reduction.
+    #[must_use]
+    pub fn mul_mod(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        self.reduce(&(a * b))
+    }
 
-        r
+    /// Computes base^exponent mod N with Barrett multiplication.
+    #[must_use]
+    pub fn pow_mod<E: Exponent>(&self, base: &BigUint, exponent: E) -> BigUint {
+        pow_with(self, &self.reduce(base), &exponent, PowStrategy::Auto)
     }
 }
 
-// Specific implementation of Barrett reduction for ModularInt<u64>
-impl BarrettReduction<u64> for ModularInt<u64> {
-    fn barrett_reduce(&self, ctx: &BarrettContext<u64>) -> ModularInt<u64> {
+// Specific implementation of Barrett reduction for ModularInt<BigUint>
+#[cfg(feature = "arbitrary-precision")]
+impl BarrettReduction<BigUint> for ModularInt<BigUint> {
+    fn barrett_reduce(&self, ctx: &BarrettContext<BigUint>) -> Self {
         assert_eq!(self.modulus(), ctx.modulus(), "Modulus mismatch");
-        ModularInt::<u64>::new(ctx.reduce_u64(self.value()), self.modulus())
+        Self::new(ctx.reduce(self.value()), self.modulus().clone())
     }
 
-    fn barrett_mul(&self, other: &ModularInt<u64>, ctx: &BarrettContext<u64>) -> ModularInt<u64> {
+    fn barrett_mul(&self, other: &Self, ctx: &BarrettContext<BigUint>) -> Self {
         assert_eq!(self.modulus(), ctx.modulus(), "Modulus mismatch for self");
         assert_eq!(other.modulus(), ctx.modulus(), "Modulus mismatch for other");
-        ModularInt::<u64>::new(ctx.mul_mod_u64(self.value(), other.value()), self.modulus())
+        Self::new(
+            ctx.mul_mod(self.value(), other.value()),
+            self.modulus().clone(),
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_barrett_reduction_u64() {
@@ -178,16 +372,126 @@ mod tests {
 
     #[test]
     fn test_barrett_large_modulus() {
-        let large_prime = 0xFFFFFFFFFFFFFFFBu64; // 2^64 - 5
+        let large_prime = 0xFFFF_FFFF_FFFF_FFFBu64; // 2^64 - 5
         let ctx = BarrettContext::new(large_prime);
 
-        let a = ModularInt::<u64>::new(0xABCDEF0123456789u64, large_prime);
-        let b = ModularInt::<u64>::new(0x123456789ABCDEFu64, large_prime);
+        let a = ModularInt::<u64>::new(0xABCD_EF01_2345_6789u64, large_prime);
+        let b = ModularInt::<u64>::new(0x0123_4567_89AB_CDEFu64, large_prime);
 
         let result = a.barrett_mul(&b, &ctx);
 
         // Verify using regular multiplication
-        let expected = (a.value() as u128 * b.value() as u128 % large_prime as u128) as u64;
+        #[allow(clippy::cast_possible_truncation)] // the remainder is below the prime
+        let expected =
+            (u128::from(a.value()) * u128::from(b.value()) % u128::from(large_prime)) as u64;
         assert_eq!(result.value(), expected);
     }
+
+    #[test]
+    fn test_barrett_pow_mod() {
+        let large_prime = 0xFFFF_FFFF_FFFF_FFFBu64; // 2^64 - 5
+        let ctx = BarrettContext::new(large_prime);
+        let base = 0xABCD_EF01_2345_6789u64;
+        assert_eq!(
+            ctx.pow_mod(base, 0x0123_4567_89AB_CDEFu64),
+            ModularInt::new(base, large_prime)
+                .pow_mod(0x0123_4567_89AB_CDEF)
+                .value()
+        );
+
+        // Fermat with a two-limb exponent (p - 1)(2^64 + 1)
+        let p = 0xFFFF_FFFF_FFFF_FFC5u64; // 2^64 - 59
+        assert_eq!(BarrettContext::new(p).pow_mod(base, [p - 1, p - 1]), 1);
+
+        let ctx = BarrettContext::<u128>::new(u128::MAX - 158);
+        assert_eq!(ctx.pow_mod(3, u128::MAX - 159), 1);
+        assert_eq!(BarrettContext::new(1u64).pow_mod(3, 0u64), 0);
+    }
+
+    #[test]
+    fn test_barrett_try_new() {
+        assert_eq!(
+            BarrettContext::<u64>::try_new(0).map(|ctx| ctx.modulus()),
+            Err(ModularError::ZeroModulus)
+        );
+        assert_eq!(
+            BarrettContext::<u64>::try_new(18).map(|ctx| ctx.modulus()),
+            Ok(18)
+        );
+    }
+
+    #[test]
+    fn test_barrett_u128() {
+        let large_prime = u128::MAX - 158; // 2^128 - 159
+        let ctx = BarrettContext::<u128>::new(large_prime);
+
+        let a = ModularInt::<u128>::new(0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210, large_prime);
+        let b = ModularInt::<u128>::new(0xF0E1_D2C3_B4A5_9687_7869_5A4B_3C2D_1E0F, large_prime);
+        let result = a.barrett_mul(&b, &ctx);
+        assert_eq!(result.value(), 0x6DFD_0381_76E3_C818_A974_C8A5_09F7_6D9C);
+
+        assert_eq!(ctx.reduce_u128(u128::MAX), 158);
+        assert_eq!(
+            ctx.reduce_u256(u128::MAX, u128::MAX),
+            (158 * 159 + 158) % large_prime
+        );
+
+        let small = BarrettContext::<u128>::new(17);
+        assert_eq!(small.mul_mod_u128(5, 7), 1);
+        assert_eq!(small.reduce_u128(u128::MAX), (u128::MAX % 17));
+        assert_eq!(BarrettContext::<u128>::new(1).reduce_u128(12_345), 0);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_barrett_u128_matches_mul_mod(m in 1u128.., a: u128, b: u128) {
+            let ctx = BarrettContext::<u128>::new(m);
+            let (a, b) = (a % m, b % m);
+            prop_assert_eq!(ctx.mul_mod_u128(a, b), u128::mul_mod(a, b, m));
+        }
+    }
+
+    #[test]
+    fn test_barrett_edge_moduli() {
+        for modulus in [1, 2, 3, (1 << 63) - 1, 1 << 63, (1 << 63) + 1, u64::MAX] {
+            let ctx = BarrettContext::<u64>::new(modulus);
+            let m = u128::from(modulus);
+            for value in [0, 1, m - 1, m, m * m - 1, u128::MAX, u128::MAX - m] {
+                assert_eq!(u128::from(ctx.reduce_u128(value)), value % m);
+            }
+            let a = modulus - 1;
+            assert_eq!(
+                u128::from(ctx.mul_mod_u64(a, a)),
+                u128::from(a) * u128::from(a) % m
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_barrett_reduce_u128_matches_rem(m in 1u64.., value: u128) {
+            let ctx = BarrettContext::<u64>::new(m);
+            prop_assert_eq!(u128::from(ctx.reduce_u128(value)), value % u128::from(m));
+        }
+
+        #[test]
+        fn prop_barrett_reduce_u64_matches_rem(m in 1u64.., value: u64) {
+            let ctx = BarrettContext::<u64>::new(m);
+            prop_assert_eq!(ctx.reduce_u64(value), value % m);
+        }
+
+        #[test]
+        fn prop_barrett_mul_small_moduli(m in 1u64..1 << 16, a: u64, b: u64) {
+            let ctx = BarrettContext::<u64>::new(m);
+            let expected = u128::from(a) * u128::from(b) % u128::from(m);
+            prop_assert_eq!(u128::from(ctx.mul_mod_u64(a, b)), expected);
+        }
+
+        #[test]
+        fn prop_barrett_mul_top_bit_moduli(m in 1u64 << 63.., a: u64, b: u64) {
+            let ctx = BarrettContext::<u64>::new(m);
+            let expected = u128::from(a) * u128::from(b) % u128::from(m);
+            prop_assert_eq!(u128::from(ctx.mul_mod_u64(a, b)), expected);
+        }
+    }
 }
@@ -0,0 +1,444 @@
+// Batch modular arithmetic over slices
+
+use crate::{
+    BarrettContext, ModularError, ModularInt, ModularStorage, MontgomeryContext, MontgomeryInt,
+    MontgomeryWord,
+};
+use std::ops::Mul;
+
+// Element-wise operations on Montgomery-form residues. The loops are branch-free
+// so LLVM can vectorize them; multiplication additionally dispatches to AVX2 or
+// AVX-512 IFMA kernels for small moduli when hardware acceleration is enabled.
+impl MontgomeryContext<u64> {
+    /// Computes a[i] = a[i] + b[i] mod n for residues below n.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices have different lengths.
+    pub fn add_slices(&self, a: &mut [u64], b: &[u64]) {
+        assert_eq!(a.len(), b.len(), "Length mismatch in add_slices");
+        add_residues(a, b, self.modulus());
+    }
+
+    /// Computes a[i] = a[i] - b[i] mod n for residues below n.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices have different lengths.
+    pub fn sub_slices(&self, a: &mut [u64], b: &[u64]) {
+        assert_eq!(a.len(), b.len(), "Length mismatch in sub_slices");
+        sub_residues(a, b, self.modulus());
+    }
+
+    /// Computes the Montgomery products a[i] = a[i] * b[i] * R⁻¹ mod n, so Montgomery
+    /// forms multiply to the Montgomery form of the product.
+    ///
+    /// The inputs must be residues below n: the SIMD kernels for small moduli read
+    /// only the low 32 or 52 bits of each word.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices have different lengths. Debug builds also panic on an
+    /// input that is not below n.
+    pub fn mul_slices(&self, a: &mut [u64], b: &[u64]) {
+        assert_eq!(a.len(), b.len(), "Length mismatch in mul_slices");
+        let (n, n_prime) = (self.modulus(), self.n_prime());
+        debug_assert!(
+            a.iter().chain(b).all(|&x| x < n),
+            "mul_slices needs residues below the modulus"
+        );
+        #[cfg(all(feature = "hardware-acceleration", target_arch = "x86_64"))]
+        if crate::intrinsics::simd::montgomery_mul_slices(a, b, n, n_prime) {
+            return;
+        }
+        for (x, &y) in a.iter_mut().zip(b) {
+            *x = Self::montgomery_reduce(u128::from(*x) * u128::from(y), n, n_prime);
+        }
+    }
+
+    /// Multiplies every Montgomery-form residue by the Montgomery-form scalar.
+    ///
+    /// The residues and the scalar must be below n, as for `mul_slices`.
+    ///
+    /// # Panics
+    ///
+    /// Debug builds panic on an input that is not below n.
+    pub fn scale_by(&self, a: &mut [u64], scalar: u64) {
+        let (n, n_prime) = (self.modulus(), self.n_prime());
+        debug_assert!(
+            scalar < n && a.iter().all(|&x| x < n),
+            "scale_by needs residues below the modulus"
+        );
+        #[cfg(all(feature = "hardware-acceleration", target_arch = "x86_64"))]
+        if crate::intrinsics::simd::montgomery_scale_slice(a, scalar, n, n_prime) {
+            return;
+        }
+        for x in a.iter_mut() {
+            *x = Self::montgomery_reduce(u128::from(*x) * u128::from(scalar), n, n_prime);
+        }
+    }
+
+    /// Returns the Montgomery form of Σ a[i]·b[i] for Montgomery-form inputs.
+    ///
+    /// Products are accumulated unreduced below n·R and reduced once at the end.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices have different lengths.
+    #[must_use]
+    pub fn dot_product(&self, a: &[u64], b: &[u64]) -> u64 {
+        assert_eq!(a.len(), b.len(), "Length mismatch in dot_product");
+        let n = self.modulus();
+        let n_r = u128::from(n) << 64;
+        let mut acc = 0u128;
+        for (&x, &y) in a.iter().zip(b) {
+            // acc < n·R and x·y < n² < n·R, so one subtraction of n·R restores the bound
+            let (sum, overflow) = acc.overflowing_add(u128::from(x) * u128::from(y));
+            acc = if overflow || sum >= n_r {
+                sum.wrapping_sub(n_r)
+            } else {
+                sum
+            };
+        }
+        Self::montgomery_reduce(acc, n, self.n_prime())
+    }
+}
+
+// Element-wise operations on plain residues
+impl BarrettContext<u64> {
+    /// Computes a[i] = a[i] + b[i] mod n for residues below n.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices have different lengths.
+    pub fn add_slices(&self, a: &mut [u64], b: &[u64]) {
+        assert_eq!(a.len(), b.len(), "Length mismatch in add_slices");
+        add_residues(a, b, self.modulus());
+    }
+
+    /// Computes a[i] = a[i] - b[i] mod n for residues below n.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices have different lengths.
+    pub fn sub_slices(&self, a: &mut [u64], b: &[u64]) {
+        assert_eq!(a.len(), b.len(), "Length mismatch in sub_slices");
+        sub_residues(a, b, self.modulus());
+    }
+
+    /// Computes a[i] = a[i] * b[i] mod n.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices have different lengths.
+    pub fn mul_slices(&self, a: &mut [u64], b: &[u64]) {
+        assert_eq!(a.len(), b.len(), "Length mismatch in mul_slices");
+        for (x, &y) in a.iter_mut().zip(b) {
+            *x = self.mul_mod_u64(*x, y);
+        }
+    }
+
+    /// Multiplies every residue by the scalar modulo n.
+    pub fn scale_by(&self, a: &mut [u64], scalar: u64) {
+        for x in a.iter_mut() {
+            *x = self.mul_mod_u64(*x, scalar);
+        }
+    }
+
+    /// Returns Σ a[i]·b[i] mod n, reducing once at the end.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices have different lengths.
+    #[must_use]
+    pub fn dot_product(&self, a: &[u64], b: &[u64]) -> u64 {
+        assert_eq!(a.len(), b.len(), "Length mismatch in dot_product");
+        let fold = (u128::MAX % u128::from(self.modulus()) + 1) % u128::from(self.modulus());
+        let mut acc = 0u128;
+        for (&x, &y) in a.iter().zip(b) {
+            // A wrapped sum is below 2^128 - 2^64, so adding 2^128 mod n cannot wrap again
+            let (sum, overflow) = acc.overflowing_add(u128::from(x) * u128::from(y));
+            acc = if overflow { sum + fold } else { sum };
+        }
+        self.reduce_u128(acc)
+    }
+}
+
+/// Branch-free a[i] + b[i] mod n, shared by both contexts since addition does not
+/// depend on the residue form.
+fn add_residues(a: &mut [u64], b: &[u64], n: u64) {
+    for (x, &y) in a.iter_mut().zip(b) {
+        let (sum, overflow) = x.overflowing_add(y);
+        *x = if overflow || sum >= n {
+            sum.wrapping_sub(n)
+        } else {
+            sum
+        };
+    }
+}
+
+/// Branch-free a[i] - b[i] mod n, shared by both contexts.
+fn sub_residues(a: &mut [u64], b: &[u64], n: u64) {
+    for (x, &y) in a.iter_mut().zip(b) {
+        let (difference, borrow) = x.overflowing_sub(y);
+        *x = if borrow {
+            difference.wrapping_add(n)
+        } else {
+            difference
+        };
+    }
+}
+
+/// Replaces every residue by its inverse with Montgomery's trick: one modular
+/// inversion plus 3(n - 1) multiplications.
+///
+/// ```
+/// use modularity::{batch_inverse, ModularInt};
+///
+/// let mut values = [3, 5, 6].map(|v| ModularInt::new(v, 17u64));
+/// batch_inverse(&mut values);
+/// assert_eq!(values.map(|v| v.value()), [6, 7, 3]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the residues have different moduli or one of them is not invertible.
+pub fn batch_inverse<T: ModularStorage>(values: &mut [ModularInt<T>]) {
+    if let Err(err) = checked_batch_inverse(values) {
+        panic!("{err}");
+    }
+}
+
+/// Inverts every residue in place, returning an error instead of panicking. The
+/// slice is left unchanged on error.
+///
+/// # Errors
+///
+/// Returns `ModularError::ModulusMismatch` if the residues have different moduli and
+/// `ModularError::NotInvertibleAt(i)` if the residue at index i, the first such, is
+/// not invertible.
+pub fn checked_batch_inverse<T: ModularStorage>(
+    values: &mut [ModularInt<T>],
+) -> Result<(), ModularError> {
+    if values.windows(2).any(|w| w[0].modulus() != w[1].modulus()) {
+        return Err(ModularError::ModulusMismatch);
+    }
+    invert_all(values, |v| v.checked_inverse())
+}
+
+// Batch inversion for values that stay in Montgomery form
+impl<T: MontgomeryWord + ModularStorage> MontgomeryInt<'_, T> {
+    /// Replaces every value by its inverse with one modular inversion plus 3(n - 1)
+    /// Montgomery multiplications.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the values have different moduli or one of them is not invertible.
+    pub fn batch_inverse(values: &mut [Self]) {
+        if let Err(err) = Self::checked_batch_inverse(values) {
+            panic!("{err}");
+        }
+    }
+
+    /// Inverts every value in place, returning an error instead of panicking. The
+    /// slice is left unchanged on error.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::ModulusMismatch` if the values have different moduli
+    /// and `ModularError::NotInvertibleAt(i)` if the value at index i, the first
+    /// such, is not invertible.
+    pub fn checked_batch_inverse(values: &mut [Self]) -> Result<(), ModularError> {
+        if values.windows(2).any(|w| w[0].modulus() != w[1].modulus()) {
+            return Err(ModularError::ModulusMismatch);
+        }
+        invert_all(values, Self::checked_inverse)
+    }
+}
+
+/// Montgomery's trick: with prefix products p_i = v_0···v_i, the running inverse
+/// (p_i)⁻¹ yields v_i⁻¹ = (p_i)⁻¹·p_(i-1) and then (p_(i-1))⁻¹ = (p_i)⁻¹·v_i.
+fn invert_all<V: Copy + Mul<Output = V>>(
+    values: &mut [V],
+    invert: impl Fn(V) -> Result<V, ModularError>,
+) -> Result<(), ModularError> {
+    let Some((&first, rest)) = values.split_first() else {
+        return Ok(());
+    };
+    let mut prefix = Vec::with_capacity(values.len());
+    prefix.push(first);
+    for &v in rest {
+        prefix.push(prefix[prefix.len() - 1] * v);
+    }
+
+    // The product is a unit exactly when every factor is, so only the error path
+    // needs to look at the elements one by one
+    let Ok(mut inverse) = invert(prefix[prefix.len() - 1]) else {
+        let index = values.iter().position(|&v| invert(v).is_err());
+        return Err(ModularError::NotInvertibleAt(index.unwrap_or(0)));
+    };
+    for i in (1..values.len()).rev() {
+        let value = values[i];
+        values[i] = inverse * prefix[i - 1];
+        inverse = inverse * value;
+    }
+    values[0] = inverse;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MontgomeryArithmetic;
+
+    // Odd moduli on both sides of the 32- and 52-bit SIMD limits
+    const MODULI: [u64; 7] = [
+        17,
+        998_244_353,
+        (1 << 32) - 5,
+        (1 << 32) + 15,
+        (1 << 52) - 47,
+        (1 << 52) + 21,
+        u64::MAX - 58,
+    ];
+
+    fn residues(len: usize, seed: u64, modulus: u64) -> Vec<u64> {
+        let mut x = seed;
+        (0..len)
+            .map(|_| {
+                x = x
+                    .wrapping_mul(0x5851_F42D_4C95_7F2D)
+                    .wrapping_add(0x1405_7B7E_F767_814F);
+                (x >> 7) % modulus
+            })
+            .collect()
+    }
+
+    fn montgomery(values: &[u64], ctx: &MontgomeryContext<u64>) -> Vec<u64> {
+        values
+            .iter()
+            .map(|&v| ModularInt::new(v, ctx.modulus()).to_montgomery(ctx).value())
+            .collect()
+    }
+
+    #[test]
+    fn test_montgomery_slices_match_scalar() {
+        for n in MODULI {
+            let ctx = MontgomeryContext::new(n);
+            // 37 is not a multiple of any lane count, so the tails are exercised too
+            let (a, b) = (residues(37, n, n), residues(37, !n, n));
+            let (am, bm) = (montgomery(&a, &ctx), montgomery(&b, &ctx));
+            let scalar = montgomery(&[n / 3], &ctx)[0];
+
+            let mut product = am.clone();
+            ctx.mul_slices(&mut product, &bm);
+            let mut scaled = am.clone();
+            ctx.scale_by(&mut scaled, scalar);
+            let mut sum = am.clone();
+            ctx.add_slices(&mut sum, &bm);
+            let mut difference = am.clone();
+            ctx.sub_slices(&mut difference, &bm);
+
+            let mut dot = ModularInt::new(0, n);
+            for i in 0..a.len() {
+                let (x, y) = (ModularInt::new(a[i], n), ModularInt::new(b[i], n));
+                let expected = montgomery(&[(x * y).value()], &ctx)[0];
+                assert_eq!(product[i], expected, "mul mod {n}");
+                let expected = montgomery(&[(x * ModularInt::new(n / 3, n)).value()], &ctx)[0];
+                assert_eq!(scaled[i], expected, "scale mod {n}");
+                assert_eq!(sum[i], montgomery(&[(x + y).value()], &ctx)[0]);
+                assert_eq!(difference[i], montgomery(&[(x - y).value()], &ctx)[0]);
+                dot += x * y;
+            }
+            assert_eq!(
+                ctx.dot_product(&am, &bm),
+                montgomery(&[dot.value()], &ctx)[0]
+            );
+        }
+    }
+
+    #[test]
+    fn test_barrett_slices_match_scalar() {
+        for n in MODULI.into_iter().chain([1 << 63, u64::MAX]) {
+            let ctx = BarrettContext::new(n);
+            let (a, b) = (residues(40, n, n), residues(40, !n, n));
+            let mut product = a.clone();
+            ctx.mul_slices(&mut product, &b);
+            let mut scaled = a.clone();
+            ctx.scale_by(&mut scaled, n - 1);
+            let mut sum = a.clone();
+            ctx.add_slices(&mut sum, &b);
+            let mut difference = a.clone();
+            ctx.sub_slices(&mut difference, &b);
+            let mut dot = ModularInt::new(0, n);
+            for i in 0..a.len() {
+                let (x, y) = (ModularInt::new(a[i], n), ModularInt::new(b[i], n));
+                assert_eq!(product[i], (x * y).value());
+                assert_eq!(scaled[i], (-x).value());
+                assert_eq!(sum[i], (x + y).value());
+                assert_eq!(difference[i], (x - y).value());
+                dot += x * y;
+            }
+            assert_eq!(ctx.dot_product(&a, &b), dot.value());
+        }
+    }
+
+    #[test]
+    fn test_batch_inverse() {
+        let n = 998_244_353u64;
+        let mut values: Vec<_> = residues(25, 7, n)
+            .into_iter()
+            .map(|v| ModularInt::new(v.max(1), n))
+            .collect();
+        let expected: Vec<_> = values.iter().map(ModularInt::<u64>::inverse_mod).collect();
+        batch_inverse(&mut values);
+        assert_eq!(values, expected);
+
+        let ctx = MontgomeryContext::new(n);
+        let mut forms: Vec<_> = expected
+            .iter()
+            .map(|v| ctx.to_montgomery(v.value()))
+            .collect();
+        MontgomeryInt::batch_inverse(&mut forms);
+        let back: Vec<_> = forms
+            .into_iter()
+            .map(MontgomeryInt::from_montgomery)
+            .collect();
+        assert_eq!(
+            back,
+            values
+                .iter()
+                .map(ModularInt::<u64>::inverse_mod)
+                .collect::<Vec<_>>()
+        );
+
+        let mut single = [ModularInt::new(2u8, 5)];
+        batch_inverse(&mut single);
+        assert_eq!(single[0].value(), 3);
+        batch_inverse::<u64>(&mut []);
+    }
+
+    #[test]
+    fn test_batch_inverse_errors() {
+        let mut values = [4, 7, 6, 9, 10].map(|v| ModularInt::new(v, 15u64));
+        let before = values;
+        assert_eq!(
+            checked_batch_inverse(&mut values),
+            Err(ModularError::NotInvertibleAt(2))
+        );
+        assert_eq!(values, before);
+
+        let mut mixed = [ModularInt::new(1u64, 7), ModularInt::new(1, 11)];
+        assert_eq!(
+            checked_batch_inverse(&mut mixed),
+            Err(ModularError::ModulusMismatch)
+        );
+
+        let ctx = MontgomeryContext::new(21u64);
+        let mut forms = [1, 2, 7].map(|v| ctx.to_montgomery(v));
+        assert_eq!(
+            MontgomeryInt::checked_batch_inverse(&mut forms),
+            Err(ModularError::NotInvertibleAt(2))
+        );
+    }
+}
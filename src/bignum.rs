@@ -0,0 +1,514 @@
+// Arbitrary-precision modular integers backed by num-bigint
+
+use crate::montgomery::{from_limbs, montgomery_mul_limbs, to_limbs};
+use crate::pow::MONTGOMERY_MIN_EXPONENT_BITS;
+use crate::{
+    pow_with, Exponent, ModularError, ModularInt, MontgomeryContext, PowDomain, PowStrategy,
+};
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+// Construction and arithmetic for bignum residues
+impl ModularInt<BigUint> {
+    /// Returns the value of the modular integer.
+    #[must_use]
+    pub const fn value(&self) -> &BigUint {
+        &self.value
+    }
+
+    /// Returns the modulus of the modular integer.
+    #[must_use]
+    pub const fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    /// Performs modular addition.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the moduli differ.
+    #[must_use]
+    pub fn add_mod(&self, other: &Self) -> Self {
+        self + other
+    }
+
+    /// Performs modular subtraction.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the moduli differ.
+    #[must_use]
+    pub fn sub_mod(&self, other: &Self) -> Self {
+        self - other
+    }
+
+    /// Performs modular multiplication.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the moduli differ.
+    #[must_use]
+    pub fn mul_mod(&self, other: &Self) -> Self {
+        self * other
+    }
+
+    /// Performs modular addition, returning an error on modulus mismatch.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::ModulusMismatch` if the moduli differ.
+    pub fn checked_add(&self, other: &Self) -> Result<Self, ModularError> {
+        self.check_modulus(other)?;
+        Ok(self + other)
+    }
+
+    /// Performs modular subtraction, returning an error on modulus mismatch.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::ModulusMismatch` if the moduli differ.
+    pub fn checked_sub(&self, other: &Self) -> Result<Self, ModularError> {
+        self.check_modulus(other)?;
+        Ok(self - other)
+    }
+
+    /// Performs modular multiplication, returning an error on modulus mismatch.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::ModulusMismatch` if the moduli differ.
+    pub fn checked_mul(&self, other: &Self) -> Result<Self, ModularError> {
+        self.check_modulus(other)?;
+        Ok(self * other)
+    }
+
+    /// Computes the modular inverse: self^(-1) mod modulus.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the inverse does not exist (i.e., if gcd(value, modulus) != 1).
+    #[must_use]
+    pub fn inverse_mod(&self) -> Self {
+        match self.checked_inverse() {
+            Ok(inverse) => inverse,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Computes the modular inverse, returning an error if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::NotInvertible` if gcd(value, modulus) != 1.
+    pub fn checked_inverse(&self) -> Result<Self, ModularError> {
+        if self.modulus.is_one() {
+            // Every residue is 0 modulo 1, and 0 * 0 = 0 = 1
+            return Ok(self.clone());
+        }
+        let inverse = self
+            .value
+            .modinv(&self.modulus)
+            .ok_or(ModularError::NotInvertible)?;
+        Ok(Self {
+            value: inverse,
+            modulus: self.modulus.clone(),
+        })
+    }
+
+    /// Computes the modular exponentiation: self^exponent mod modulus.
+    ///
+    /// Odd moduli run the loop in Montgomery form with word-by-word (CIOS) reduction.
+    #[must_use]
+    pub fn pow<E: Exponent>(&self, exponent: E) -> Self {
+        self.pow_with(exponent, PowStrategy::Auto)
+    }
+
+    /// Computes self^exponent mod modulus with an explicit exponentiation strategy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a window strategy is given a width outside 1 to 8.
+    #[must_use]
+    pub fn pow_with<E: Exponent>(&self, exponent: E, strategy: PowStrategy) -> Self {
+        let value = if exponent.bits() >= MONTGOMERY_MIN_EXPONENT_BITS {
+            MontgomeryContext::try_new(self.modulus.clone()).map_or_else(
+                |_| self.pow_plain(&exponent, strategy),
+                |ctx| {
+                    let domain = LimbDomain::new(&ctx);
+                    let base = domain.to_montgomery(&self.value, &ctx);
+                    domain.from_montgomery(&pow_with(&domain, &base, &exponent, strategy))
+                },
+            )
+        } else {
+            self.pow_plain(&exponent, strategy)
+        };
+        Self {
+            value,
+            modulus: self.modulus.clone(),
+        }
+    }
+
+    /// Computes the modular exponentiation for a `u64` exponent.
+    #[must_use]
+    pub fn pow_mod(&self, exponent: u64) -> Self {
+        self.pow(exponent)
+    }
+
+    /// Computes self^exponent mod modulus for a signed exponent.
+    ///
+    /// A negative exponent raises the inverse to the absolute value of the exponent.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::NotInvertible` if the exponent is negative and the
+    /// inverse does not exist.
+    pub fn checked_pow(&self, exponent: i64) -> Result<Self, ModularError> {
+        if exponent < 0 {
+            Ok(self.checked_inverse()?.pow(exponent.unsigned_abs()))
+        } else {
+            Ok(self.pow(exponent.unsigned_abs()))
+        }
+    }
+
+    /// Exponentiation with a division after every multiplication, for even moduli.
+    fn pow_plain<E: Exponent>(&self, exponent: &E, strategy: PowStrategy) -> BigUint {
+        let domain = PlainDomain {
+            modulus: &self.modulus,
+        };
+        pow_with(&domain, &self.value, exponent, strategy)
+    }
+
+    /// Returns an error unless both operands share a modulus.
+    fn check_modulus(&self, other: &Self) -> Result<(), ModularError> {
+        if self.modulus == other.modulus {
+            Ok(())
+        } else {
+            Err(ModularError::ModulusMismatch)
+        }
+    }
+}
+
+/// Montgomery-form residues as fixed-length limb vectors, so the exponentiation loop
+/// runs CIOS directly without converting to and from `BigUint` at every step.
+struct LimbDomain {
+    modulus: Vec<u64>,
+    n_prime: u64,
+    one: Vec<u64>, // R mod N, the Montgomery form of 1
+}
+
+impl LimbDomain {
+    fn new(ctx: &MontgomeryContext<BigUint>) -> Self {
+        let mut domain = Self {
+            modulus: ctx.modulus().to_u64_digits(),
+            n_prime: ctx.limb_n_prime(),
+            one: Vec::new(),
+        };
+        // R mod N = R² * R⁻¹, one reduction instead of a bignum division
+        domain.one = domain.from_montgomery_limbs(&to_limbs(ctx.r_squared(), domain.modulus.len()));
+        domain
+    }
+
+    fn to_montgomery(&self, value: &BigUint, ctx: &MontgomeryContext<BigUint>) -> Vec<u64> {
+        let len = self.modulus.len();
+        self.mul(&to_limbs(value, len), &to_limbs(ctx.r_squared(), len))
+    }
+
+    #[allow(clippy::wrong_self_convention)] // converts the argument out of Montgomery form
+    fn from_montgomery(&self, value: &[u64]) -> BigUint {
+        from_limbs(&self.from_montgomery_limbs(value))
+    }
+
+    #[allow(clippy::wrong_self_convention)] // converts the argument out of Montgomery form
+    fn from_montgomery_limbs(&self, value: &[u64]) -> Vec<u64> {
+        let mut unit = vec![0; self.modulus.len()];
+        unit[0] = 1;
+        self.mul(&value.to_vec(), &unit)
+    }
+}
+
+impl PowDomain for LimbDomain {
+    type Element = Vec<u64>;
+
+    fn one(&self) -> Vec<u64> {
+        self.one.clone()
+    }
+
+    fn mul(&self, a: &Vec<u64>, b: &Vec<u64>) -> Vec<u64> {
+        let mut product = vec![0; self.modulus.len()];
+        montgomery_mul_limbs(a, b, &self.modulus, self.n_prime, &mut product);
+        product
+    }
+}
+
+/// Bignum residues multiplied with a division after every product.
+struct PlainDomain<'a> {
+    modulus: &'a BigUint,
+}
+
+impl PowDomain for PlainDomain<'_> {
+    type Element = BigUint;
+
+    fn one(&self) -> BigUint {
+        BigUint::one() % self.modulus
+    }
+
+    fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        a * b % self.modulus
+    }
+}
+
+// Implement Add trait for &ModularInt<BigUint>
+impl Add for &ModularInt<BigUint> {
+    type Output = ModularInt<BigUint>;
+
+    fn add(self, other: Self) -> Self::Output {
+        assert_eq!(self.modulus, other.modulus, "Modulus mismatch in addition");
+        let mut value = &self.value + &other.value;
+        if value >= self.modulus {
+            value -= &self.modulus;
+        }
+        ModularInt {
+            value,
+            modulus: self.modulus.clone(),
+        }
+    }
+}
+
+// Implement Sub trait for &ModularInt<BigUint>
+impl Sub for &ModularInt<BigUint> {
+    type Output = ModularInt<BigUint>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        assert_eq!(
+            self.modulus, other.modulus,
+            "Modulus mismatch in subtraction"
+        );
+        let value = if self.value >= other.value {
+            &self.value - &other.value
+        } else {
+            &self.modulus - (&other.value - &self.value)
+        };
+        ModularInt {
+            value,
+            modulus: self.modulus.clone(),
+        }
+    }
+}
+
+// Implement Mul trait for &ModularInt<BigUint>
+impl Mul for &ModularInt<BigUint> {
+    type Output = ModularInt<BigUint>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        assert_eq!(
+            self.modulus, other.modulus,
+            "Modulus mismatch in multiplication"
+        );
+        ModularInt {
+            value: &self.value * &other.value % &self.modulus,
+            modulus: self.modulus.clone(),
+        }
+    }
+}
+
+// Implement Neg trait for &ModularInt<BigUint>
+impl Neg for &ModularInt<BigUint> {
+    type Output = ModularInt<BigUint>;
+
+    fn neg(self) -> Self::Output {
+        let value = if self.value.is_zero() {
+            BigUint::zero()
+        } else {
+            &self.modulus - &self.value
+        };
+        ModularInt {
+            value,
+            modulus: self.modulus.clone(),
+        }
+    }
+}
+
+/// Implements the by-value operator and its assigning form in terms of the by-reference one.
+macro_rules! impl_bignum_owned_ops {
+    ($(($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident)),* $(,)?) => {
+        $(
+            impl $trait for ModularInt<BigUint> {
+                type Output = Self;
+
+                fn $method(self, other: Self) -> Self::Output {
+                    (&self).$method(&other)
+                }
+            }
+
+            impl $assign_trait for ModularInt<BigUint> {
+                fn $assign_method(&mut self, other: Self) {
+                    *self = (&*self).$method(&other);
+                }
+            }
+        )*
+    };
+}
+
+impl_bignum_owned_ops!(
+    (Add, add, AddAssign, add_assign),
+    (Sub, sub, SubAssign, sub_assign),
+    (Mul, mul, MulAssign, mul_assign),
+);
+
+// Implement Neg trait for ModularInt<BigUint>
+impl Neg for ModularInt<BigUint> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        -&self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BarrettContext, BarrettReduction, MontgomeryArithmetic, MontgomeryWord};
+    use proptest::prelude::*;
+
+    /// 2^521 - 1, a Mersenne prime spanning nine 64-bit limbs.
+    fn m521() -> BigUint {
+        (BigUint::one() << 521u32) - 1u32
+    }
+
+    /// An odd 2048-bit modulus with every limb populated, standing in for an RSA modulus.
+    fn rsa_like_modulus() -> BigUint {
+        let limbs: Vec<u64> = (1..=32u64)
+            .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ 0xD1B5_4A32_D192_ED03)
+            .collect();
+        from_limbs(&limbs) | BigUint::one() | (BigUint::one() << 2047u32)
+    }
+
+    fn big(value: u64) -> BigUint {
+        BigUint::from(value)
+    }
+
+    #[test]
+    fn test_bignum_arithmetic() {
+        let m = big(17);
+        let a = ModularInt::new(big(5), m.clone());
+        let b = ModularInt::new(big(7), m);
+
+        assert_eq!((&a + &b).value(), &big(12));
+        assert_eq!((&a - &b).value(), &big(15));
+        assert_eq!((&a * &b).value(), &big(1));
+        assert_eq!((-&a).value(), &big(12));
+        assert_eq!(a.inverse_mod().value(), &big(7));
+        assert_eq!(a.pow_mod(16).value(), &big(1));
+        assert_eq!(a.checked_pow(-1).map(|c| c.value().clone()), Ok(big(7)));
+
+        let mut c = a.clone();
+        c *= b.clone();
+        c -= a;
+        c += b;
+        assert_eq!(c.value(), &big(3)); // 5 * 7 - 5 + 7 = 37 = 3 mod 17
+
+        assert_eq!(
+            ModularInt::try_new(big(1), BigUint::zero()),
+            Err(ModularError::ZeroModulus)
+        );
+        assert_eq!(
+            ModularInt::new(big(6), big(9)).checked_inverse(),
+            Err(ModularError::NotInvertible)
+        );
+        assert_eq!(
+            ModularInt::new(big(1), big(9)).checked_add(&ModularInt::new(big(1), big(7))),
+            Err(ModularError::ModulusMismatch)
+        );
+    }
+
+    #[test]
+    fn test_bignum_pow_fermat() {
+        let p = m521();
+        let a = ModularInt::new(big(3), p.clone());
+        assert!(a.pow(&p - 1u32).value().is_one());
+        assert_eq!(a.pow(&p), a);
+        assert_eq!(a.pow_with(&p, PowStrategy::FixedWindow(5)), a);
+        assert_eq!(
+            a.inverse_mod().pow(2u64).mul_mod(&a.pow(2u64)).value(),
+            &big(1)
+        );
+    }
+
+    #[test]
+    fn test_bignum_pow_matches_modpow() {
+        let n = rsa_like_modulus();
+        let base = &n / 3u32;
+        let exponent = (&n >> 7u32) + 65_537u32;
+        let expected = base.modpow(&exponent, &n);
+
+        let a = ModularInt::new(base.clone(), n.clone());
+        assert_eq!(a.pow(&exponent).value(), &expected);
+
+        // An even modulus takes the plain path
+        let even = &n + 1u32;
+        assert_eq!(
+            ModularInt::new(base.clone(), even.clone())
+                .pow(&exponent)
+                .value(),
+            &base.modpow(&exponent, &even)
+        );
+    }
+
+    #[test]
+    fn test_bignum_montgomery_context() {
+        let n = rsa_like_modulus();
+        let ctx = MontgomeryContext::new(n.clone());
+        let a = ModularInt::new(&n - 12_345u32, n.clone());
+        let b = ModularInt::new(&n >> 1u32, n);
+
+        let product = a
+            .to_montgomery(&ctx)
+            .montgomery_mul(&b.to_montgomery(&ctx), &ctx)
+            .from_montgomery(&ctx);
+        assert_eq!(product, &a * &b);
+
+        assert_eq!(
+            MontgomeryContext::try_new(big(10)).map(|ctx| ctx.modulus().clone()),
+            Err(ModularError::EvenModulus)
+        );
+    }
+
+    #[test]
+    fn test_bignum_barrett_context() {
+        let n = rsa_like_modulus();
+        let ctx = BarrettContext::new(n.clone());
+        let a = ModularInt::new(&n - 1u32, n.clone());
+        let b = ModularInt::new(&n / 5u32, n.clone());
+
+        assert_eq!(a.barrett_mul(&b, &ctx), &a * &b);
+        assert_eq!(a.barrett_mul(&a, &ctx).value(), &big(1));
+        assert_eq!(ctx.reduce(&(&n * &n * 3u32 + 4u32)), big(4)); // beyond n², by division
+        assert_eq!(
+            ctx.pow_mod(&big(2), 1_000u64),
+            big(2).modpow(&big(1_000), &n)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_cios_matches_division(
+            limbs in prop::collection::vec(any::<u64>(), 1..6),
+            a in prop::collection::vec(any::<u64>(), 1..6),
+            b in prop::collection::vec(any::<u64>(), 1..6),
+        ) {
+            let n = from_limbs(&limbs) | BigUint::one();
+            let ctx = MontgomeryContext::new(n.clone());
+            let (a, b) = (from_limbs(&a) % &n, from_limbs(&b) % &n);
+            let (ma, mb) = (
+                BigUint::montgomery_product(&ctx, &a, ctx.r_squared()),
+                BigUint::montgomery_product(&ctx, &b, ctx.r_squared()),
+            );
+            let product = BigUint::montgomery_redc(&ctx, &BigUint::montgomery_product(&ctx, &ma, &mb));
+            prop_assert_eq!(product, &a * &b % &n);
+
+            let barrett = BarrettContext::new(n.clone());
+            prop_assert_eq!(barrett.mul_mod(&a, &b), &a * &b % &n);
+        }
+    }
+}
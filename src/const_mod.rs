@@ -0,0 +1,310 @@
+// Modular integers with a compile-time modulus
+
+use crate::{BarrettContext, ModularError, ModularInt, ModularStorage, MontgomeryContext};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Represents an integer modulo the compile-time modulus `M`.
+///
+/// The modulus is part of the type, so a `ConstModInt<M>` is a single `u64`, binary
+/// operations need no runtime modulus checks, and values with different moduli cannot
+/// be mixed. Multiplication uses Barrett reduction and exponentiation uses Montgomery
+/// form (when `M` is odd), with all constants evaluated at compile time.
+///
+/// ```compile_fail
+/// use modularity::ConstModInt;
+///
+/// let a = ConstModInt::<17>::new(5);
+/// let b = ConstModInt::<19>::new(5);
+/// let _ = a + b; // mismatched moduli are a type error
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConstModInt<const M: u64> {
+    value: u64,
+}
+
+impl<const M: u64> ConstModInt<M> {
+    /// Rejects a zero modulus when the type is instantiated.
+    const VALID_MODULUS: () = assert!(M > 0, "Modulus cannot be zero");
+
+    /// floor((2^128 - 1) / M), the Barrett constant for reducing 128-bit products.
+    const BARRETT_MU: u128 = if M == 0 { 0 } else { u128::MAX / M as u128 };
+
+    /// -M⁻¹ mod 2^64, only meaningful when M is odd.
+    const MONTGOMERY_N_PRIME: u64 = if M % 2 == 1 {
+        MontgomeryContext::<u64>::compute_n_prime(M)
+    } else {
+        0
+    };
+
+    /// R² mod M with R = 2^64, only meaningful when M is odd.
+    const MONTGOMERY_R_SQUARED: u64 = if M % 2 == 1 {
+        MontgomeryContext::<u64>::compute_r_squared(M)
+    } else {
+        0
+    };
+
+    /// The additive identity.
+    pub const ZERO: Self = Self::new(0);
+
+    /// The multiplicative identity.
+    pub const ONE: Self = Self::new(1);
+
+    /// Creates a new ConstModInt, reducing the value modulo `M`.
+    #[must_use]
+    pub const fn new(value: u64) -> Self {
+        let () = Self::VALID_MODULUS;
+        Self { value: value % M }
+    }
+
+    /// Returns the value of the modular integer.
+    #[must_use]
+    pub const fn value(self) -> u64 {
+        self.value
+    }
+
+    /// Returns the modulus `M`.
+    #[must_use]
+    pub const fn modulus(self) -> u64 {
+        M
+    }
+
+    /// Computes the modular exponentiation: self^exponent mod M.
+    ///
+    /// Uses square-and-multiply in Montgomery form for odd moduli and Barrett
+    /// multiplication otherwise.
+    #[must_use]
+    pub const fn pow_mod(self, exponent: u64) -> Self {
+        let mut exp = exponent;
+
+        if M % 2 == 1 {
+            let n_prime = Self::MONTGOMERY_N_PRIME;
+            let r_squared = Self::MONTGOMERY_R_SQUARED as u128;
+            let mut base = MontgomeryContext::<u64>::montgomery_reduce(
+                self.value as u128 * r_squared,
+                M,
+                n_prime,
+            );
+            // R mod M, i.e. 1 in Montgomery form
+            let mut result = MontgomeryContext::<u64>::montgomery_reduce(r_squared, M, n_prime);
+
+            while exp > 0 {
+                if exp & 1 == 1 {
+                    result = MontgomeryContext::<u64>::montgomery_reduce(
+                        result as u128 * base as u128,
+                        M,
+                        n_prime,
+                    );
+                }
+                base = MontgomeryContext::<u64>::montgomery_reduce(
+                    base as u128 * base as u128,
+                    M,
+                    n_prime,
+                );
+                exp >>= 1;
+            }
+
+            Self {
+                value: MontgomeryContext::<u64>::montgomery_reduce(result as u128, M, n_prime),
+            }
+        } else {
+            let mut base = self.value;
+            let mut result = 1 % M;
+
+            while exp > 0 {
+                if exp & 1 == 1 {
+                    result = Self::mul_reduce(result, base);
+                }
+                base = Self::mul_reduce(base, base);
+                exp >>= 1;
+            }
+
+            Self { value: result }
+        }
+    }
+
+    /// Computes the modular inverse: self^(-1) mod M.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the inverse does not exist (i.e., if gcd(self.value, M) != 1).
+    #[must_use]
+    pub fn inverse_mod(self) -> Self {
+        Self {
+            value: ModularInt::<u64>::new(self.value, M).inverse_mod().value(),
+        }
+    }
+
+    /// Computes the modular inverse, returning an error if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::NotInvertible` if gcd(self.value, M) != 1.
+    pub fn checked_inverse(self) -> Result<Self, ModularError> {
+        let inverse = ModularInt::<u64>::new(self.value, M).checked_inverse()?;
+        Ok(Self {
+            value: inverse.value(),
+        })
+    }
+
+    /// Computes (a * b) mod M with Barrett reduction.
+    const fn mul_reduce(a: u64, b: u64) -> u64 {
+        BarrettContext::<u64>::barrett_reduce_u128(a as u128 * b as u128, M, Self::BARRETT_MU)
+    }
+}
+
+/// The zero residue, built through `new` so that `M` is checked as for any other
+/// value.
+///
+/// ```compile_fail
+/// let _ = modularity::ConstModInt::<0>::default(); // a zero modulus is rejected
+/// ```
+impl<const M: u64> Default for ConstModInt<M> {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl<const M: u64> From<u64> for ConstModInt<M> {
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const M: u64> From<ConstModInt<M>> for ModularInt<u64> {
+    fn from(value: ConstModInt<M>) -> Self {
+        Self::new(value.value, M)
+    }
+}
+
+// Implement Add trait for ConstModInt
+impl<const M: u64> Add for ConstModInt<M> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self {
+            value: u64::add_mod(self.value, other.value, M),
+        }
+    }
+}
+
+// Implement AddAssign trait for ConstModInt
+impl<const M: u64> AddAssign for ConstModInt<M> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+// Implement Sub trait for ConstModInt
+impl<const M: u64> Sub for ConstModInt<M> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self {
+            value: u64::sub_mod(self.value, other.value, M),
+        }
+    }
+}
+
+// Implement SubAssign trait for ConstModInt
+impl<const M: u64> SubAssign for ConstModInt<M> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+// Implement Mul trait for ConstModInt
+impl<const M: u64> Mul for ConstModInt<M> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        Self {
+            value: Self::mul_reduce(self.value, other.value),
+        }
+    }
+}
+
+// Implement MulAssign trait for ConstModInt
+impl<const M: u64> MulAssign for ConstModInt<M> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+// Implement Neg trait for ConstModInt
+impl<const M: u64> Neg for ConstModInt<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            value: u64::neg_mod(self.value, M),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LARGE_PRIME: u64 = 0xFFFF_FFFF_FFFF_FFFB; // 2^64 - 5
+
+    #[test]
+    fn test_const_mod_int_arithmetic() {
+        type F17 = ConstModInt<17>;
+        let a = F17::new(5);
+        let b = F17::new(7);
+
+        assert_eq!(F17::new(20).value(), 3);
+        assert_eq!((a + b).value(), 12);
+        assert_eq!((a - b).value(), 15);
+        assert_eq!((a * b).value(), 1);
+        assert_eq!((-a).value(), 12);
+        assert_eq!(a.pow_mod(16), F17::ONE);
+        assert_eq!((a * a.inverse_mod()), F17::ONE);
+        assert_eq!(
+            ConstModInt::<18>::new(3).checked_inverse(),
+            Err(ModularError::NotInvertible)
+        );
+    }
+
+    #[test]
+    fn test_const_mod_int_is_one_word() {
+        assert_eq!(std::mem::size_of::<ConstModInt<17>>(), 8);
+        assert_eq!(std::mem::size_of::<ModularInt<u64>>(), 16);
+    }
+
+    #[test]
+    fn test_const_mod_int_evaluates_at_compile_time() {
+        const CUBE: ConstModInt<17> = ConstModInt::new(5).pow_mod(3);
+        assert_eq!(CUBE.value(), 125 % 17);
+    }
+
+    #[test]
+    fn test_const_mod_int_matches_modular_int_large_modulus() {
+        let x = 0xABCD_EF01_2345_6789u64;
+        let y = 0x0123_4567_89AB_CDEFu64;
+        let a = ConstModInt::<LARGE_PRIME>::new(x);
+        let b = ConstModInt::<LARGE_PRIME>::new(y);
+        let (ra, rb) = (
+            ModularInt::<u64>::new(x, LARGE_PRIME),
+            ModularInt::<u64>::new(y, LARGE_PRIME),
+        );
+
+        assert_eq!(ModularInt::from(a * b), ra * rb);
+        assert_eq!(ModularInt::from(a + b), ra + rb);
+        assert_eq!(ModularInt::from(a - b), ra - rb);
+        assert_eq!(ModularInt::from(a.pow_mod(y)), ra.pow_mod(y));
+
+        let minus_one = ConstModInt::<LARGE_PRIME>::new(LARGE_PRIME - 1);
+        assert_eq!(minus_one * minus_one, ConstModInt::ONE);
+    }
+
+    #[test]
+    fn test_const_mod_int_even_modulus() {
+        const M: u64 = 1 << 63;
+        let a = ConstModInt::<M>::new(M - 1);
+        assert_eq!(a * a, ConstModInt::ONE);
+        assert_eq!(a.pow_mod(3), a);
+        assert_eq!(ConstModInt::<1000>::new(7).pow_mod(3).value(), 343);
+        assert_eq!(ConstModInt::<1>::new(7).pow_mod(0).value(), 0);
+    }
+}
@@ -0,0 +1,293 @@
+// Chinese Remainder Theorem reconstruction
+
+use crate::{ModularError, ModularInt, ModularStorage};
+#[cfg(feature = "arbitrary-precision")]
+use num_bigint::BigUint;
+use num_integer::Integer;
+
+/// Solves the system x ≡ r_i (mod m_i), returning x modulo lcm(m_i).
+///
+/// The moduli need not be pairwise coprime: congruences are merged one at a time,
+/// and a pair whose moduli share a factor g is consistent only if the residues agree
+/// modulo g. An empty system has the solution 0 modulo 1.
+///
+/// Returns `None` if the congruences are inconsistent or the combined modulus does
+/// not fit in a `u128`; `crt_biguint` handles any size.
+///
+/// ```
+/// use modularity::{crt, ModularInt};
+///
+/// let x = crt(&[ModularInt::new(2, 3), ModularInt::new(3, 5), ModularInt::new(2, 7)]);
+/// assert_eq!(x, Some(ModularInt::new(23, 105)));
+///
+/// // 6 and 4 share the factor 2, and 1 and 2 disagree modulo 2
+/// assert_eq!(crt(&[ModularInt::new(1, 6), ModularInt::new(2, 4)]), None);
+/// ```
+#[must_use]
+pub fn crt(residues: &[ModularInt<u64>]) -> Option<ModularInt<u128>> {
+    let (mut value, mut modulus) = (0u128, 1u128);
+    for residue in residues {
+        let m = u128::from(residue.modulus());
+        #[allow(clippy::cast_possible_truncation)] // both remainders are below m < 2^64
+        let (t, cofactor) = merge_step((value % m) as u64, (modulus % m) as u64, *residue)?;
+        // value + modulus·t < modulus·cofactor, the new modulus, so nothing overflows
+        // once the new modulus fits
+        let combined = modulus.checked_mul(u128::from(cofactor))?;
+        value += modulus * u128::from(t);
+        modulus = combined;
+    }
+    Some(ModularInt::new(value, modulus))
+}
+
+/// Solves the system x ≡ r_i (mod m_i) with no bound on the combined modulus.
+///
+/// Returns `None` if the congruences are inconsistent. See `crt`.
+#[cfg(feature = "arbitrary-precision")]
+#[must_use]
+pub fn crt_biguint(residues: &[ModularInt<u64>]) -> Option<ModularInt<BigUint>> {
+    let (mut value, mut modulus) = (BigUint::from(0u8), BigUint::from(1u8));
+    for residue in residues {
+        let m = residue.modulus();
+        let reduce = |x: &BigUint| (x % m).try_into().unwrap_or_default();
+        let (t, cofactor) = merge_step(reduce(&value), reduce(&modulus), *residue)?;
+        value += &modulus * t;
+        modulus *= cofactor;
+    }
+    Some(ModularInt::new(value, modulus))
+}
+
+/// Merges x ≡ a (mod m1) with x ≡ r (mod m2), given a and m1 reduced modulo m2.
+///
+/// Returns (t, m2 / g) with g = gcd(m1, m2), such that a + m1·t is the combined
+/// solution modulo m1·m2 / g, or `None` if the congruences are inconsistent.
+fn merge_step(a: u64, m1: u64, residue: ModularInt<u64>) -> Option<(u64, u64)> {
+    let m2 = residue.modulus();
+    let g = m1.gcd(&m2);
+    let difference = u64::sub_mod(residue.value(), a, m2);
+    if !difference.is_multiple_of(g) {
+        return None;
+    }
+
+    // m1·t ≡ difference (mod m2)  <=>  (m1/g)·t ≡ difference/g (mod m2/g)
+    let cofactor = m2 / g;
+    let scaled = ModularInt::new(m1 / g, cofactor).checked_inverse().ok()?;
+    let t = scaled * ModularInt::new(difference / g, cofactor);
+    Some((t.value(), cofactor))
+}
+
+/// A fixed set of pairwise coprime moduli, precomputed for Garner's algorithm.
+///
+/// Reconstruction first converts the residues into mixed-radix digits v_i, with
+/// x = v_0 + v_1·m_0 + v_2·m_0·m_1 + ..., using only word-sized arithmetic and the
+/// precomputed inverses (m_0···m_(i-1))⁻¹ mod m_i. Only the final accumulation needs
+/// a wide type, so reconstructing many values against the same moduli is cheap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrtBasis {
+    moduli: Vec<u64>,
+    inverses: Vec<u64>, // (m_0···m_(i-1))⁻¹ mod m_i
+}
+
+impl CrtBasis {
+    /// Creates a basis from pairwise coprime moduli.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a modulus is zero or two moduli share a factor.
+    #[must_use]
+    pub fn new(moduli: &[u64]) -> Self {
+        match Self::try_new(moduli) {
+            Ok(basis) => basis,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Creates a basis, returning an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::ZeroModulus` if a modulus is zero and
+    /// `ModularError::NotInvertible` if two moduli share a factor.
+    pub fn try_new(moduli: &[u64]) -> Result<Self, ModularError> {
+        let mut inverses = Vec::with_capacity(moduli.len());
+        for (i, &m) in moduli.iter().enumerate() {
+            let product = ModularInt::try_new(1, m)?;
+            let product = moduli[..i]
+                .iter()
+                .fold(product, |acc, &earlier| acc * ModularInt::new(earlier, m));
+            inverses.push(product.checked_inverse()?.value());
+        }
+        Ok(Self {
+            moduli: moduli.to_vec(),
+            inverses,
+        })
+    }
+
+    /// Returns the moduli.
+    #[must_use]
+    pub fn moduli(&self) -> &[u64] {
+        &self.moduli
+    }
+
+    /// Returns the product of the moduli, or `None` if it does not fit in a `u128`.
+    #[must_use]
+    pub fn modulus_u128(&self) -> Option<u128> {
+        self.moduli
+            .iter()
+            .try_fold(1u128, |acc, &m| acc.checked_mul(u128::from(m)))
+    }
+
+    /// Converts residues into mixed-radix digits v_i < m_i, with
+    /// x = v_0 + v_1·m_0 + v_2·m_0·m_1 + ...
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of residues differs from the number of moduli.
+    #[must_use]
+    pub fn mixed_radix(&self, residues: &[u64]) -> Vec<u64> {
+        assert_eq!(
+            residues.len(),
+            self.moduli.len(),
+            "residue count does not match the basis"
+        );
+        let mut digits: Vec<u64> = Vec::with_capacity(residues.len());
+        for ((&r, &m), &inverse) in residues.iter().zip(&self.moduli).zip(&self.inverses) {
+            // The partial value v_0 + v_1·m_0 + ... reduced modulo m by Horner's rule
+            let partial = digits
+                .iter()
+                .zip(&self.moduli)
+                .rev()
+                .fold(0, |acc, (&v, &radix)| {
+                    u64::add_mod(u64::mul_mod(acc, radix % m, m), v % m, m)
+                });
+            let difference = u64::sub_mod(r % m, partial, m);
+            digits.push(u64::mul_mod(difference, inverse, m));
+        }
+        digits
+    }
+
+    /// Reconstructs the unique x below the product of the moduli.
+    ///
+    /// Returns `None` if that product does not fit in a `u128`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of residues differs from the number of moduli.
+    #[must_use]
+    pub fn reconstruct_u128(&self, residues: &[u64]) -> Option<u128> {
+        self.modulus_u128()?;
+        let digits = self.mixed_radix(residues);
+        Some(
+            digits
+                .iter()
+                .zip(&self.moduli)
+                .rev()
+                .fold(0, |acc, (&v, &m)| acc * u128::from(m) + u128::from(v)),
+        )
+    }
+
+    /// Reconstructs the unique x below the product of the moduli.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of residues differs from the number of moduli.
+    #[cfg(feature = "arbitrary-precision")]
+    #[must_use]
+    pub fn reconstruct_biguint(&self, residues: &[u64]) -> BigUint {
+        let digits = self.mixed_radix(residues);
+        digits
+            .iter()
+            .zip(&self.moduli)
+            .rev()
+            .fold(BigUint::from(0u8), |acc, (&v, &m)| acc * m + v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crt_coprime_and_general() {
+        assert_eq!(crt(&[]), Some(ModularInt::new(0, 1)));
+        assert_eq!(crt(&[ModularInt::new(4, 9)]), Some(ModularInt::new(4, 9)));
+
+        // x ≡ 3 (mod 4) and x ≡ 5 (mod 6) give x ≡ 11 (mod 12)
+        let general = [ModularInt::new(3, 4), ModularInt::new(5, 6)];
+        assert_eq!(crt(&general), Some(ModularInt::new(11, 12)));
+        assert_eq!(crt(&[ModularInt::new(3, 4), ModularInt::new(4, 6)]), None);
+
+        // Repeated and nested moduli
+        let nested = [
+            ModularInt::new(7, 12),
+            ModularInt::new(1, 3),
+            ModularInt::new(7, 12),
+        ];
+        assert_eq!(crt(&nested), Some(ModularInt::new(7, 12)));
+    }
+
+    #[test]
+    fn test_crt_u128_overflow() {
+        let primes = [u64::MAX - 58, u64::MAX - 82, u64::MAX - 94]; // 2^64 - 59, 2^64 - 83, 2^64 - 95
+        let x = 0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210u128;
+        #[allow(clippy::cast_possible_truncation)] // each remainder is below its prime
+        let residues: Vec<_> = primes[..2]
+            .iter()
+            .map(|&p| ModularInt::new((x % u128::from(p)) as u64, p))
+            .collect();
+        let solution = crt(&residues).unwrap();
+        assert_eq!(solution.value(), x);
+        assert_eq!(
+            solution.modulus(),
+            u128::from(primes[0]) * u128::from(primes[1])
+        );
+
+        let residues: Vec<_> = primes.iter().map(|&p| ModularInt::new(1, p)).collect();
+        assert_eq!(crt(&residues), None);
+    }
+
+    #[test]
+    fn test_garner_matches_crt() {
+        let moduli = [998_244_353, 1_000_000_007, 1_000_000_009, 65_537];
+        let basis = CrtBasis::new(&moduli);
+        let x = 0x00DE_ADBE_EF01_2345_6789_ABCD_EF01u128;
+        #[allow(clippy::cast_possible_truncation)] // each remainder is below its modulus
+        let residues: Vec<u64> = moduli.iter().map(|&m| (x % u128::from(m)) as u64).collect();
+
+        let digits = basis.mixed_radix(&residues);
+        assert!(digits.iter().zip(&moduli).all(|(v, m)| v < m));
+        assert_eq!(basis.reconstruct_u128(&residues), Some(x));
+
+        let as_modular: Vec<_> = residues
+            .iter()
+            .zip(&moduli)
+            .map(|(&r, &m)| ModularInt::new(r, m))
+            .collect();
+        assert_eq!(crt(&as_modular).map(|s| s.value()), Some(x));
+
+        assert_eq!(
+            CrtBasis::try_new(&[6, 35, 10]),
+            Err(ModularError::NotInvertible)
+        );
+        assert_eq!(CrtBasis::try_new(&[6, 0]), Err(ModularError::ZeroModulus));
+    }
+
+    #[cfg(feature = "arbitrary-precision")]
+    #[test]
+    fn test_crt_biguint() {
+        let primes = [u64::MAX - 58, u64::MAX - 82, u64::MAX - 94, 998_244_353];
+        let x = (BigUint::from(0xFEDC_BA98_7654_3210u64) << 150u32) + 12_345u32;
+        let residues: Vec<_> = primes
+            .iter()
+            .map(|&p| ModularInt::new((&x % p).try_into().unwrap(), p))
+            .collect();
+        let solution = crt_biguint(&residues).unwrap();
+        assert_eq!(solution.value(), &x);
+
+        let raw: Vec<u64> = residues.iter().map(ModularInt::<u64>::value).collect();
+        assert_eq!(CrtBasis::new(&primes).reconstruct_biguint(&raw), x);
+        assert_eq!(
+            crt_biguint(&[ModularInt::new(1, 6), ModularInt::new(2, 4)]),
+            None
+        );
+    }
+}
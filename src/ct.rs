@@ -0,0 +1,574 @@
+// Constant-time modular arithmetic for cryptographic use
+//
+// Nothing in this module branches on or indexes by secret data: conditions are
+// turned into all-zero or all-one masks and combined with bitwise operations. The
+// modulus, the exponent length and the context are treated as public.
+
+use crate::MontgomeryContext;
+use std::hint::black_box;
+use std::ops::{Add, AddAssign, BitAnd, BitOr, BitXor, Mul, MulAssign, Neg, Not, Sub, SubAssign};
+
+/// A secret boolean, stored as 0 or 1 and only combined with bitwise operations.
+///
+/// Convert to `bool` only once the result is allowed to become public.
+#[derive(Debug, Clone, Copy)]
+pub struct Choice(u8);
+
+impl Choice {
+    /// Returns the choice as 0 or 1.
+    #[must_use]
+    pub const fn unwrap_u8(self) -> u8 {
+        self.0
+    }
+}
+
+impl From<u8> for Choice {
+    /// Wraps 0 or 1 as a `Choice`; any other value is a logic error.
+    fn from(value: u8) -> Self {
+        debug_assert!(value <= 1, "Choice must be 0 or 1");
+        // Hide the value from the optimizer so it cannot reintroduce branches
+        Self(black_box(value))
+    }
+}
+
+impl From<Choice> for bool {
+    fn from(choice: Choice) -> Self {
+        choice.0 != 0
+    }
+}
+
+impl BitAnd for Choice {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self::Output {
+        Self(self.0 & other.0)
+    }
+}
+
+impl BitOr for Choice {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self::Output {
+        Self(self.0 | other.0)
+    }
+}
+
+impl BitXor for Choice {
+    type Output = Self;
+
+    fn bitxor(self, other: Self) -> Self::Output {
+        Self(self.0 ^ other.0)
+    }
+}
+
+impl Not for Choice {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        Self(1 & !self.0)
+    }
+}
+
+/// Types that can be selected between without branching on the `Choice`.
+pub trait ConditionallySelectable: Copy {
+    /// Returns `a` if `choice` is 0 and `b` if it is 1.
+    #[must_use]
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self;
+
+    /// Replaces `self` with `other` if `choice` is 1.
+    fn conditional_assign(&mut self, other: &Self, choice: Choice) {
+        *self = Self::conditional_select(self, other, choice);
+    }
+
+    /// Swaps `a` and `b` if `choice` is 1.
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: Choice) {
+        let previous_a = *a;
+        a.conditional_assign(b, choice);
+        b.conditional_assign(&previous_a, choice);
+    }
+}
+
+/// Equality that takes the same time whether or not the operands are equal.
+pub trait ConstantTimeEq {
+    /// Returns 1 if `self == other` and 0 otherwise.
+    #[must_use]
+    fn ct_eq(&self, other: &Self) -> Choice;
+}
+
+/// Implements the constant-time traits for primitive unsigned integers.
+macro_rules! impl_ct_primitive {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl ConditionallySelectable for $t {
+                fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+                    // All ones when choice is 1, all zeros when it is 0
+                    let mask = <$t>::from(choice.unwrap_u8()).wrapping_neg();
+                    a ^ (mask & (a ^ b))
+                }
+            }
+
+            impl ConstantTimeEq for $t {
+                fn ct_eq(&self, other: &Self) -> Choice {
+                    // x | -x has its top bit set exactly when x != 0
+                    let x = self ^ other;
+                    let nonzero = ((x | x.wrapping_neg()) >> (<$t>::BITS - 1)) as u8;
+                    Choice::from(nonzero ^ 1)
+                }
+            }
+        )*
+    };
+}
+
+impl_ct_primitive!(u8, u16, u32, u64, u128);
+
+/// A value that may be absent, with the presence flag kept secret.
+#[derive(Debug, Clone, Copy)]
+pub struct CtOption<T> {
+    value: T,
+    is_some: Choice,
+}
+
+impl<T: ConditionallySelectable> CtOption<T> {
+    /// Creates a `CtOption` holding `value` when `is_some` is 1.
+    #[must_use]
+    pub const fn new(value: T, is_some: Choice) -> Self {
+        Self { value, is_some }
+    }
+
+    /// Returns 1 if a value is present.
+    #[must_use]
+    pub const fn is_some(&self) -> Choice {
+        self.is_some
+    }
+
+    /// Returns 1 if no value is present.
+    #[must_use]
+    pub fn is_none(&self) -> Choice {
+        !self.is_some
+    }
+
+    /// Returns the value if present and `default` otherwise, without branching.
+    #[must_use]
+    pub fn unwrap_or(self, default: T) -> T {
+        T::conditional_select(&default, &self.value, self.is_some)
+    }
+
+    /// Returns the value, revealing whether it was present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no value is present.
+    #[must_use]
+    pub fn unwrap(self) -> T {
+        assert!(
+            bool::from(self.is_some),
+            "called unwrap on an empty CtOption"
+        );
+        self.value
+    }
+
+    /// Converts into an `Option`, revealing whether a value was present.
+    #[must_use]
+    pub fn into_option(self) -> Option<T> {
+        bool::from(self.is_some).then_some(self.value)
+    }
+}
+
+/// An integer modulo an odd `u64` modulus with constant-time arithmetic.
+///
+/// Values are kept in Montgomery form. Addition, subtraction and multiplication
+/// replace the final conditional subtraction with a masked select, exponentiation
+/// uses a Montgomery ladder, and inversion uses Fermat's little theorem, so the
+/// sequence of instructions depends only on the modulus and the exponent length.
+#[derive(Debug, Clone, Copy)]
+pub struct CtModularInt<'ctx> {
+    value: u64, // a * R mod N
+    ctx: &'ctx MontgomeryContext<u64>,
+}
+
+impl<'ctx> CtModularInt<'ctx> {
+    /// Creates a new CtModularInt from any `u64`, reducing it in constant time.
+    #[must_use]
+    pub fn new(value: u64, ctx: &'ctx MontgomeryContext<u64>) -> Self {
+        // value * R² < N * R for any u64 value, so no separate reduction is needed
+        Self {
+            value: montgomery_product(ctx, value, ctx.r_squared()),
+            ctx,
+        }
+    }
+
+    /// Returns 0 modulo the context's modulus.
+    #[must_use]
+    pub const fn zero(ctx: &'ctx MontgomeryContext<u64>) -> Self {
+        Self { value: 0, ctx }
+    }
+
+    /// Returns 1 modulo the context's modulus.
+    #[must_use]
+    pub fn one(ctx: &'ctx MontgomeryContext<u64>) -> Self {
+        Self {
+            value: montgomery_product(ctx, 1, ctx.r_squared()),
+            ctx,
+        }
+    }
+
+    /// Returns the value of the modular integer, converted out of Montgomery form.
+    #[must_use]
+    pub fn value(self) -> u64 {
+        montgomery_product(self.ctx, self.value, 1)
+    }
+
+    /// Returns the modulus of the underlying context.
+    #[must_use]
+    pub const fn modulus(self) -> u64 {
+        self.ctx.modulus()
+    }
+
+    /// Returns 1 if the value is zero.
+    #[must_use]
+    pub fn is_zero(self) -> Choice {
+        self.value.ct_eq(&0)
+    }
+
+    /// Computes self².
+    #[must_use]
+    pub fn square(self) -> Self {
+        self * self
+    }
+
+    /// Computes self^exponent with a Montgomery ladder.
+    ///
+    /// The exponent is given as little-endian `u64` limbs and is secret: every one of
+    /// its `64 * exponent.len()` bits costs one multiplication and one squaring.
+    #[must_use]
+    pub fn pow(self, exponent: &[u64]) -> Self {
+        // Invariant: r1 = r0 * self
+        let mut r0 = Self::one(self.ctx);
+        let mut r1 = self;
+
+        for index in (0..exponent.len() * 64).rev() {
+            let bit = Choice::from(((exponent[index / 64] >> (index % 64)) & 1) as u8);
+            Self::conditional_swap(&mut r0, &mut r1, bit);
+            r1 = r0 * r1;
+            r0 = r0.square();
+            Self::conditional_swap(&mut r0, &mut r1, bit);
+        }
+
+        r0
+    }
+
+    /// Computes the modular inverse as self^(N - 2).
+    ///
+    /// The modulus must be prime. The result is empty exactly when the value is zero.
+    #[must_use]
+    pub fn invert(self) -> CtOption<Self> {
+        let modulus = self.ctx.modulus();
+        let inverse = self.pow(&[modulus.wrapping_sub(2)]);
+        CtOption::new(inverse, !self.is_zero())
+    }
+
+    /// Asserts that both operands share a modulus; the modulus is public.
+    fn check_modulus(self, other: Self, operation: &str) {
+        assert_eq!(
+            self.ctx.modulus(),
+            other.ctx.modulus(),
+            "Modulus mismatch in {operation}"
+        );
+    }
+}
+
+impl ConditionallySelectable for CtModularInt<'_> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        a.check_modulus(*b, "conditional_select");
+        Self {
+            value: u64::conditional_select(&a.value, &b.value, choice),
+            ctx: a.ctx,
+        }
+    }
+}
+
+impl ConstantTimeEq for CtModularInt<'_> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        // Montgomery form is a bijection, so comparing the representations suffices
+        self.value.ct_eq(&other.value) & self.ctx.modulus().ct_eq(&other.ctx.modulus())
+    }
+}
+
+impl PartialEq for CtModularInt<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for CtModularInt<'_> {}
+
+// Implement Add trait for CtModularInt
+impl Add for CtModularInt<'_> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.check_modulus(other, "addition");
+        let modulus = self.ctx.modulus();
+        let (sum, carry) = self.value.overflowing_add(other.value);
+        let (reduced, borrow) = sum.overflowing_sub(modulus);
+        // Keep the unreduced sum only if it fits in a word and is below the modulus
+        Self {
+            value: u64::conditional_select(
+                &reduced,
+                &sum,
+                Choice::from(u8::from(borrow)) & !Choice::from(u8::from(carry)),
+            ),
+            ctx: self.ctx,
+        }
+    }
+}
+
+// Implement AddAssign trait for CtModularInt
+impl AddAssign for CtModularInt<'_> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+// Implement Sub trait for CtModularInt
+impl Sub for CtModularInt<'_> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self.check_modulus(other, "subtraction");
+        let (difference, borrow) = self.value.overflowing_sub(other.value);
+        // Add the modulus back if the subtraction wrapped
+        let correction =
+            u64::conditional_select(&0, &self.ctx.modulus(), Choice::from(u8::from(borrow)));
+        Self {
+            value: difference.wrapping_add(correction),
+            ctx: self.ctx,
+        }
+    }
+}
+
+// Implement SubAssign trait for CtModularInt
+impl SubAssign for CtModularInt<'_> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+// Implement Mul trait for CtModularInt
+impl Mul for CtModularInt<'_> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        self.check_modulus(other, "multiplication");
+        Self {
+            value: montgomery_product(self.ctx, self.value, other.value),
+            ctx: self.ctx,
+        }
+    }
+}
+
+// Implement MulAssign trait for CtModularInt
+impl MulAssign for CtModularInt<'_> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+// Implement Neg trait for CtModularInt
+impl Neg for CtModularInt<'_> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::zero(self.ctx) - self
+    }
+}
+
+/// Computes a * b * R⁻¹ mod N with a masked final subtraction.
+///
+/// Requires b < N; a may be any `u64`, so a * b < N * R.
+fn montgomery_product(ctx: &MontgomeryContext<u64>, a: u64, b: u64) -> u64 {
+    let modulus = ctx.modulus();
+    let t = u128::from(a) * u128::from(b);
+    #[allow(clippy::cast_possible_truncation)] // m only needs T mod R
+    let m = (t as u64).wrapping_mul(ctx.n_prime());
+
+    // (t + m * N) / R < 2N is 65 bits wide: the carry out of the u128 sum is bit 64
+    let (sum, overflow) = t.overflowing_add(u128::from(m) * u128::from(modulus));
+    let high = (sum >> 64) as u64;
+    let (reduced, borrow) = high.overflowing_sub(modulus);
+
+    // The 65-bit value is below N only if the carry is clear and the subtraction borrowed
+    u64::conditional_select(
+        &reduced,
+        &high,
+        Choice::from(u8::from(borrow)) & !Choice::from(u8::from(overflow)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ModularInt;
+    use std::time::Instant;
+
+    const LARGE_PRIME: u64 = 0xFFFF_FFFF_FFFF_FFC5; // 2^64 - 59
+
+    #[test]
+    fn test_choice_and_select() {
+        let (yes, no) = (Choice::from(1), Choice::from(0));
+        assert!(bool::from(yes & !no));
+        assert!(!bool::from(yes ^ yes));
+        assert!(bool::from(no | yes));
+
+        assert_eq!(u64::conditional_select(&3, &9, no), 3);
+        assert_eq!(u128::conditional_select(&3, &u128::MAX, yes), u128::MAX);
+        assert!(bool::from(u8::MAX.ct_eq(&u8::MAX)));
+        assert!(!bool::from(0u64.ct_eq(&(1 << 63))));
+
+        let (mut a, mut b) = (1u32, 2u32);
+        u32::conditional_swap(&mut a, &mut b, yes);
+        assert_eq!((a, b), (2, 1));
+
+        assert_eq!(CtOption::new(5u64, no).unwrap_or(7), 7);
+        assert_eq!(CtOption::new(5u64, yes).into_option(), Some(5));
+    }
+
+    #[test]
+    fn test_ct_arithmetic_matches_modular_int() {
+        let ctx = MontgomeryContext::new(LARGE_PRIME);
+        let values = [0, 1, 2, LARGE_PRIME - 1, 0xABCD_EF01_2345_6789, u64::MAX];
+        for &x in &values {
+            for &y in &values {
+                let (a, b) = (CtModularInt::new(x, &ctx), CtModularInt::new(y, &ctx));
+                let (ra, rb) = (
+                    ModularInt::new(x, LARGE_PRIME),
+                    ModularInt::new(y, LARGE_PRIME),
+                );
+                assert_eq!((a + b).value(), (ra + rb).value());
+                assert_eq!((a - b).value(), (ra - rb).value());
+                assert_eq!((a * b).value(), (ra * rb).value());
+                assert_eq!((-a).value(), (-ra).value());
+                assert_eq!(bool::from(a.ct_eq(&b)), ra == rb);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ct_pow_and_invert() {
+        let ctx = MontgomeryContext::new(LARGE_PRIME);
+        let x = 0x1234_5678_9ABC_DEF0u64;
+        let a = CtModularInt::new(x, &ctx);
+
+        let exponent = [0xFEDC_BA98_7654_3210, 0x0123_4567_89AB_CDEF];
+        assert_eq!(
+            a.pow(&exponent).value(),
+            ModularInt::new(x, LARGE_PRIME).pow(exponent).value()
+        );
+        assert_eq!(a.pow(&[]), CtModularInt::one(&ctx));
+
+        let inverse = a.invert();
+        assert!(bool::from(inverse.is_some()));
+        assert_eq!(a * inverse.unwrap(), CtModularInt::one(&ctx));
+        assert!(bool::from(CtModularInt::zero(&ctx).invert().is_none()));
+
+        // Small modulus where the 65-bit intermediate never appears
+        let ctx = MontgomeryContext::new(17u64);
+        let five = CtModularInt::new(5, &ctx);
+        assert_eq!(five.invert().unwrap().value(), 7);
+        assert_eq!(five.pow(&[16]).value(), 1);
+    }
+
+    /// Welch's t-statistic between two samples, as used by dudect.
+    #[allow(clippy::cast_precision_loss)] // sample counts are far below 2^52
+    fn welch_t(a: &[f64], b: &[f64]) -> f64 {
+        let stats = |xs: &[f64]| {
+            let n = xs.len() as f64;
+            let mean = xs.iter().sum::<f64>() / n;
+            let variance = xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            (mean, variance, n)
+        };
+        let (mean_a, var_a, n_a) = stats(a);
+        let (mean_b, var_b, n_b) = stats(b);
+        (mean_a - mean_b) / (var_a / n_a + var_b / n_b).sqrt()
+    }
+
+    /// Times `operation` on a fixed input class against a random input class and
+    /// returns the t-statistic, dropping the slowest 10% of each class as dudect does.
+    fn dudect<F: FnMut(u64) -> u64>(fixed: u64, mut operation: F) -> f64 {
+        const SAMPLES: usize = 20_000;
+        const BATCH: usize = 16;
+
+        // xorshift64*, enough to interleave the classes and draw random inputs
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        let mut next = move || {
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+        };
+
+        let (mut fixed_times, mut random_times) = (Vec::new(), Vec::new());
+        for _ in 0..SAMPLES {
+            let use_fixed = next() & 1 == 0;
+            let input = if use_fixed { fixed } else { next() };
+            let start = Instant::now();
+            for _ in 0..BATCH {
+                black_box(operation(black_box(input)));
+            }
+            let elapsed = start.elapsed().as_secs_f64();
+            if use_fixed {
+                fixed_times.push(elapsed);
+            } else {
+                random_times.push(elapsed);
+            }
+        }
+
+        for times in [&mut fixed_times, &mut random_times] {
+            times.sort_by(f64::total_cmp);
+            times.truncate(times.len() * 9 / 10);
+        }
+        welch_t(&fixed_times, &random_times)
+    }
+
+    #[test]
+    fn test_welch_t() {
+        assert!(welch_t(&[1.0, 2.0, 3.0], &[1.0, 2.0, 3.0]).abs() < 1e-12);
+        assert!(welch_t(&[10.0, 11.0, 12.0], &[1.0, 2.0, 3.0]) > 10.0);
+    }
+
+    // dudect reports a leak once |t| exceeds 4.5; allow extra headroom for shared
+    // machines. Timing only means something in optimized builds, so run these with
+    // `cargo test --release -- --ignored`.
+    const DUDECT_THRESHOLD: f64 = 10.0;
+
+    #[test]
+    #[ignore = "timing-sensitive; run with --release -- --ignored"]
+    fn test_dudect_mul() {
+        let ctx = MontgomeryContext::new(LARGE_PRIME);
+        let b = CtModularInt::new(0xABCD_EF01_2345_6789, &ctx);
+        let t = dudect(0, |x| (CtModularInt::new(x, &ctx) * b).value());
+        assert!(t.abs() < DUDECT_THRESHOLD, "t = {t}");
+    }
+
+    #[test]
+    #[ignore = "timing-sensitive; run with --release -- --ignored"]
+    fn test_dudect_pow() {
+        let ctx = MontgomeryContext::new(LARGE_PRIME);
+        let base = CtModularInt::new(3, &ctx);
+        let t = dudect(0, |x| base.pow(&[x]).value());
+        assert!(t.abs() < DUDECT_THRESHOLD, "t = {t}");
+    }
+
+    #[test]
+    #[ignore = "timing-sensitive; run with --release -- --ignored"]
+    fn test_dudect_invert() {
+        let ctx = MontgomeryContext::new(LARGE_PRIME);
+        let t = dudect(1, |x| {
+            CtModularInt::new(x, &ctx)
+                .invert()
+                .unwrap_or(CtModularInt::zero(&ctx))
+                .value()
+        });
+        assert!(t.abs() < DUDECT_THRESHOLD, "t = {t}");
+    }
+}
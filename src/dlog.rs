@@ -0,0 +1,289 @@
+// Discrete logarithms in the unit group modulo n
+
+use crate::group::UnitGroup;
+use crate::{crt, ModularInt, ModularStorage, PowStrategy};
+use std::collections::HashMap;
+
+/// Prime-order subgroups up to this order are solved with baby-step giant-step,
+/// whose table then holds at most 2^16 entries; larger ones use Pollard's rho.
+const BSGS_MAX_ORDER: u64 = 1 << 32;
+
+/// Pollard's rho restarts from a new starting point at most this many times.
+const RHO_ATTEMPTS: u64 = 16;
+
+/// Returns the smallest x ≥ 0 with base^x ≡ target, or `None` if there is none.
+///
+/// Factors the modulus and λ(n) through a `UnitGroup`, then runs Pohlig–Hellman over
+/// the prime factors of the order of base, solving each prime-order subproblem with
+/// baby-step giant-step or, for large primes, Pollard's rho. The cost is governed by
+/// the largest prime factor q of the order: about √q steps. Use
+/// `UnitGroup::discrete_log` to solve several logarithms modulo the same n.
+///
+/// Only unit bases are supported; `None` is returned for any other base unless the
+/// target is 1.
+///
+/// ```
+/// use modularity::{dlog::discrete_log, ModularInt};
+///
+/// let (base, target) = (ModularInt::new(2, 1_000_003), ModularInt::new(123_456, 1_000_003));
+/// let x = discrete_log(base, target).unwrap();
+/// assert_eq!(base.pow_mod(x), target);
+/// ```
+///
+/// # Panics
+///
+/// Panics if base and target have different moduli.
+#[must_use]
+pub fn discrete_log(base: ModularInt<u64>, target: ModularInt<u64>) -> Option<u64> {
+    assert_eq!(
+        base.modulus(),
+        target.modulus(),
+        "Modulus mismatch in discrete_log"
+    );
+    UnitGroup::new(base.modulus()).discrete_log(base.value(), target.value())
+}
+
+/// Returns the smallest x < bound with base^x ≡ target by baby-step giant-step, or
+/// `None` if there is none.
+///
+/// Uses about √bound multiplications and a hash table of √bound entries. Bounds
+/// above 2^32, whose table would pass 2^16 entries, also return `None`; use
+/// `discrete_log` or `pollard_rho_log` for larger groups.
+///
+/// # Panics
+///
+/// Panics if base and target have different moduli or base is not a unit.
+#[must_use]
+pub fn baby_step_giant_step(
+    base: ModularInt<u64>,
+    target: ModularInt<u64>,
+    bound: u64,
+) -> Option<u64> {
+    assert_eq!(
+        base.modulus(),
+        target.modulus(),
+        "Modulus mismatch in baby_step_giant_step"
+    );
+    if bound > BSGS_MAX_ORDER {
+        return None;
+    }
+    bsgs(base.value(), target.value(), bound, base.modulus())
+}
+
+/// Returns log_base(target) modulo the prime order q of base by Pollard's rho, or
+/// `None` if target is not a power of base.
+///
+/// Needs about √q multiplications and constant memory. The order must be prime and
+/// equal to the multiplicative order of base; neither is checked.
+///
+/// # Panics
+///
+/// Panics if base and target have different moduli or base is not a unit.
+#[must_use]
+pub fn pollard_rho_log(base: ModularInt<u64>, target: ModularInt<u64>, order: u64) -> Option<u64> {
+    assert_eq!(
+        base.modulus(),
+        target.modulus(),
+        "Modulus mismatch in pollard_rho_log"
+    );
+    rho(base.value(), target.value(), order, base.modulus())
+}
+
+impl UnitGroup {
+    /// Returns the smallest x ≥ 0 with base^x ≡ target modulo n, or `None` if there
+    /// is none or base is not a unit.
+    ///
+    /// See `discrete_log`; the factorizations come from this context.
+    #[must_use]
+    pub fn discrete_log(&self, base: u64, target: u64) -> Option<u64> {
+        let n = self.modulus();
+        let (base, target) = (base % n, target % n);
+        if target == 1 % n {
+            return Some(0);
+        }
+        let order = self.multiplicative_order(base)?;
+
+        // The order divides λ(n), whose factorization is already known
+        let factors = self.exponent_factors().iter().filter_map(|&(q, _)| {
+            let mut e = 0;
+            let mut rest = order;
+            while rest.is_multiple_of(q) {
+                rest /= q;
+                e += 1;
+            }
+            (e > 0).then_some((q, e))
+        });
+        let mut residues = Vec::new();
+        for (q, e) in factors {
+            let x = log_prime_power(base, target, order, q, e, n)?;
+            residues.push(ModularInt::new(x, q.pow(e)));
+        }
+        // The combined modulus is the order of base, which fits in a u64
+        #[allow(clippy::cast_possible_truncation)] // the CRT modulus is the order, below n
+        let x = crt(&residues)?.value() as u64;
+        (pow(base, x, n) == target).then_some(x)
+    }
+}
+
+/// Pohlig–Hellman for one prime power: finds x mod q^e digit by digit, each digit a
+/// logarithm in the subgroup of order q.
+#[allow(clippy::many_single_char_names)] // q^e, n and x as in the Pohlig–Hellman formulas
+fn log_prime_power(base: u64, target: u64, order: u64, q: u64, e: u32, n: u64) -> Option<u64> {
+    let gamma = pow(base, order / q, n);
+    let base_inverse = ModularInt::new(base, n).inverse_mod().value();
+    let (mut x, mut power) = (0, 1);
+    for _ in 0..e {
+        // (target·base^(-x))^(order / q^(k+1)) = gamma^(digit k)
+        let shifted = u64::mul_mod(target, pow(base_inverse, x, n), n);
+        let h = pow(shifted, order / (power * q), n);
+        let digit = if q <= BSGS_MAX_ORDER {
+            bsgs(gamma, h, q, n)?
+        } else {
+            rho(gamma, h, q, n)?
+        };
+        x += digit * power;
+        power *= q;
+    }
+    Some(x)
+}
+
+fn bsgs(base: u64, target: u64, bound: u64, n: u64) -> Option<u64> {
+    let m = bound.isqrt() + u64::from(bound.isqrt().pow(2) < bound);
+    #[allow(clippy::cast_possible_truncation)] // callers keep bound ≤ BSGS_MAX_ORDER, so m ≤ 2^16
+    let mut table = HashMap::with_capacity(m as usize);
+    let mut baby = 1 % n;
+    for j in 0..m {
+        // Keep the smallest exponent for each value
+        table.entry(baby).or_insert(j);
+        baby = u64::mul_mod(baby, base, n);
+    }
+
+    let giant = ModularInt::new(base, n).inverse_mod().pow_mod(m).value();
+    let mut gamma = target % n;
+    for i in 0..m {
+        if let Some(&j) = table.get(&gamma) {
+            let x = i * m + j;
+            return (x < bound).then_some(x);
+        }
+        gamma = u64::mul_mod(gamma, giant, n);
+    }
+    None
+}
+
+/// Pollard's rho for logarithms with Floyd's cycle detection. Each walk tracks
+/// x = base^a · target^b, and a collision x_i = x_2i yields a linear equation for
+/// the logarithm modulo the prime order q.
+fn rho(base: u64, target: u64, q: u64, n: u64) -> Option<u64> {
+    let step = |(x, a, b): (u64, u64, u64)| {
+        // Split the group into three pseudo-random classes by a multiplicative hash
+        match (x.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 32) % 3 {
+            0 => (u64::mul_mod(x, target, n), a, u64::add_mod(b, 1, q)),
+            1 => (
+                u64::mul_mod(x, x, n),
+                u64::add_mod(a, a, q),
+                u64::add_mod(b, b, q),
+            ),
+            _ => (u64::mul_mod(x, base, n), u64::add_mod(a, 1, q), b),
+        }
+    };
+
+    for start in 1..=RHO_ATTEMPTS {
+        let b0 = start % q;
+        let initial = (u64::mul_mod(base, pow(target, b0, n), n), 1 % q, b0);
+        let (mut tortoise, mut hare) = (step(initial), step(step(initial)));
+        while tortoise.0 != hare.0 {
+            tortoise = step(tortoise);
+            hare = step(step(hare));
+        }
+
+        // base^a1·target^b1 = base^a2·target^b2, so (b1 - b2)·x ≡ a2 - a1 (mod q)
+        let (_, a1, b1) = tortoise;
+        let (_, a2, b2) = hare;
+        let Ok(inverse) = ModularInt::new(u64::sub_mod(b1, b2, q), q).checked_inverse() else {
+            continue;
+        };
+        let x = u64::mul_mod(u64::sub_mod(a2, a1, q), inverse.value(), q);
+        if pow(base, x, n) == target % n {
+            return Some(x);
+        }
+    }
+    None
+}
+
+fn pow(base: u64, exponent: u64, modulus: u64) -> u64 {
+    u64::pow_mod(base, &exponent, modulus, PowStrategy::Auto)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ntt::P998244353;
+
+    // p - 1 = 2·3·q for the prime q = 68719476767, just above 2^36
+    const RHO_PRIME: u64 = 412_316_860_603;
+    const RHO_ORDER: u64 = 68_719_476_767;
+
+    fn naive_log(base: u64, target: u64, n: u64) -> Option<u64> {
+        let mut x = 1 % n;
+        for k in 0..=n {
+            if x == target % n {
+                return Some(k);
+            }
+            x = u64::mul_mod(x, base, n);
+        }
+        None
+    }
+
+    #[test]
+    fn test_discrete_log_matches_brute_force() {
+        for n in [2, 9, 15, 16, 23, 45, 97, 100, 101] {
+            let group = UnitGroup::new(n);
+            for base in (0..n).filter(|&b| group.is_unit(b)) {
+                for target in 0..n {
+                    assert_eq!(
+                        group.discrete_log(base, target),
+                        naive_log(base, target, n),
+                        "log_{base}({target}) mod {n}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pohlig_hellman_smooth_order() {
+        let group = UnitGroup::new(P998244353);
+        let mut x = 1u64;
+        for _ in 0..10 {
+            x = x.wrapping_mul(0x5851_F42D_4C95_7F2D).wrapping_add(1) % (P998244353 - 1);
+            let target = pow(3, x, P998244353);
+            assert_eq!(group.discrete_log(3, target), Some(x));
+        }
+        // 9 generates the squares, and 3 is a non-residue
+        assert_eq!(group.discrete_log(9, 3), None);
+    }
+
+    #[test]
+    fn test_rho_large_prime_order() {
+        let x = 0x0F_EDCB_A987 % RHO_ORDER;
+        let generator = ModularInt::new(2, RHO_PRIME);
+        let target = generator.pow_mod(x);
+        assert_eq!(discrete_log(generator, target), Some(x));
+
+        // Directly in the subgroup of order q
+        let base = generator.pow_mod(6);
+        let target = base.pow_mod(x);
+        assert_eq!(pollard_rho_log(base, target, RHO_ORDER), Some(x));
+    }
+
+    #[test]
+    fn test_baby_step_giant_step_bound() {
+        let base = ModularInt::new(3, P998244353);
+        let target = base.pow_mod(1_000);
+        assert_eq!(baby_step_giant_step(base, target, 1_001), Some(1_000));
+        assert_eq!(baby_step_giant_step(base, target, 1_000), None);
+        assert_eq!(baby_step_giant_step(base, base, 2), Some(1));
+        // Bounds above BSGS_MAX_ORDER are refused rather than allocating the table
+        assert_eq!(baby_step_giant_step(base, base, u64::MAX), None);
+    }
+}
@@ -0,0 +1,54 @@
+// Error type for the fallible modular arithmetic API
+
+use std::error::Error;
+use std::fmt;
+
+/// Errors returned by the `try_*` and `checked_*` operations.
+///
+/// Each variant corresponds to a condition that the panicking counterpart of the
+/// operation asserts on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModularError {
+    /// The modulus is zero.
+    ZeroModulus,
+    /// The modulus is even, but the operation (e.g. Montgomery reduction) needs an odd one.
+    EvenModulus,
+    /// The value shares a factor with the modulus, so it has no inverse.
+    NotInvertible,
+    /// The operands, or an operand and a context, use different moduli.
+    ModulusMismatch,
+    /// A transform size is not a power of two.
+    InvalidTransformSize,
+    /// The modulus has no root of unity of the order an operation needs.
+    NoRootOfUnity,
+    /// The divisor is the zero polynomial.
+    DivisionByZero,
+    /// The element at this index of a batch has no inverse.
+    NotInvertibleAt(usize),
+    /// The polynomial defining a field extension is not irreducible.
+    ReducibleModulus,
+}
+
+impl fmt::Display for ModularError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::ZeroModulus => "modulus cannot be zero",
+            Self::EvenModulus => "modulus must be odd for Montgomery reduction",
+            Self::NotInvertible => "the inverse does not exist because gcd(value, modulus) != 1",
+            Self::ModulusMismatch => "modulus mismatch",
+            Self::InvalidTransformSize => "transform size must be a power of two",
+            Self::NoRootOfUnity => "the modulus has no root of unity of the required order",
+            Self::DivisionByZero => "division by the zero polynomial",
+            Self::ReducibleModulus => "the field polynomial must be irreducible",
+            Self::NotInvertibleAt(index) => {
+                return write!(
+                    f,
+                    "the inverse of element {index} does not exist because gcd(value, modulus) != 1"
+                );
+            }
+        };
+        f.write_str(message)
+    }
+}
+
+impl Error for ModularError {}
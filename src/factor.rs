@@ -0,0 +1,571 @@
+// Integer factorization and the arithmetic functions built on it
+
+use crate::prime::{is_prime, is_prime_u128, small_primes};
+#[cfg(feature = "arbitrary-precision")]
+use crate::{prime::is_prime_biguint, BarrettContext};
+use crate::{ModularStorage, MontgomeryContext, MontgomeryWord};
+#[cfg(feature = "arbitrary-precision")]
+use num_bigint::BigUint;
+#[cfg(feature = "arbitrary-precision")]
+use num_integer::Integer;
+use num_integer::Roots;
+
+/// Trial division covers the primes below this bound before Pollard's rho starts.
+const TRIAL_BOUND: u64 = 1 << 10;
+
+/// Pollard–Brent accumulates this many differences before taking a gcd.
+const RHO_BATCH: u64 = 128;
+
+/// The first ECM stage-1 bound; it grows by half after every batch of curves.
+#[cfg(feature = "arbitrary-precision")]
+const ECM_FIRST_BOUND: u64 = 2000;
+
+/// Curves tried with each ECM stage-1 bound.
+#[cfg(feature = "arbitrary-precision")]
+const ECM_CURVES_PER_BOUND: u64 = 20;
+
+/// Word types whose odd composites Pollard's rho can split in Montgomery form.
+trait RhoWord: MontgomeryWord + ModularStorage + Roots + From<u64> {
+    fn is_prime_word(self) -> bool;
+}
+
+impl RhoWord for u64 {
+    fn is_prime_word(self) -> bool {
+        is_prime(self)
+    }
+}
+
+impl RhoWord for u128 {
+    fn is_prime_word(self) -> bool {
+        is_prime_u128(self)
+    }
+}
+
+/// Factors n into primes, returned as (prime, exponent) pairs in increasing order.
+///
+/// Small factors are removed by trial division; the remaining cofactor is split by
+/// Pollard's rho with Brent's cycle detection, running in Montgomery form, until
+/// every part passes `is_prime`.
+///
+/// ```
+/// use modularity::factor::factor;
+///
+/// assert_eq!(factor(360), vec![(2, 3), (3, 2), (5, 1)]);
+/// assert_eq!(factor(1), vec![]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if n is zero.
+#[must_use]
+pub fn factor(n: u64) -> Vec<(u64, u32)> {
+    factor_word(n)
+}
+
+/// Factors n into primes, returned as (prime, exponent) pairs in increasing order.
+///
+/// See `factor`.
+///
+/// # Panics
+///
+/// Panics if n is zero.
+#[must_use]
+pub fn factor_u128(n: u128) -> Vec<(u128, u32)> {
+    if let Ok(n) = u64::try_from(n) {
+        return factor(n)
+            .into_iter()
+            .map(|(p, e)| (u128::from(p), e))
+            .collect();
+    }
+    factor_word(n)
+}
+
+/// Factors n into primes, returned as (prime, exponent) pairs in increasing order.
+///
+/// Cofactors that fit in a `u128` go through `factor_u128`. Larger composites are
+/// split with stage 1 of Lenstra's elliptic curve method on Montgomery curves, which
+/// finds factors of up to about 20 digits quickly; a product of two large primes can
+/// take a very long time.
+///
+/// # Panics
+///
+/// Panics if n is zero.
+#[cfg(feature = "arbitrary-precision")]
+#[must_use]
+pub fn factor_biguint(n: &BigUint) -> Vec<(BigUint, u32)> {
+    assert!(n.bits() > 0, "cannot factor zero");
+    let mut primes = Vec::new();
+    let mut pending = vec![n.clone()];
+    while let Some(m) = pending.pop() {
+        if let Ok(small) = u128::try_from(&m) {
+            for (p, e) in factor_u128(small) {
+                primes.extend(std::iter::repeat_n(BigUint::from(p), e as usize));
+            }
+        } else if is_prime_biguint(&m) {
+            primes.push(m);
+        } else {
+            let d = split_biguint(&m);
+            pending.push(&m / &d);
+            pending.push(d);
+        }
+    }
+    group(primes)
+}
+
+/// Returns Euler's totient φ(n), the number of residues modulo n that are units.
+///
+/// # Panics
+///
+/// Panics if n is zero.
+#[must_use]
+pub fn euler_phi(n: u64) -> u64 {
+    phi_from_factors(&factor(n))
+}
+
+/// Returns Euler's totient φ(n).
+///
+/// # Panics
+///
+/// Panics if n is zero.
+#[must_use]
+pub fn euler_phi_u128(n: u128) -> u128 {
+    phi_from_factors(&factor_u128(n))
+}
+
+/// Returns Carmichael's function λ(n), the exponent of the unit group modulo n: the
+/// least m with a^m ≡ 1 (mod n) for every unit a.
+///
+/// # Panics
+///
+/// Panics if n is zero.
+#[must_use]
+pub fn carmichael_lambda(n: u64) -> u64 {
+    lambda_from_factors(&factor(n))
+}
+
+/// Returns Carmichael's function λ(n).
+///
+/// # Panics
+///
+/// Panics if n is zero.
+#[must_use]
+pub fn carmichael_lambda_u128(n: u128) -> u128 {
+    lambda_from_factors(&factor_u128(n))
+}
+
+/// Returns the divisors of n in increasing order.
+///
+/// # Panics
+///
+/// Panics if n is zero.
+#[must_use]
+pub fn divisors(n: u64) -> Vec<u64> {
+    let mut divisors = vec![1];
+    for (p, e) in factor(n) {
+        let existing = divisors.len();
+        let mut power = 1;
+        for _ in 0..e {
+            power *= p;
+            divisors.extend_from_within(..existing);
+            let start = divisors.len() - existing;
+            for d in &mut divisors[start..] {
+                *d *= power;
+            }
+        }
+    }
+    divisors.sort_unstable();
+    divisors
+}
+
+/// φ(n) from the factorization of n: the product of p^(e-1)·(p - 1).
+pub(crate) fn phi_from_factors<T: ModularStorage>(factors: &[(T, u32)]) -> T {
+    factors
+        .iter()
+        .fold(T::one(), |acc, &(p, e)| acc * p.pow(e - 1) * (p - T::one()))
+}
+
+/// λ(n) from the factorization of n: the lcm of λ(p^e), which is φ(p^e) except that
+/// λ(2^e) = 2^(e-2) for e ≥ 3.
+pub(crate) fn lambda_from_factors<T: ModularStorage>(factors: &[(T, u32)]) -> T {
+    let two = T::one() + T::one();
+    factors.iter().fold(T::one(), |acc, &(p, e)| {
+        let lambda = if p == two && e >= 3 {
+            two.pow(e - 2)
+        } else {
+            p.pow(e - 1) * (p - T::one())
+        };
+        acc.lcm(&lambda)
+    })
+}
+
+/// Sorts primes and counts repeats.
+fn group<T: Ord + Clone>(mut primes: Vec<T>) -> Vec<(T, u32)> {
+    primes.sort_unstable();
+    let mut factors: Vec<(T, u32)> = Vec::new();
+    for p in primes {
+        match factors.last_mut() {
+            Some((last, e)) if *last == p => *e += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+    factors
+}
+
+/// Trial division, then Pollard–Brent on whatever is left.
+fn factor_word<T: RhoWord>(n: T) -> Vec<(T, u32)> {
+    assert!(!n.is_zero(), "cannot factor zero");
+    let mut primes = Vec::new();
+    let mut n = n;
+    for p in small_primes(TRIAL_BOUND) {
+        let p = <T as From<u64>>::from(p);
+        if p * p > n {
+            break;
+        }
+        while (n % p).is_zero() {
+            primes.push(p);
+            n = n / p;
+        }
+    }
+
+    // Every factor left is at least TRIAL_BOUND, so the cofactor is 1, a prime, or
+    // a product of odd primes that rho can split
+    let mut pending = vec![n];
+    while let Some(m) = pending.pop() {
+        if m.is_one() {
+            continue;
+        }
+        if m.is_prime_word() {
+            primes.push(m);
+            continue;
+        }
+        // Rho needs about √p steps for the factor p, far too many for a square of a
+        // large prime, so split off perfect powers first
+        if let Some((root, k)) = perfect_power(m) {
+            pending.extend(std::iter::repeat_n(root, k as usize));
+            continue;
+        }
+        let d = (1..).find_map(|c| pollard_brent(m, c)).unwrap_or(m);
+        pending.push(d);
+        pending.push(m / d);
+    }
+    group(primes)
+}
+
+/// Returns (r, k) with r^k = n for some k ≥ 2, if n is a perfect power.
+fn perfect_power<T: RhoWord>(n: T) -> Option<(T, u32)> {
+    let bits = T::zero().count_zeros() - n.leading_zeros();
+    (2..bits).find_map(|k| {
+        let root = n.nth_root(k);
+        // The floor of the root never overflows when raised back
+        (root.pow(k) == n).then_some((root, k))
+    })
+}
+
+/// Pollard's rho with Brent's cycle detection on x ↦ x² + c, in Montgomery form.
+///
+/// Returns a proper factor of the odd composite n, or `None` if this c fails.
+#[allow(clippy::many_single_char_names)] // x, y, r, q, g as in Brent's paper
+fn pollard_brent<T: RhoWord>(n: T, c: u64) -> Option<T> {
+    let ctx = MontgomeryContext::new(n);
+    let c = ctx
+        .to_montgomery(<T as From<u64>>::from(c))
+        .montgomery_value();
+    let step = |x: T| T::add_mod(T::montgomery_product(&ctx, &x, &x), c, n);
+    let distance = |a: T, b: T| if a > b { a - b } else { b - a };
+
+    // The walk starts at x = 2 and doubles its stride r after every block
+    let (mut x, mut y, mut saved) = (T::zero(), <T as From<u64>>::from(2), T::zero());
+    let mut product = ctx.to_montgomery(T::one()).montgomery_value();
+    let mut g = T::one();
+    let mut r = 1u64;
+    while g.is_one() {
+        x = y;
+        for _ in 0..r {
+            y = step(y);
+        }
+        let mut k = 0;
+        while k < r && g.is_one() {
+            saved = y;
+            for _ in 0..RHO_BATCH.min(r - k) {
+                y = step(y);
+                product = T::montgomery_product(&ctx, &product, &distance(x, y));
+            }
+            g = product.gcd(&n);
+            k += RHO_BATCH;
+        }
+        r *= 2;
+    }
+
+    // The batched product hit 0 modulo n; redo the last batch one step at a time
+    if g == n {
+        loop {
+            saved = step(saved);
+            g = distance(x, saved).gcd(&n);
+            if !g.is_one() {
+                break;
+            }
+        }
+    }
+    (g != n).then_some(g)
+}
+
+/// Finds a proper factor of a composite n above 2^128: small primes, perfect
+/// powers, then ECM with growing bounds.
+#[cfg(feature = "arbitrary-precision")]
+fn split_biguint(n: &BigUint) -> BigUint {
+    for p in small_primes(TRIAL_BOUND) {
+        if n.is_multiple_of(&BigUint::from(p)) {
+            return BigUint::from(p);
+        }
+    }
+    #[allow(clippy::cast_possible_truncation)] // n has fewer than 2^32 bits
+    for k in 2..n.bits() as u32 {
+        let root = n.nth_root(k);
+        if root.bits() < 2 {
+            break;
+        }
+        if root.pow(k) == *n {
+            return root;
+        }
+    }
+
+    let mut bound = ECM_FIRST_BOUND;
+    let mut sigma = 6;
+    loop {
+        for _ in 0..ECM_CURVES_PER_BOUND {
+            if let Some(d) = ecm_stage_one(n, sigma, bound) {
+                return d;
+            }
+            sigma += 1;
+        }
+        bound += bound / 2;
+    }
+}
+
+/// Stage 1 of Lenstra's ECM on the Montgomery curve with Suyama parameter σ:
+/// multiplies the starting point by every prime power up to `bound` and looks for a
+/// factor in the gcd of the final Z coordinate with n.
+#[cfg(feature = "arbitrary-precision")]
+fn ecm_stage_one(n: &BigUint, sigma: u64, bound: u64) -> Option<BigUint> {
+    let ctx = BarrettContext::new(n.clone());
+    let reduce = |x: BigUint| ctx.reduce(&x);
+    let mul = |a: &BigUint, b: &BigUint| ctx.mul_mod(a, b);
+    let sub = |a: &BigUint, b: &BigUint| if a >= b { a - b } else { n - b + a };
+
+    // u = σ² - 5, v = 4σ, P = (u³ : v³), and (A + 2)/4 = (v - u)³(3u + v) / (16u³v)
+    let sigma = BigUint::from(sigma);
+    let u = sub(&reduce(&sigma * &sigma), &BigUint::from(5u8));
+    let v = reduce(sigma * 4u8);
+    let u3 = mul(&mul(&u, &u), &u);
+    let point = (u3.clone(), mul(&mul(&v, &v), &v));
+    let difference = sub(&v, &u);
+    let numerator = mul(
+        &mul(&mul(&difference, &difference), &difference),
+        &reduce(&u * 3u8 + &v),
+    );
+    let denominator = reduce(mul(&u3, &v) * 16u8);
+    let curve = MontgomeryCurve {
+        ctx: &ctx,
+        numerator,
+        denominator,
+    };
+
+    let mut point = point;
+    for p in small_primes(bound) {
+        let mut power = p;
+        while power * p <= bound {
+            power *= p;
+        }
+        point = curve.ladder(&point, power);
+    }
+    let g = point.1.gcd(n);
+    (g.bits() > 1 && g != *n).then_some(g)
+}
+
+/// x-only arithmetic on By² = x³ + Ax² + x, with (A + 2)/4 kept as a fraction.
+#[cfg(feature = "arbitrary-precision")]
+struct MontgomeryCurve<'a> {
+    ctx: &'a BarrettContext<BigUint>,
+    numerator: BigUint,
+    denominator: BigUint,
+}
+
+#[cfg(feature = "arbitrary-precision")]
+impl MontgomeryCurve<'_> {
+    fn add(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        let sum = a + b;
+        if &sum >= self.ctx.modulus() {
+            sum - self.ctx.modulus()
+        } else {
+            sum
+        }
+    }
+
+    fn sub(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        if a >= b {
+            a - b
+        } else {
+            self.ctx.modulus() - b + a
+        }
+    }
+
+    fn double(&self, (x, z): &(BigUint, BigUint)) -> (BigUint, BigUint) {
+        let sum = self.add(x, z);
+        let sum = self.ctx.mul_mod(&sum, &sum);
+        let difference = self.sub(x, z);
+        let difference = self.ctx.mul_mod(&difference, &difference);
+        let t = self.sub(&sum, &difference); // 4xz
+        let x2 = self
+            .ctx
+            .mul_mod(&self.ctx.mul_mod(&sum, &difference), &self.denominator);
+        let inner = self.add(
+            &self.ctx.mul_mod(&self.denominator, &difference),
+            &self.ctx.mul_mod(&self.numerator, &t),
+        );
+        (x2, self.ctx.mul_mod(&t, &inner))
+    }
+
+    /// P + Q given P - Q.
+    fn differential_add(
+        &self,
+        (xp, zp): &(BigUint, BigUint),
+        (xq, zq): &(BigUint, BigUint),
+        (xd, zd): &(BigUint, BigUint),
+    ) -> (BigUint, BigUint) {
+        let a = self.ctx.mul_mod(&self.sub(xp, zp), &self.add(xq, zq));
+        let b = self.ctx.mul_mod(&self.add(xp, zp), &self.sub(xq, zq));
+        let plus = self.add(&a, &b);
+        let minus = self.sub(&a, &b);
+        (
+            self.ctx.mul_mod(zd, &self.ctx.mul_mod(&plus, &plus)),
+            self.ctx.mul_mod(xd, &self.ctx.mul_mod(&minus, &minus)),
+        )
+    }
+
+    /// k·P by the Montgomery ladder, for k ≥ 1.
+    fn ladder(&self, point: &(BigUint, BigUint), k: u64) -> (BigUint, BigUint) {
+        let (mut r0, mut r1) = (point.clone(), self.double(point));
+        for i in (0..k.ilog2()).rev() {
+            if (k >> i) & 1 == 1 {
+                r0 = self.differential_add(&r1, &r0, point);
+                r1 = self.double(&r1);
+            } else {
+                r1 = self.differential_add(&r1, &r0, point);
+                r0 = self.double(&r0);
+            }
+        }
+        r0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_integer::Integer;
+
+    fn naive_phi(n: u64) -> u64 {
+        (1..=n).filter(|&a| a.gcd(&n) == 1).count() as u64
+    }
+
+    fn naive_lambda(n: u64) -> u64 {
+        let units: Vec<u64> = (1..=n).filter(|&a| a.gcd(&n) == 1).collect();
+        (1..=n)
+            .find(|&m| {
+                units
+                    .iter()
+                    .all(|&a| u64::pow_mod(a, &m, n, crate::PowStrategy::Auto) == 1 % n)
+            })
+            .unwrap_or(1)
+    }
+
+    #[test]
+    fn test_factor_u64() {
+        assert_eq!(factor(1), vec![]);
+        assert_eq!(factor(2), vec![(2, 1)]);
+        assert_eq!(factor(1 << 63), vec![(2, 63)]);
+        assert_eq!(
+            factor(u64::MAX),
+            vec![
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65_537, 1),
+                (6_700_417, 1)
+            ]
+        );
+        assert_eq!(factor(u64::MAX - 58), vec![(u64::MAX - 58, 1)]);
+
+        let (p, q) = (4_294_967_291, 4_294_967_279);
+        assert_eq!(factor(p * q), vec![(q, 1), (p, 1)]);
+        assert_eq!(factor(p * p), vec![(p, 2)]);
+        assert_eq!(
+            factor(1_000_003 * 1_000_003 * 1009),
+            vec![(1009, 1), (1_000_003, 2)]
+        );
+    }
+
+    #[test]
+    fn test_factor_u128() {
+        let p = u128::from(u64::MAX - 58);
+        let q = (1u128 << 40) - 87;
+        assert_eq!(factor_u128(p * p), vec![(p, 2)]);
+        assert_eq!(factor_u128(q * p), vec![(q, 1), (p, 1)]);
+        assert_eq!(factor_u128(q * q * q), vec![(q, 3)]);
+        assert_eq!(factor_u128((1 << 127) - 1), vec![((1 << 127) - 1, 1)]);
+
+        let product: u128 = factor_u128(u128::MAX)
+            .iter()
+            .map(|&(p, e)| p.pow(e))
+            .product();
+        assert_eq!(product, u128::MAX);
+        assert_eq!(
+            factor_u128(u128::MAX).last(),
+            Some(&(67_280_421_310_721, 1))
+        );
+    }
+
+    #[test]
+    fn test_phi_and_lambda_match_brute_force() {
+        for n in 1..300 {
+            assert_eq!(euler_phi(n), naive_phi(n), "phi({n})");
+            assert_eq!(carmichael_lambda(n), naive_lambda(n), "lambda({n})");
+        }
+        assert_eq!(carmichael_lambda(561), 80);
+        assert_eq!(carmichael_lambda(u64::MAX), 17_153_064_960);
+        assert_eq!(euler_phi(u64::MAX - 58), u64::MAX - 59);
+        assert_eq!(euler_phi_u128(1 << 100), 1 << 99);
+        assert_eq!(carmichael_lambda_u128(1 << 100), 1 << 98);
+    }
+
+    #[test]
+    fn test_divisors() {
+        assert_eq!(divisors(1), vec![1]);
+        assert_eq!(divisors(36), vec![1, 2, 3, 4, 6, 9, 12, 18, 36]);
+        let many = divisors(720_720);
+        assert_eq!(many.len(), 240);
+        assert!(many.windows(2).all(|w| w[0] < w[1]));
+        assert!(many.iter().all(|d| 720_720 % d == 0));
+    }
+
+    #[cfg(feature = "arbitrary-precision")]
+    #[test]
+    fn test_factor_biguint_with_ecm() {
+        let mersenne_127 = (BigUint::from(1u8) << 127u32) - 1u8;
+        let (p, q) = (4_294_967_279u64, 4_294_967_291u64);
+        let n = &mersenne_127 * p * q * 7u8 * 7u8;
+        assert_eq!(
+            factor_biguint(&n),
+            vec![
+                (BigUint::from(7u8), 2),
+                (BigUint::from(p), 1),
+                (BigUint::from(q), 1),
+                (mersenne_127.clone(), 1)
+            ]
+        );
+
+        let square = &mersenne_127 * &mersenne_127;
+        assert_eq!(factor_biguint(&square), vec![(mersenne_127, 2)]);
+    }
+}
@@ -0,0 +1,991 @@
+// Prime fields in Montgomery form and their extensions GF(p^k)
+
+use crate::prime::is_prime;
+use crate::sqrt::{legendre, sqrt_mod};
+use crate::{
+    pow_with, ConstModInt, Exponent, ModularError, ModularInt, MontgomeryContext, Poly, PowDomain,
+    PowStrategy,
+};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Operand length below which extension field products use the schoolbook method.
+const KARATSUBA_THRESHOLD: usize = 4;
+
+/// An element of the prime field GF(P), held in Montgomery form.
+///
+/// Like `ConstModInt`, the modulus is part of the type and every constant is
+/// evaluated at compile time, but values stay in Montgomery form, so a product costs
+/// a single Montgomery reduction. This is the base field of `ExtField` and of the
+/// `tower` extensions. `P` must be an odd prime; primality is not checked.
+///
+/// ```
+/// use modularity::field::Fp;
+///
+/// type F = Fp<1_000_000_007>;
+/// let a = F::new(123_456_789);
+/// assert_eq!((a * a.inverse()).value(), 1);
+/// assert_eq!(F::new(4).sqrt(), Some(F::new(2)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fp<const P: u64> {
+    value: u64, // a * R mod P
+}
+
+impl<const P: u64> Fp<P> {
+    /// Rejects moduli that Montgomery reduction cannot handle when the type is
+    /// instantiated.
+    const VALID_MODULUS: () = assert!(P > 2 && P % 2 == 1, "P must be an odd prime");
+
+    /// -P⁻¹ mod 2^64.
+    const N_PRIME: u64 = MontgomeryContext::<u64>::compute_n_prime(P);
+
+    /// R² mod P with R = 2^64.
+    const R_SQUARED: u64 = MontgomeryContext::<u64>::compute_r_squared(P);
+
+    /// The additive identity.
+    pub const ZERO: Self = Self::new(0);
+
+    /// The multiplicative identity.
+    pub const ONE: Self = Self::new(1);
+
+    /// Creates a field element, reducing the value modulo `P`.
+    #[must_use]
+    pub const fn new(value: u64) -> Self {
+        let () = Self::VALID_MODULUS;
+        Self {
+            value: Self::reduce((value % P) as u128 * Self::R_SQUARED as u128),
+        }
+    }
+
+    /// Returns the canonical representative in [0, P).
+    #[must_use]
+    pub const fn value(self) -> u64 {
+        Self::reduce(self.value as u128)
+    }
+
+    /// Returns the modulus `P`.
+    #[must_use]
+    pub const fn modulus(self) -> u64 {
+        P
+    }
+
+    /// Returns true if this is the zero element.
+    #[must_use]
+    pub const fn is_zero(self) -> bool {
+        self.value == 0
+    }
+
+    /// Computes self².
+    #[must_use]
+    pub const fn square(self) -> Self {
+        self.mul_const(self)
+    }
+
+    /// Computes self^exponent.
+    ///
+    /// Accepts any `Exponent`, including `u64` limb slices and `BigUint`.
+    #[must_use]
+    pub fn pow<E: Exponent>(self, exponent: E) -> Self {
+        pow_with(&FieldDomain(Self::ONE), &self, &exponent, PowStrategy::Auto)
+    }
+
+    /// Computes the multiplicative inverse.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element is zero.
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        match self.checked_inverse() {
+            Ok(inverse) => inverse,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Computes the multiplicative inverse, returning an error for zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::NotInvertible` if the element is zero.
+    pub fn checked_inverse(self) -> Result<Self, ModularError> {
+        let inverse = ModularInt::<u64>::new(self.value(), P).checked_inverse()?;
+        Ok(Self::new(inverse.value()))
+    }
+
+    /// Returns the Legendre symbol: 1 for a nonzero square, -1 for a non-square and
+    /// 0 for zero.
+    #[must_use]
+    pub fn legendre(self) -> i8 {
+        legendre(self.value(), P)
+    }
+
+    /// Returns the square root with the smaller representative, or `None` if the
+    /// element is not a square.
+    #[must_use]
+    pub fn sqrt(self) -> Option<Self> {
+        sqrt_mod(self.value(), P).map(|(root, _)| Self::new(root))
+    }
+
+    pub(crate) const fn add_const(self, other: Self) -> Self {
+        let (sum, carry) = self.value.overflowing_add(other.value);
+        Self {
+            value: if carry || sum >= P {
+                sum.wrapping_sub(P)
+            } else {
+                sum
+            },
+        }
+    }
+
+    pub(crate) const fn sub_const(self, other: Self) -> Self {
+        let (difference, borrow) = self.value.overflowing_sub(other.value);
+        Self {
+            value: if borrow {
+                difference.wrapping_add(P)
+            } else {
+                difference
+            },
+        }
+    }
+
+    pub(crate) const fn neg_const(self) -> Self {
+        Self::ZERO.sub_const(self)
+    }
+
+    pub(crate) const fn mul_const(self, other: Self) -> Self {
+        Self {
+            value: Self::reduce(self.value as u128 * other.value as u128),
+        }
+    }
+
+    /// Square-and-multiply for compile-time constants.
+    pub(crate) const fn pow_const(self, mut exponent: u128) -> Self {
+        let (mut base, mut result) = (self, Self::ONE);
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.mul_const(base);
+            }
+            base = base.square();
+            exponent >>= 1;
+        }
+        result
+    }
+
+    const fn reduce(t: u128) -> u64 {
+        MontgomeryContext::<u64>::montgomery_reduce(t, P, Self::N_PRIME)
+    }
+}
+
+/// The zero element, built through `new` so that `P` is checked as for any other
+/// value.
+///
+/// ```compile_fail
+/// let _ = modularity::field::Fp::<16>::default(); // Montgomery form needs an odd P
+/// ```
+impl<const P: u64> Default for Fp<P> {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl<const P: u64> From<u64> for Fp<P> {
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const P: u64> From<ConstModInt<P>> for Fp<P> {
+    fn from(value: ConstModInt<P>) -> Self {
+        Self::new(value.value())
+    }
+}
+
+impl<const P: u64> From<Fp<P>> for ModularInt<u64> {
+    fn from(value: Fp<P>) -> Self {
+        Self::new(value.value(), P)
+    }
+}
+
+// Implement Add trait for Fp
+impl<const P: u64> Add for Fp<P> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.add_const(other)
+    }
+}
+
+// Implement AddAssign trait for Fp
+impl<const P: u64> AddAssign for Fp<P> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+// Implement Sub trait for Fp
+impl<const P: u64> Sub for Fp<P> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self.sub_const(other)
+    }
+}
+
+// Implement SubAssign trait for Fp
+impl<const P: u64> SubAssign for Fp<P> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+// Implement Mul trait for Fp
+impl<const P: u64> Mul for Fp<P> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        self.mul_const(other)
+    }
+}
+
+// Implement MulAssign trait for Fp
+impl<const P: u64> MulAssign for Fp<P> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+// Implement Neg trait for Fp
+impl<const P: u64> Neg for Fp<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.neg_const()
+    }
+}
+
+/// Precomputed data for GF(P^K) = GF(P)[x]/(f) with f monic and irreducible of
+/// degree K.
+///
+/// Holds the reduction polynomial, the Frobenius map x^i ↦ x^(i·P) as a matrix, the
+/// traces of the basis monomials, and the Tonelli–Shanks constants for square roots.
+#[derive(Debug, Clone)]
+pub struct ExtFieldContext<const P: u64, const K: usize> {
+    modulus: [Fp<P>; K],        // f = x^K + Σ modulus[i] x^i
+    terms: Vec<(usize, Fp<P>)>, // the nonzero entries of modulus
+    frobenius: [[Fp<P>; K]; K], // x^(i·P) mod f
+    traces: [Fp<P>; K],         // Tr(x^i)
+    two_adicity: u32,           // s with P^K - 1 = 2^s · t for odd t
+    half_odd_part: Vec<u64>,    // (t - 1) / 2 as little-endian limbs
+    root_of_unity: [Fp<P>; K],  // z^t for a non-square z, of order 2^s
+}
+
+/// An element of GF(P^K), bound to the context that created it.
+///
+/// Coefficients are stored lowest degree first. Products use Karatsuba
+/// multiplication followed by reduction modulo the field polynomial; the Frobenius
+/// map is a matrix-vector product, which makes the norm, the inverse (as
+/// a^(P + P² + … + P^(K-1)) divided by the norm) and the quadratic character cheap.
+///
+/// ```
+/// use modularity::field::ExtFieldContext;
+///
+/// // GF(p³) with f = x³ + x + 7
+/// let ctx = ExtFieldContext::<998_244_353, 3>::new(&[7, 1, 0]);
+/// let a = ctx.from_coefficients(&[1, 2, 3]);
+/// assert_eq!(a * a.inverse(), ctx.one());
+/// assert_eq!(a.frobenius(), a.pow(998_244_353u64));
+/// assert_eq!((a * a).sqrt().map(|r| r * r), Some(a * a));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ExtField<'ctx, const P: u64, const K: usize> {
+    coefficients: [Fp<P>; K],
+    ctx: &'ctx ExtFieldContext<P, K>,
+}
+
+impl<const P: u64, const K: usize> ExtFieldContext<P, K> {
+    /// Creates the context for the field polynomial
+    /// f = x^K + modulus[K-1]·x^(K-1) + … + modulus[0].
+    ///
+    /// # Panics
+    ///
+    /// Panics if K is zero or f is reducible.
+    #[must_use]
+    pub fn new(modulus: &[u64; K]) -> Self {
+        match Self::try_new(modulus) {
+            Ok(ctx) => ctx,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Creates a context, returning an error instead of panicking.
+    ///
+    /// Irreducibility is checked with Rabin's test: f is irreducible exactly when
+    /// x^(P^K) ≡ x (mod f) and gcd(x^(P^(K/q)) - x, f) = 1 for every prime q dividing
+    /// K. `P` must be prime; this is not checked.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::ReducibleModulus` if K is zero or f is reducible.
+    pub fn try_new(modulus: &[u64; K]) -> Result<Self, ModularError> {
+        if K == 0 {
+            return Err(ModularError::ReducibleModulus);
+        }
+        let f = Poly::<P>::from_coeffs(&[&modulus[..], &[1]].concat());
+        let x = Poly::monomial(ConstModInt::ONE, 1);
+
+        // x^(P^i) mod f for i = 1, ..., K
+        let mut power = x.clone();
+        let mut x_to_p = Poly::zero();
+        for i in 1..=K {
+            power = power.pow_mod(P, &f);
+            if i == 1 {
+                x_to_p = power.clone();
+            }
+            if i < K
+                && K.is_multiple_of(i)
+                && is_prime((K / i) as u64)
+                && f.gcd(&(&power - &x)).degree() != Some(0)
+            {
+                return Err(ModularError::ReducibleModulus);
+            }
+        }
+        if power != &x % &f {
+            return Err(ModularError::ReducibleModulus);
+        }
+
+        let mut frobenius = [[Fp::ZERO; K]; K];
+        let mut row = Poly::one();
+        for entry in &mut frobenius {
+            *entry = to_array(&row);
+            row = &(&row * &x_to_p) % &f;
+        }
+
+        let modulus = modulus.map(Fp::new);
+        let terms = modulus
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !c.is_zero())
+            .map(|(j, &c)| (j, c))
+            .collect();
+
+        // Newton's identities give the power sums s_m of the roots of f:
+        // s_m = -(m·c_(K-m) + Σ_(j<m) c_(K-j)·s_(m-j)), with s_0 = K
+        let mut traces = [Fp::ZERO; K];
+        traces[0] = Fp::new(K as u64);
+        for m in 1..K {
+            let mut sum = Fp::new(m as u64) * modulus[K - m];
+            for j in 1..m {
+                sum += modulus[K - j] * traces[m - j];
+            }
+            traces[m] = -sum;
+        }
+
+        let mut ctx = Self {
+            modulus,
+            terms,
+            frobenius,
+            traces,
+            two_adicity: 0,
+            half_odd_part: Vec::new(),
+            root_of_unity: [Fp::ZERO; K],
+        };
+        let (two_adicity, odd_part) = split_order(P, K);
+        ctx.root_of_unity = ctx.non_square().pow(&odd_part[..]).coefficients;
+        ctx.half_odd_part = shift_right(&odd_part, 1);
+        ctx.two_adicity = two_adicity;
+        Ok(ctx)
+    }
+
+    /// Returns the characteristic `P`.
+    #[must_use]
+    pub const fn characteristic(&self) -> u64 {
+        P
+    }
+
+    /// Returns the coefficients below x^K of the field polynomial.
+    #[must_use]
+    pub const fn modulus(&self) -> &[Fp<P>; K] {
+        &self.modulus
+    }
+
+    /// Returns the zero element.
+    #[must_use]
+    pub const fn zero(&self) -> ExtField<'_, P, K> {
+        self.element([Fp::ZERO; K])
+    }
+
+    /// Returns the multiplicative identity.
+    #[must_use]
+    pub const fn one(&self) -> ExtField<'_, P, K> {
+        self.from_base(Fp::ONE)
+    }
+
+    /// Embeds an element of the prime field.
+    #[must_use]
+    pub const fn from_base(&self, value: Fp<P>) -> ExtField<'_, P, K> {
+        let mut coefficients = [Fp::ZERO; K];
+        coefficients[0] = value;
+        self.element(coefficients)
+    }
+
+    /// Creates an element from its coefficients, lowest degree first, reducing each
+    /// modulo `P`.
+    #[must_use]
+    pub fn from_coefficients(&self, coefficients: &[u64; K]) -> ExtField<'_, P, K> {
+        self.element(coefficients.map(Fp::new))
+    }
+
+    /// Creates an element from prime field coefficients, lowest degree first.
+    #[must_use]
+    pub const fn element(&self, coefficients: [Fp<P>; K]) -> ExtField<'_, P, K> {
+        ExtField {
+            coefficients,
+            ctx: self,
+        }
+    }
+
+    /// Returns the first non-square in the enumeration of the field by the base-P
+    /// digits of a counter, skipping the constants when K is even since they are
+    /// all squares.
+    #[allow(clippy::cast_possible_truncation)] // each base-P digit is below P
+    fn non_square(&self) -> ExtField<'_, P, K> {
+        let mut counter = if K.is_multiple_of(2) {
+            u128::from(P)
+        } else {
+            2
+        };
+        loop {
+            let mut digits = counter;
+            let mut candidate = [Fp::ZERO; K];
+            for c in &mut candidate {
+                *c = Fp::new((digits % u128::from(P)) as u64);
+                digits /= u128::from(P);
+            }
+            let candidate = self.element(candidate);
+            if !candidate.is_square() {
+                return candidate;
+            }
+            counter += 1;
+        }
+    }
+
+    /// Multiplies two residues and reduces the product modulo f.
+    fn mul(&self, a: &[Fp<P>; K], b: &[Fp<P>; K]) -> [Fp<P>; K] {
+        let mut product = [[Fp::ZERO; K]; 2];
+        {
+            let product = product.as_flattened_mut();
+            if K < KARATSUBA_THRESHOLD {
+                schoolbook(a, b, product);
+            } else {
+                let mut scratch = [[Fp::ZERO; K]; 6];
+                karatsuba(a, b, product, scratch.as_flattened_mut());
+            }
+
+            // x^i = x^(i-K)·x^K ≡ -x^(i-K)·Σ modulus[j] x^j, from the top down
+            for i in (K..2 * K - 1).rev() {
+                let top = product[i];
+                if !top.is_zero() {
+                    for &(j, c) in &self.terms {
+                        product[i - K + j] -= top * c;
+                    }
+                }
+            }
+        }
+        product[0]
+    }
+}
+
+impl<'ctx, const P: u64, const K: usize> ExtField<'ctx, P, K> {
+    /// Returns the coefficients, lowest degree first.
+    #[must_use]
+    pub const fn coefficients(&self) -> &[Fp<P>; K] {
+        &self.coefficients
+    }
+
+    /// Returns the context this element belongs to.
+    #[must_use]
+    pub const fn context(&self) -> &'ctx ExtFieldContext<P, K> {
+        self.ctx
+    }
+
+    /// Returns true if this is the zero element.
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.iter().all(|c| c.is_zero())
+    }
+
+    /// Computes self².
+    #[must_use]
+    pub fn square(self) -> Self {
+        self * self
+    }
+
+    /// Computes self^exponent.
+    ///
+    /// Accepts any `Exponent`, including `u64` limb slices and `BigUint`.
+    #[must_use]
+    pub fn pow<E: Exponent>(self, exponent: E) -> Self {
+        pow_with(
+            &FieldDomain(self.ctx.one()),
+            &self,
+            &exponent,
+            PowStrategy::Auto,
+        )
+    }
+
+    /// Applies the Frobenius automorphism a ↦ a^P.
+    ///
+    /// The coefficients are fixed by it, so this is a linear map on the coefficient
+    /// vector.
+    #[must_use]
+    pub fn frobenius(self) -> Self {
+        let mut coefficients = [Fp::ZERO; K];
+        for (&a, row) in self.coefficients.iter().zip(&self.ctx.frobenius) {
+            if !a.is_zero() {
+                for (c, &m) in coefficients.iter_mut().zip(row) {
+                    *c += a * m;
+                }
+            }
+        }
+        self.ctx.element(coefficients)
+    }
+
+    /// Applies the Frobenius automorphism `power` times, giving a^(P^power).
+    #[must_use]
+    pub fn frobenius_power(self, power: usize) -> Self {
+        (0..power % K).fold(self, |a, _| a.frobenius())
+    }
+
+    /// Returns the norm to GF(P), the product of the K conjugates a^(P^i).
+    #[must_use]
+    pub fn norm(self) -> Fp<P> {
+        (self * self.other_conjugates()).coefficients[0]
+    }
+
+    /// Returns the trace to GF(P), the sum of the K conjugates a^(P^i).
+    #[must_use]
+    pub fn trace(self) -> Fp<P> {
+        self.coefficients
+            .iter()
+            .zip(&self.ctx.traces)
+            .fold(Fp::ZERO, |sum, (&a, &t)| sum + a * t)
+    }
+
+    /// Computes the multiplicative inverse.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element is zero.
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        match self.checked_inverse() {
+            Ok(inverse) => inverse,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Computes the multiplicative inverse, returning an error for zero.
+    ///
+    /// Uses a⁻¹ = (a^P · a^(P²) ··· a^(P^(K-1))) / N(a), so the only inversion is in
+    /// the prime field.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::NotInvertible` if the element is zero.
+    pub fn checked_inverse(self) -> Result<Self, ModularError> {
+        let conjugates = self.other_conjugates();
+        let norm_inverse = (self * conjugates).coefficients[0].checked_inverse()?;
+        Ok(self
+            .ctx
+            .element(conjugates.coefficients.map(|c| c * norm_inverse)))
+    }
+
+    /// Returns true if the element is a square, including zero.
+    ///
+    /// a^((P^K - 1)/2) = N(a)^((P - 1)/2), so this is the Legendre symbol of the norm.
+    #[must_use]
+    pub fn is_square(self) -> bool {
+        self.norm().legendre() != -1
+    }
+
+    /// Returns a square root, or `None` if the element is not a square.
+    ///
+    /// Uses Tonelli–Shanks in the 2-Sylow subgroup of GF(P^K)*, with the root of
+    /// unity precomputed by the context. The other root is its negation.
+    #[must_use]
+    #[allow(clippy::many_single_char_names)] // the Tonelli–Shanks names w, b, c, m, t
+    pub fn sqrt(self) -> Option<Self> {
+        if self.is_zero() {
+            return Some(self);
+        }
+        if !self.is_square() {
+            return None;
+        }
+        let one = self.ctx.one();
+        // With P^K - 1 = 2^s·t: root = a^((t+1)/2) and b = a^t, so root² = a·b
+        let w = self.pow(&self.ctx.half_odd_part[..]);
+        let mut root = w * self;
+        let mut b = w * root;
+        let mut c = self.ctx.element(self.ctx.root_of_unity);
+        let mut m = self.ctx.two_adicity;
+        while b != one {
+            // b has order 2^i for some i < m, since a is a square
+            let mut i = 0;
+            let mut t = b;
+            while t != one {
+                t = t.square();
+                i += 1;
+            }
+            let mut g = c;
+            for _ in i + 1..m {
+                g = g.square();
+            }
+            root *= g;
+            c = g.square();
+            b *= c;
+            m = i;
+        }
+        Some(root)
+    }
+
+    /// Returns a^P · a^(P²) ··· a^(P^(K-1)).
+    fn other_conjugates(self) -> Self {
+        let mut conjugate = self;
+        let mut product = self.ctx.one();
+        for _ in 1..K {
+            conjugate = conjugate.frobenius();
+            product *= conjugate;
+        }
+        product
+    }
+
+    fn check_context(&self, other: &Self, operation: &str) {
+        assert!(
+            std::ptr::eq(self.ctx, other.ctx) || self.ctx.modulus == other.ctx.modulus,
+            "Modulus mismatch in {operation}"
+        );
+    }
+}
+
+impl<const P: u64, const K: usize> PartialEq for ExtField<'_, P, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ctx.modulus == other.ctx.modulus && self.coefficients == other.coefficients
+    }
+}
+
+impl<const P: u64, const K: usize> Eq for ExtField<'_, P, K> {}
+
+// Implement Add trait for ExtField
+impl<const P: u64, const K: usize> Add for ExtField<'_, P, K> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self::Output {
+        self.check_context(&other, "addition");
+        for (a, &b) in self.coefficients.iter_mut().zip(&other.coefficients) {
+            *a += b;
+        }
+        self
+    }
+}
+
+// Implement AddAssign trait for ExtField
+impl<const P: u64, const K: usize> AddAssign for ExtField<'_, P, K> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+// Implement Sub trait for ExtField
+impl<const P: u64, const K: usize> Sub for ExtField<'_, P, K> {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self::Output {
+        self.check_context(&other, "subtraction");
+        for (a, &b) in self.coefficients.iter_mut().zip(&other.coefficients) {
+            *a -= b;
+        }
+        self
+    }
+}
+
+// Implement SubAssign trait for ExtField
+impl<const P: u64, const K: usize> SubAssign for ExtField<'_, P, K> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+// Implement Mul trait for ExtField
+impl<const P: u64, const K: usize> Mul for ExtField<'_, P, K> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        self.check_context(&other, "multiplication");
+        self.ctx
+            .element(self.ctx.mul(&self.coefficients, &other.coefficients))
+    }
+}
+
+// Implement MulAssign trait for ExtField
+impl<const P: u64, const K: usize> MulAssign for ExtField<'_, P, K> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+// Implement Neg trait for ExtField
+impl<const P: u64, const K: usize> Neg for ExtField<'_, P, K> {
+    type Output = Self;
+
+    fn neg(mut self) -> Self::Output {
+        for a in &mut self.coefficients {
+            *a = -*a;
+        }
+        self
+    }
+}
+
+/// Exponentiation domain for field types whose identity is a value rather than a
+/// constant of the type.
+pub(crate) struct FieldDomain<F>(pub(crate) F);
+
+impl<F: Copy + Mul<Output = F>> PowDomain for FieldDomain<F> {
+    type Element = F;
+
+    fn one(&self) -> F {
+        self.0
+    }
+
+    fn mul(&self, a: &F, b: &F) -> F {
+        *a * *b
+    }
+}
+
+/// Writes the full product of two equal-length operands to out[..2n].
+fn schoolbook<const P: u64>(a: &[Fp<P>], b: &[Fp<P>], out: &mut [Fp<P>]) {
+    out[..2 * a.len()].fill(Fp::ZERO);
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            out[i + j] += x * y;
+        }
+    }
+}
+
+/// Writes the full product of two equal-length operands to out[..2n] by recursive
+/// Karatsuba, using at most about 4n + 4·log2(n) elements of scratch space.
+#[allow(clippy::many_single_char_names)] // a0, a1, b0, b1 and h follow the Karatsuba split
+fn karatsuba<const P: u64>(a: &[Fp<P>], b: &[Fp<P>], out: &mut [Fp<P>], scratch: &mut [Fp<P>]) {
+    let n = a.len();
+    if n < KARATSUBA_THRESHOLD {
+        schoolbook(a, b, out);
+        return;
+    }
+    // a = a0 + a1·x^h with a1 at least as long as a0
+    let h = n / 2;
+    let m = n - h;
+    let (a0, a1) = a.split_at(h);
+    let (b0, b1) = b.split_at(h);
+    {
+        let (z0, z2) = out[..2 * n].split_at_mut(2 * h);
+        karatsuba(a0, b0, z0, scratch);
+        karatsuba(a1, b1, z2, scratch);
+    }
+
+    let (sums, rest) = scratch.split_at_mut(2 * m);
+    let (sum_a, sum_b) = sums.split_at_mut(m);
+    sum_a.copy_from_slice(a1);
+    sum_b.copy_from_slice(b1);
+    for (s, &x) in sum_a.iter_mut().zip(a0) {
+        *s += x;
+    }
+    for (s, &y) in sum_b.iter_mut().zip(b0) {
+        *s += y;
+    }
+    let (z1, rest) = rest.split_at_mut(2 * m);
+    karatsuba(sum_a, sum_b, z1, rest);
+
+    // z1 = (a0 + a1)(b0 + b1) - z0 - z2 = a0·b1 + a1·b0
+    for (i, z) in z1.iter_mut().enumerate() {
+        if i < 2 * h {
+            *z -= out[i];
+        }
+        *z -= out[2 * h + i];
+    }
+    for (i, &z) in z1.iter().enumerate() {
+        out[h + i] += z;
+    }
+}
+
+/// Returns (s, t) with P^K - 1 = 2^s · t and t odd, t as little-endian limbs.
+#[allow(clippy::cast_possible_truncation)] // splits u128 products into 64-bit limbs
+fn split_order(p: u64, k: usize) -> (u32, Vec<u64>) {
+    let mut order = vec![1u64];
+    for _ in 0..k {
+        let mut carry = 0u128;
+        for limb in &mut order {
+            let product = u128::from(*limb) * u128::from(p) + carry;
+            *limb = product as u64;
+            carry = product >> 64;
+        }
+        if carry > 0 {
+            order.push(carry as u64);
+        }
+    }
+    // P^K is odd, so subtracting 1 only clears the lowest bit
+    order[0] -= 1;
+    let mut two_adicity = 0;
+    for &limb in &order {
+        if limb != 0 {
+            two_adicity += limb.trailing_zeros();
+            break;
+        }
+        two_adicity += 64;
+    }
+    (two_adicity, shift_right(&order, two_adicity))
+}
+
+/// Shifts a little-endian limb vector right by the given number of bits.
+fn shift_right(limbs: &[u64], bits: u32) -> Vec<u64> {
+    let (words, bits) = ((bits / 64) as usize, bits % 64);
+    let limbs = &limbs[words.min(limbs.len())..];
+    (0..limbs.len())
+        .map(|i| {
+            let high = limbs
+                .get(i + 1)
+                .map_or(0, |&next| if bits == 0 { 0 } else { next << (64 - bits) });
+            (limbs[i] >> bits) | high
+        })
+        .collect()
+}
+
+/// Converts a reduced polynomial into a coefficient array.
+fn to_array<const P: u64, const K: usize>(poly: &Poly<P>) -> [Fp<P>; K] {
+    std::array::from_fn(|i| Fp::from(poly.coeff(i)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ntt::P998244353;
+
+    // 2^63 - 25, which is 7 mod 12
+    const Q: u64 = 9_223_372_036_854_775_783;
+
+    fn sample<const P: u64, const K: usize>(seed: u64) -> [u64; K] {
+        let mut state = seed;
+        std::array::from_fn(|_| {
+            state = state
+                .wrapping_mul(0x5851_F42D_4C95_7F2D)
+                .wrapping_add(0x1405_7B7E_F767_814F);
+            state % P
+        })
+    }
+
+    #[test]
+    fn test_prime_field() {
+        type F = Fp<Q>;
+        let (a, b) = (Q - 5, Q / 3);
+        #[allow(clippy::cast_possible_truncation)] // the remainder is below Q
+        let expected = (u128::from(a) * u128::from(b) % u128::from(Q)) as u64;
+        assert_eq!((F::new(a) * F::new(b)).value(), expected);
+        assert_eq!((F::new(a) + F::new(b)).value(), a + b - Q);
+        assert_eq!((F::new(b) - F::new(a)).value(), b + 5);
+        assert_eq!((-F::ONE).value(), Q - 1);
+        assert_eq!(F::new(a).pow(Q - 1), F::ONE);
+        assert_eq!(F::new(a) * F::new(a).inverse(), F::ONE);
+        assert_eq!(F::ZERO.checked_inverse(), Err(ModularError::NotInvertible));
+        assert_eq!(F::new(9).sqrt(), Some(F::new(3)));
+        // Q ≡ 3 mod 4, so -1 is not a square
+        assert_eq!((-F::ONE).legendre(), -1);
+        assert_eq!((-F::ONE).sqrt(), None);
+    }
+
+    /// Checks the context's arithmetic against polynomial arithmetic modulo f.
+    fn check_field<const P: u64, const K: usize>(modulus: &[u64; K]) {
+        let ctx = ExtFieldContext::<P, K>::new(modulus);
+        let f = Poly::<P>::from_coeffs(&[&modulus[..], &[1]].concat());
+        for seed in 0..8 {
+            let a = ctx.from_coefficients(&sample::<P, K>(seed));
+            let b = ctx.from_coefficients(&sample::<P, K>(seed + 100));
+            let (pa, pb) = (to_poly(&a), to_poly(&b));
+            assert_eq!(to_poly(&(a * b)), &(&pa * &pb) % &f, "K = {K}");
+            assert_eq!(a.frobenius(), a.pow(P));
+            assert_eq!(a.frobenius_power(K), a);
+            if !a.is_zero() {
+                assert_eq!(a * a.inverse(), ctx.one());
+            }
+
+            let conjugates = (0..K).map(|i| a.frobenius_power(i));
+            let trace = conjugates.clone().fold(ctx.zero(), |s, c| s + c);
+            let norm = conjugates.fold(ctx.one(), |s, c| s * c);
+            assert_eq!(trace, ctx.from_base(a.trace()));
+            assert_eq!(norm, ctx.from_base(a.norm()));
+
+            let square = a.square();
+            let root = square.sqrt().unwrap();
+            assert!(root == a || root == -a);
+            assert_eq!(a.is_square(), a.sqrt().is_some());
+        }
+        assert_eq!(ctx.zero().sqrt(), Some(ctx.zero()));
+        assert_eq!(
+            ctx.zero().checked_inverse(),
+            Err(ModularError::NotInvertible)
+        );
+    }
+
+    fn to_poly<const P: u64, const K: usize>(a: &ExtField<'_, P, K>) -> Poly<P> {
+        Poly::new(
+            a.coefficients()
+                .iter()
+                .map(|c| ConstModInt::new(c.value()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_extension_fields() {
+        // x^K + x + c, irreducible for these c
+        check_field::<P998244353, 1>(&[5]);
+        check_field::<P998244353, 2>(&[1, 1]);
+        check_field::<P998244353, 3>(&[7, 1, 0]);
+        check_field::<P998244353, 5>(&[4, 1, 0, 0, 0]);
+        check_field::<P998244353, 8>(&[4, 1, 0, 0, 0, 0, 0, 0]);
+        check_field::<P998244353, 13>(&[16, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        check_field::<Q, 2>(&[4, 1]);
+        check_field::<Q, 5>(&[14, 1, 0, 0, 0]);
+        check_field::<Q, 13>(&[7, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        // A tiny field, where the non-square search has few candidates
+        check_field::<3, 2>(&[1, 0]);
+    }
+
+    #[test]
+    fn test_reducible_modulus() {
+        // x² - 4 = (x - 2)(x + 2)
+        assert_eq!(
+            ExtFieldContext::<P998244353, 2>::try_new(&[P998244353 - 4, 0]).unwrap_err(),
+            ModularError::ReducibleModulus
+        );
+        // (x² + 1)² has no roots modulo Q, but is not irreducible
+        assert_eq!(
+            ExtFieldContext::<Q, 4>::try_new(&[1, 0, 2, 0]).unwrap_err(),
+            ModularError::ReducibleModulus
+        );
+        assert_eq!(
+            ExtFieldContext::<Q, 0>::try_new(&[]).unwrap_err(),
+            ModularError::ReducibleModulus
+        );
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation)] // the low limb of the order
+    fn test_split_order() {
+        // 998244353² - 1 = 2^24 · 7 · 17 · 499122177
+        let (s, t) = split_order(P998244353, 2);
+        assert_eq!((s, t), (24, vec![7 * 17 * 499_122_177]));
+        // Q ≡ 3 mod 4 and Q² + Q + 1 is odd, so Q³ - 1 = 2 · t
+        let (s, t) = split_order(Q, 3);
+        let low = u128::from(Q).wrapping_pow(3) - 1;
+        assert_eq!((s, t.len()), (1, 3));
+        assert_eq!(t[0], (low >> 1) as u64);
+    }
+}
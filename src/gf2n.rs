@@ -0,0 +1,634 @@
+// Binary field arithmetic over GF(2^n)
+
+use crate::wide::carryless_mul;
+use crate::{pow_with, Exponent, ModularError, PowDomain, PowStrategy};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// An element of GF(2^DEGREE) = GF(2)[x] / (f) for f = x^DEGREE + POLY.
+///
+/// Bit i of the value is the coefficient of x^i, and `POLY` holds the terms of the
+/// field polynomial below x^DEGREE. The polynomial is checked for irreducibility at
+/// compile time, so any degree from 1 to 128 works, not just the named fields.
+/// Addition is XOR; multiplication is a carryless product (PCLMULQDQ or PMULL with
+/// hardware acceleration) followed by Barrett reduction modulo f, whose constant is
+/// also computed at compile time.
+///
+/// ```
+/// use modularity::gf2n::Gf2p8;
+///
+/// // FIPS-197: {57} · {83} = {c1}, and {53} and {ca} are inverses
+/// assert_eq!(Gf2p8::new(0x57) * Gf2p8::new(0x83), Gf2p8::new(0xC1));
+/// assert_eq!(Gf2p8::new(0x53).inverse(), Gf2p8::new(0xCA));
+/// ```
+///
+/// ```compile_fail
+/// use modularity::gf2n::GF2n;
+///
+/// let _ = GF2n::<8, 0x01>::new(3); // x^8 + 1 is reducible
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GF2n<const DEGREE: u32, const POLY: u128> {
+    value: u128,
+}
+
+/// GF(2^8) with the AES polynomial x^8 + x^4 + x^3 + x + 1.
+pub type Gf2p8 = GF2n<8, 0x1B>;
+
+/// GF(2^64) with the polynomial x^64 + x^4 + x^3 + x + 1.
+pub type Gf2p64 = GF2n<64, 0x1B>;
+
+/// GF(2^128) with the GHASH polynomial x^128 + x^7 + x^2 + x + 1.
+pub type Gf2p128 = GF2n<128, 0x87>;
+
+/// GF(2^128) with the POLYVAL polynomial x^128 + x^127 + x^126 + x^121 + 1.
+pub type Polyval = GF2n<128, POLYVAL_POLY>;
+
+const POLYVAL_POLY: u128 = (1 << 127) | (1 << 126) | (1 << 121) | 1;
+
+impl<const DEGREE: u32, const POLY: u128> GF2n<DEGREE, POLY> {
+    /// Rejects field polynomials that are out of range or reducible when the type is
+    /// instantiated.
+    const VALID_FIELD: () = assert!(
+        is_irreducible(DEGREE, POLY),
+        "x^DEGREE + POLY must be an irreducible polynomial of degree 1 to 128"
+    );
+
+    /// The bits below x^DEGREE.
+    const MASK: u128 = low_mask(DEGREE);
+
+    /// floor(x^(2·DEGREE) / f) without its leading x^DEGREE term, the Barrett
+    /// constant for reducing products.
+    const MU: u128 = barrett_mu(DEGREE, POLY);
+
+    /// The additive identity.
+    pub const ZERO: Self = Self::new(0);
+
+    /// The multiplicative identity.
+    pub const ONE: Self = Self::new(1);
+
+    /// Creates a field element from the coefficients of a polynomial, reducing it
+    /// modulo the field polynomial.
+    #[must_use]
+    pub const fn new(value: u128) -> Self {
+        let () = Self::VALID_FIELD;
+        Self {
+            value: reduce_bitwise(value, DEGREE, POLY),
+        }
+    }
+
+    /// Returns the coefficients of the element, bit i holding the coefficient of x^i.
+    #[must_use]
+    pub const fn value(self) -> u128 {
+        self.value
+    }
+
+    /// Returns the degree n of the field GF(2^n).
+    #[must_use]
+    pub const fn degree(self) -> u32 {
+        DEGREE
+    }
+
+    /// Returns true if this is the zero element.
+    #[must_use]
+    pub const fn is_zero(self) -> bool {
+        self.value == 0
+    }
+
+    /// Computes self².
+    #[must_use]
+    pub fn square(self) -> Self {
+        self * self
+    }
+
+    /// Computes self^exponent.
+    ///
+    /// Accepts any `Exponent`, including `u64` limb slices and `BigUint`.
+    #[must_use]
+    pub fn pow<E: Exponent>(self, exponent: E) -> Self {
+        pow_with(
+            &FieldDomain::<DEGREE, POLY>,
+            &self,
+            &exponent,
+            PowStrategy::Auto,
+        )
+    }
+
+    /// Computes the multiplicative inverse.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element is zero.
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        match self.checked_inverse() {
+            Ok(inverse) => inverse,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Computes the multiplicative inverse, returning an error for zero.
+    ///
+    /// Uses a^(2^n - 2) = a² · a⁴ ··· a^(2^(n-1)), so the running time does not depend
+    /// on the value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::NotInvertible` if the element is zero.
+    pub fn checked_inverse(self) -> Result<Self, ModularError> {
+        if self.is_zero() {
+            return Err(ModularError::NotInvertible);
+        }
+        let mut power = self;
+        let mut inverse = Self::ONE;
+        for _ in 1..DEGREE {
+            power = power.square();
+            inverse *= power;
+        }
+        Ok(inverse)
+    }
+
+    /// Reduces the 256-bit product hi·x^128 + lo, of degree below 2·DEGREE - 1.
+    fn reduce(hi: u128, lo: u128) -> Self {
+        // With P = H·x^n + L, the quotient by f is exactly floor(H·μ / x^n), where
+        // μ = x^n + MU, and the remainder is L + (quotient · POLY mod x^n)
+        let high = if DEGREE == 128 {
+            hi
+        } else {
+            (hi << (128 - DEGREE)) | (lo >> DEGREE)
+        };
+        let (mu_hi, mu_lo) = clmul128(high, Self::MU);
+        let quotient = high
+            ^ if DEGREE == 128 {
+                mu_hi
+            } else {
+                (mu_hi << (128 - DEGREE)) | (mu_lo >> DEGREE)
+            };
+        let (_, product) = clmul128(quotient, POLY);
+        Self {
+            value: (lo ^ product) & Self::MASK,
+        }
+    }
+}
+
+// Byte encodings of the 128-bit fields used by authenticated encryption
+impl Gf2p128 {
+    /// Decodes a GHASH block, in which the first bit of the first byte is the
+    /// coefficient of x^0.
+    #[must_use]
+    pub const fn from_ghash_bytes(bytes: [u8; 16]) -> Self {
+        Self::new(u128::from_be_bytes(bytes).reverse_bits())
+    }
+
+    /// Encodes the element as a GHASH block.
+    #[must_use]
+    pub const fn to_ghash_bytes(self) -> [u8; 16] {
+        self.value.reverse_bits().to_be_bytes()
+    }
+}
+
+impl Polyval {
+    /// Decodes a POLYVAL block, a little-endian integer whose bit i is the
+    /// coefficient of x^i.
+    #[must_use]
+    pub const fn from_le_bytes(bytes: [u8; 16]) -> Self {
+        Self::new(u128::from_le_bytes(bytes))
+    }
+
+    /// Encodes the element as a POLYVAL block.
+    #[must_use]
+    pub const fn to_le_bytes(self) -> [u8; 16] {
+        self.value.to_le_bytes()
+    }
+
+    /// The POLYVAL product dot(a, b) = a · b · x^-128 of RFC 8452.
+    #[must_use]
+    pub fn dot(self, other: Self) -> Self {
+        // x^-128 = x^127 + x^124 + x^121 + x^114 + 1 modulo the POLYVAL polynomial
+        const X_INV_128: Polyval =
+            Polyval::new((1 << 127) | (1 << 124) | (1 << 121) | (1 << 114) | 1);
+        self * other * X_INV_128
+    }
+}
+
+/// Computes GHASH (NIST SP 800-38D) of the blocks under the hash key H.
+///
+/// The caller supplies the padded additional data, ciphertext and length blocks.
+#[must_use]
+pub fn ghash(h: [u8; 16], blocks: &[[u8; 16]]) -> [u8; 16] {
+    let h = Gf2p128::from_ghash_bytes(h);
+    blocks
+        .iter()
+        .fold(Gf2p128::ZERO, |acc, &block| {
+            (acc + Gf2p128::from_ghash_bytes(block)) * h
+        })
+        .to_ghash_bytes()
+}
+
+/// Computes POLYVAL (RFC 8452) of the blocks under the hash key H.
+#[must_use]
+pub fn polyval(h: [u8; 16], blocks: &[[u8; 16]]) -> [u8; 16] {
+    // dot(S + X, H) = (S + X) · (H · x^-128), so fold x^-128 into the key once
+    let h = Polyval::from_le_bytes(h).dot(Polyval::ONE);
+    blocks
+        .iter()
+        .fold(Polyval::ZERO, |acc, &block| {
+            (acc + Polyval::from_le_bytes(block)) * h
+        })
+        .to_le_bytes()
+}
+
+// Implement Default trait for GF2n through `new`, which checks the field polynomial
+impl<const DEGREE: u32, const POLY: u128> Default for GF2n<DEGREE, POLY> {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+// Addition of polynomials over GF(2) is XOR
+impl<const DEGREE: u32, const POLY: u128> Add for GF2n<DEGREE, POLY> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, other: Self) -> Self::Output {
+        Self {
+            value: self.value ^ other.value,
+        }
+    }
+}
+
+// Implement AddAssign trait for GF2n
+impl<const DEGREE: u32, const POLY: u128> AddAssign for GF2n<DEGREE, POLY> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+// Subtraction is addition in characteristic 2
+impl<const DEGREE: u32, const POLY: u128> Sub for GF2n<DEGREE, POLY> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, other: Self) -> Self::Output {
+        self + other
+    }
+}
+
+// Implement SubAssign trait for GF2n
+impl<const DEGREE: u32, const POLY: u128> SubAssign for GF2n<DEGREE, POLY> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+// Every element is its own negative
+impl<const DEGREE: u32, const POLY: u128> Neg for GF2n<DEGREE, POLY> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self
+    }
+}
+
+// Implement Mul trait for GF2n
+impl<const DEGREE: u32, const POLY: u128> Mul for GF2n<DEGREE, POLY> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        let (hi, lo) = clmul128(self.value, other.value);
+        Self::reduce(hi, lo)
+    }
+}
+
+// Implement MulAssign trait for GF2n
+impl<const DEGREE: u32, const POLY: u128> MulAssign for GF2n<DEGREE, POLY> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+/// Exponentiation domain for the shared square-and-multiply engine.
+struct FieldDomain<const DEGREE: u32, const POLY: u128>;
+
+impl<const DEGREE: u32, const POLY: u128> PowDomain for FieldDomain<DEGREE, POLY> {
+    type Element = GF2n<DEGREE, POLY>;
+
+    fn one(&self) -> Self::Element {
+        GF2n::ONE
+    }
+
+    fn mul(&self, a: &Self::Element, b: &Self::Element) -> Self::Element {
+        *a * *b
+    }
+}
+
+/// Carryless 128 × 128 → 256-bit product as (high, low), with one Karatsuba step
+/// over three 64-bit products.
+#[allow(clippy::cast_possible_truncation)] // splits the operands into 64-bit halves
+fn clmul128(a: u128, b: u128) -> (u128, u128) {
+    let (a1, a0) = ((a >> 64) as u64, a as u64);
+    let (b1, b0) = ((b >> 64) as u64, b as u64);
+    let lo = join(carryless_mul(a0, b0));
+    let hi = join(carryless_mul(a1, b1));
+    let mid = join(carryless_mul(a0 ^ a1, b0 ^ b1)) ^ lo ^ hi;
+    (hi ^ (mid >> 64), lo ^ (mid << 64))
+}
+
+const fn join((hi, lo): (u64, u64)) -> u128 {
+    ((hi as u128) << 64) | lo as u128
+}
+
+const fn low_mask(degree: u32) -> u128 {
+    if degree >= 128 {
+        u128::MAX
+    } else {
+        (1 << degree) - 1
+    }
+}
+
+/// Returns the degree of a nonzero polynomial.
+const fn degree_of(a: u128) -> u32 {
+    a.ilog2()
+}
+
+/// Reduces a polynomial of degree below 128 modulo x^degree + poly, one leading term
+/// at a time.
+const fn reduce_bitwise(mut value: u128, degree: u32, poly: u128) -> u128 {
+    if degree == 128 {
+        return value;
+    }
+    while value != 0 && degree_of(value) >= degree {
+        let shift = degree_of(value) - degree;
+        value ^= (1 << degree_of(value)) | (poly << shift);
+    }
+    value
+}
+
+/// Computes a · x modulo x^degree + poly for a of degree below `degree`.
+const fn times_x(a: u128, degree: u32, poly: u128) -> u128 {
+    let carry = (a >> (degree - 1)) & 1;
+    let shifted = (a << 1) & low_mask(degree);
+    if carry == 1 {
+        shifted ^ poly
+    } else {
+        shifted
+    }
+}
+
+/// Shift-and-add multiplication modulo x^degree + poly, for compile-time use.
+const fn mul_bitwise(a: u128, b: u128, degree: u32, poly: u128) -> u128 {
+    let mut result = 0;
+    let mut i = degree;
+    while i > 0 {
+        i -= 1;
+        result = times_x(result, degree, poly);
+        if (b >> i) & 1 == 1 {
+            result ^= a;
+        }
+    }
+    result
+}
+
+/// Computes floor(x^(2·degree) / f) - x^degree by long division.
+const fn barrett_mu(degree: u32, poly: u128) -> u128 {
+    if degree == 0 || degree > 128 {
+        return 0;
+    }
+    // The remainder after bringing down the leading 1 of x^(2n); each later bit of
+    // the dividend is zero, and a quotient bit is set whenever the remainder reaches
+    // degree n
+    let mut remainder = 1u128;
+    let mut mu = 0u128;
+    let mut j = 2 * degree;
+    while j > 0 {
+        j -= 1;
+        let carry = (remainder >> (degree - 1)) & 1;
+        remainder = (remainder << 1) & low_mask(degree);
+        if carry == 1 {
+            remainder ^= poly;
+            if j < degree {
+                mu |= 1 << j;
+            }
+        }
+    }
+    mu
+}
+
+/// Remainder of a by a nonzero b in GF(2)[x].
+const fn poly_rem(mut a: u128, b: u128) -> u128 {
+    let db = degree_of(b);
+    while a != 0 && degree_of(a) >= db {
+        a ^= b << (degree_of(a) - db);
+    }
+    a
+}
+
+/// Returns true if gcd(g, x^degree + poly) = 1 for g of degree below `degree`.
+#[allow(clippy::many_single_char_names)] // the Euclidean remainders a, b, g, d
+const fn coprime_to_field_poly(g: u128, degree: u32, poly: u128) -> bool {
+    if g == 0 {
+        return false;
+    }
+    let d = degree_of(g);
+    if d == 0 {
+        return true;
+    }
+    // f mod g = (x^degree mod g) + (poly mod g); f itself may not fit in 128 bits
+    let mut x_power = 1u128;
+    let mut i = 0;
+    while i < degree {
+        x_power <<= 1;
+        if (x_power >> d) & 1 == 1 {
+            x_power ^= g;
+        }
+        i += 1;
+    }
+    let (mut a, mut b) = (g, x_power ^ poly_rem(poly, g));
+    while b != 0 {
+        let r = poly_rem(a, b);
+        a = b;
+        b = r;
+    }
+    a == 1
+}
+
+/// Returns true if x^degree + poly is irreducible over GF(2), for degree 1 to 128 and
+/// poly of degree below `degree`.
+///
+/// Uses Rabin's test: f of degree n is irreducible exactly when x^(2^n) ≡ x (mod f)
+/// and gcd(x^(2^(n/q)) - x, f) = 1 for every prime q dividing n.
+///
+/// ```
+/// use modularity::gf2n::is_irreducible;
+///
+/// assert!(is_irreducible(8, 0x1B)); // the AES polynomial
+/// assert!(!is_irreducible(8, 0x01)); // x^8 + 1 = (x + 1)^8
+/// ```
+#[must_use]
+pub const fn is_irreducible(degree: u32, poly: u128) -> bool {
+    if degree == 0 || degree > 128 || (degree < 128 && poly >> degree != 0) {
+        return false;
+    }
+    if degree == 1 {
+        return true;
+    }
+    let x = 2u128;
+    let mut power = x; // x^(2^i) mod f
+    let mut i = 1;
+    while i <= degree {
+        power = mul_bitwise(power, power, degree, poly);
+        if i < degree
+            && degree.is_multiple_of(i)
+            && is_small_prime(degree / i)
+            && !coprime_to_field_poly(power ^ x, degree, poly)
+        {
+            return false;
+        }
+        i += 1;
+    }
+    power == x
+}
+
+const fn is_small_prime(n: u32) -> bool {
+    if n < 2 {
+        return false;
+    }
+    let mut d = 2;
+    while d * d <= n {
+        if n.is_multiple_of(d) {
+            return false;
+        }
+        d += 1;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex_block(hex: &str) -> [u8; 16] {
+        let mut block = [0u8; 16];
+        for (i, byte) in block.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap();
+        }
+        block
+    }
+
+    /// Multiplication by shift-and-add, independent of the Barrett reduction.
+    fn naive_mul<const DEGREE: u32, const POLY: u128>(
+        a: GF2n<DEGREE, POLY>,
+        b: GF2n<DEGREE, POLY>,
+    ) -> u128 {
+        mul_bitwise(a.value(), b.value(), DEGREE, POLY)
+    }
+
+    fn check_field<const DEGREE: u32, const POLY: u128>() {
+        let mut x = 0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210u128;
+        for _ in 0..50 {
+            x = x
+                .wrapping_mul(0x2360_ED05_1FC6_5DA4_4385_DF64_9FCC_F645)
+                .wrapping_add(1);
+            let a = GF2n::<DEGREE, POLY>::new(x);
+            let b = GF2n::<DEGREE, POLY>::new(x.rotate_left(41));
+            assert_eq!((a * b).value(), naive_mul(a, b), "degree {DEGREE}");
+            assert_eq!(a.square(), a * a);
+            if !a.is_zero() {
+                assert_eq!(a * a.inverse(), GF2n::ONE);
+            }
+            // Frobenius: a^(2^n) = a
+            let mut frobenius = a;
+            for _ in 0..DEGREE {
+                frobenius = frobenius.square();
+            }
+            assert_eq!(frobenius, a);
+        }
+    }
+
+    #[test]
+    fn test_fields_match_bitwise_multiplication() {
+        check_field::<8, 0x1B>();
+        check_field::<64, 0x1B>();
+        check_field::<128, 0x87>();
+        check_field::<128, POLYVAL_POLY>();
+        // Less common degrees, including a dense polynomial
+        check_field::<1, 1>();
+        check_field::<3, 0b011>();
+        check_field::<31, 0b1001>();
+        check_field::<127, 0b11>();
+    }
+
+    #[test]
+    fn test_aes_field() {
+        assert_eq!(Gf2p8::new(0x57) * Gf2p8::new(0x83), Gf2p8::new(0xC1));
+        assert_eq!(Gf2p8::new(0x53).inverse(), Gf2p8::new(0xCA));
+        assert_eq!(Gf2p8::new(0x1B), Gf2p8::new(0x100));
+        // The multiplicative group has order 255
+        assert_eq!(Gf2p8::new(0x03).pow(255u64), Gf2p8::ONE);
+        assert_eq!(
+            Gf2p8::ZERO.checked_inverse(),
+            Err(ModularError::NotInvertible)
+        );
+        assert_eq!(Gf2p8::new(0x53) - Gf2p8::new(0x53), Gf2p8::ZERO);
+    }
+
+    #[test]
+    fn test_wide_fields() {
+        let a = Gf2p64::new(0x0123_4567_89AB_CDEF);
+        let b = Gf2p64::new(0xFEDC_BA98_7654_3210);
+        assert_eq!((a * b).value(), 0x4882_7AB5_5D97_6FA0);
+
+        let a = 0x0123_4567_89AB_CDEF_0011_2233_4455_6677;
+        let b = 0xFEDC_BA98_7654_3210_AABB_CCDD_EEFF_0099;
+        assert_eq!(
+            (Gf2p128::new(a) * Gf2p128::new(b)).value(),
+            0xCA9F_1D75_BFEA_5881_C0C3_FF11_8291_1242
+        );
+        assert_eq!(
+            (Polyval::new(a) * Polyval::new(b)).value(),
+            0xD0A2_4FD2_47A4_57FD_1D87_D707_5112_270B
+        );
+        assert_eq!(Gf2p128::new(a).pow(u128::MAX), Gf2p128::ONE);
+    }
+
+    #[test]
+    fn test_ghash_and_polyval_vectors() {
+        // GCM specification, test case 2
+        let h = hex_block("66e94bd4ef8a2c3b884cfa59ca342b2e");
+        let ciphertext = hex_block("0388dace60b6a392f328c2b971b2fe78");
+        let lengths = hex_block("00000000000000000000000000000080");
+        assert_eq!(
+            ghash(h, &[ciphertext, lengths]),
+            hex_block("f38cbb1ad69223dcc3457ae5b6b0f885")
+        );
+
+        // RFC 8452, Appendix A
+        let h = hex_block("25629347589242761d31f826ba4b757b");
+        let x1 = hex_block("4f4f95668c83dfb6401762bb2d01a262");
+        let x2 = hex_block("d1a24ddd2721d006bbe45f20d3c9f362");
+        assert_eq!(
+            polyval(h, &[x1, x2]),
+            hex_block("f7a3b47b846119fae5b7866cf5e5b77e")
+        );
+    }
+
+    #[test]
+    fn test_irreducibility() {
+        assert!(is_irreducible(128, 0x87));
+        assert!(is_irreducible(128, POLYVAL_POLY));
+        assert!(is_irreducible(64, 0x1B));
+        assert!(is_irreducible(2, 0b11));
+        assert!(!is_irreducible(2, 0b01)); // (x + 1)^2
+        assert!(!is_irreducible(4, 0b0101)); // (x^2 + x + 1)^2
+        assert!(!is_irreducible(8, 0x100));
+        assert!(!is_irreducible(0, 0));
+        assert!(!is_irreducible(129, 1));
+        // Brute force for small degrees: no factor of degree at most n/2
+        for degree in 2..=10u32 {
+            for poly in 0..1u128 << degree {
+                let f = (1u128 << degree) | poly;
+                let reducible = (2..1u128 << (degree / 2 + 1)).any(|g| poly_rem(f, g) == 0);
+                assert_eq!(is_irreducible(degree, poly), !reducible, "{f:#b}");
+            }
+        }
+    }
+}
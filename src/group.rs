@@ -0,0 +1,291 @@
+// The multiplicative group of units modulo n
+
+use crate::factor::{factor, lambda_from_factors, phi_from_factors};
+use crate::{ModularError, ModularInt, ModularStorage, PowStrategy};
+use num_integer::Integer;
+
+/// The group of units modulo n, with the factorizations that order computations
+/// need precomputed once.
+///
+/// Creating the context factors n and its exponent λ(n) (Carmichael's function) and
+/// finds the smallest unit of order λ(n), so each later order or generator query
+/// costs only a few modular exponentiations per prime factor of λ(n).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitGroup {
+    modulus: u64,
+    order: u64,    // φ(n)
+    exponent: u64, // λ(n)
+    exponent_factors: Vec<(u64, u32)>,
+    cyclic: bool,
+    maximal: u64, // the smallest unit of order λ(n)
+}
+
+impl UnitGroup {
+    /// Creates the unit group modulo n.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the modulus is zero.
+    #[must_use]
+    pub fn new(modulus: u64) -> Self {
+        match Self::try_new(modulus) {
+            Ok(group) => group,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Creates the unit group, returning an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::ZeroModulus` if the modulus is zero.
+    pub fn try_new(modulus: u64) -> Result<Self, ModularError> {
+        if modulus == 0 {
+            return Err(ModularError::ZeroModulus);
+        }
+        let factors = factor(modulus);
+        let order = phi_from_factors(&factors);
+        let exponent = lambda_from_factors(&factors);
+        let mut group = Self {
+            modulus,
+            order,
+            exponent,
+            exponent_factors: factor(exponent),
+            // Units modulo n form a cyclic group exactly when λ(n) = φ(n), which
+            // happens for n = 1, 2, 4, p^k and 2·p^k
+            cyclic: exponent == order,
+            maximal: 0,
+        };
+        // Elements of order λ(n) exist in every finite abelian group, so the search
+        // always succeeds, and they are common enough for a linear search
+        group.maximal = (0..modulus)
+            .find(|&a| group.has_maximal_order(a))
+            .unwrap_or_default();
+        Ok(group)
+    }
+
+    /// Returns the modulus n.
+    #[must_use]
+    pub const fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// Returns the number of units, Euler's totient φ(n).
+    #[must_use]
+    pub const fn order(&self) -> u64 {
+        self.order
+    }
+
+    /// Returns the exponent of the group, Carmichael's λ(n): every unit's order
+    /// divides it, and some unit has exactly this order.
+    #[must_use]
+    pub const fn exponent(&self) -> u64 {
+        self.exponent
+    }
+
+    /// Returns the factorization of λ(n) as (prime, exponent) pairs.
+    #[must_use]
+    pub fn exponent_factors(&self) -> &[(u64, u32)] {
+        &self.exponent_factors
+    }
+
+    /// Returns true if the group is cyclic, i.e. n has a primitive root.
+    #[must_use]
+    pub const fn is_cyclic(&self) -> bool {
+        self.cyclic
+    }
+
+    /// Returns true if a is a unit modulo n.
+    #[must_use]
+    pub fn is_unit(&self, a: u64) -> bool {
+        (a % self.modulus).gcd(&self.modulus) == 1
+    }
+
+    /// Returns the least k ≥ 1 with a^k ≡ 1 (mod n), or `None` if a is not a unit.
+    ///
+    /// Starts from λ(n) and strips each prime factor p while a^(k/p) stays 1.
+    #[must_use]
+    pub fn multiplicative_order(&self, a: u64) -> Option<u64> {
+        if !self.is_unit(a) {
+            return None;
+        }
+        let mut order = self.exponent;
+        for &(p, e) in &self.exponent_factors {
+            for _ in 0..e {
+                if self.pow(a, order / p) != self.one() {
+                    break;
+                }
+                order /= p;
+            }
+        }
+        Some(order)
+    }
+
+    /// Returns true if a generates the whole unit group, which requires the group to
+    /// be cyclic.
+    #[must_use]
+    pub fn is_generator(&self, a: u64) -> bool {
+        self.cyclic && self.has_maximal_order(a)
+    }
+
+    /// Returns the smallest primitive root modulo n, or `None` if the group is not
+    /// cyclic.
+    #[must_use]
+    pub fn primitive_root(&self) -> Option<u64> {
+        // In a cyclic group the units of maximal order are the generators
+        self.cyclic.then_some(self.maximal)
+    }
+
+    /// Returns an element of order exactly k, or `None` if k does not divide λ(n).
+    ///
+    /// For a prime modulus this is a principal k-th root of unity, as needed for a
+    /// length-k number-theoretic transform. For a composite modulus the root has
+    /// order k, but 1 - ω^j need not be a unit.
+    #[must_use]
+    pub fn root_of_unity(&self, k: u64) -> Option<u64> {
+        if k == 0 || !self.exponent.is_multiple_of(k) {
+            return None;
+        }
+        Some(self.pow(self.maximal, self.exponent / k))
+    }
+
+    /// Returns true if a is a unit whose order is λ(n).
+    fn has_maximal_order(&self, a: u64) -> bool {
+        self.is_unit(a)
+            && self
+                .exponent_factors
+                .iter()
+                .all(|&(p, _)| self.pow(a, self.exponent / p) != self.one())
+    }
+
+    const fn one(&self) -> u64 {
+        1 % self.modulus
+    }
+
+    fn pow(&self, base: u64, exponent: u64) -> u64 {
+        u64::pow_mod(base, &exponent, self.modulus, PowStrategy::Auto)
+    }
+}
+
+/// Returns the smallest primitive root modulo n, or `None` if there is none.
+///
+/// ```
+/// use modularity::group::primitive_root;
+///
+/// assert_eq!(primitive_root(998_244_353), Some(3));
+/// assert_eq!(primitive_root(8), None);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the modulus is zero.
+#[must_use]
+pub fn primitive_root(modulus: u64) -> Option<u64> {
+    UnitGroup::new(modulus).primitive_root()
+}
+
+// Order queries on a single residue; build a `UnitGroup` to reuse the factorization
+impl ModularInt<u64> {
+    /// Returns the multiplicative order of the value, or `None` if it is not a unit.
+    #[must_use]
+    pub fn multiplicative_order(&self) -> Option<u64> {
+        UnitGroup::new(self.modulus()).multiplicative_order(self.value())
+    }
+
+    /// Returns true if the value is a primitive root of its modulus.
+    #[must_use]
+    pub fn is_generator(&self) -> bool {
+        UnitGroup::new(self.modulus()).is_generator(self.value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ntt::{GOLDILOCKS, P998244353};
+
+    fn naive_order(a: u64, n: u64) -> Option<u64> {
+        if a.gcd(&n) != 1 {
+            return None;
+        }
+        let mut x = a % n;
+        (1..=n).find(|_| {
+            let done = x == 1 % n;
+            x = u64::mul_mod(x, a, n);
+            done
+        })
+    }
+
+    #[test]
+    fn test_orders_match_brute_force() {
+        for n in 1..120 {
+            let group = UnitGroup::new(n);
+            let units = (0..n).filter(|&a| group.is_unit(a)).count() as u64;
+            assert_eq!(group.order(), units);
+            for a in 0..n {
+                let order = group.multiplicative_order(a);
+                assert_eq!(order, naive_order(a, n), "ord({a}) mod {n}");
+                assert_eq!(group.is_generator(a), order == Some(group.order()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_primitive_roots() {
+        assert_eq!(primitive_root(P998244353), Some(3));
+        assert_eq!(primitive_root(GOLDILOCKS), Some(7));
+        assert_eq!(primitive_root(u64::MAX - 58), Some(2));
+        assert_eq!(primitive_root(18), Some(5));
+        assert_eq!(primitive_root(2), Some(1));
+        assert_eq!(primitive_root(15), None);
+
+        assert!(ModularInt::new(3, P998244353).is_generator());
+        assert!(!ModularInt::new(4, P998244353).is_generator());
+        assert_eq!(
+            ModularInt::new(7, GOLDILOCKS).multiplicative_order(),
+            Some(GOLDILOCKS - 1)
+        );
+        assert_eq!(ModularInt::new(6, 9).multiplicative_order(), None);
+    }
+
+    #[test]
+    fn test_roots_of_unity() {
+        let group = UnitGroup::new(P998244353);
+        let root = group.root_of_unity(1 << 23).unwrap();
+        assert_eq!(group.multiplicative_order(root), Some(1 << 23));
+        assert_eq!(
+            u64::pow_mod(root, &(1u64 << 22), P998244353, PowStrategy::Auto),
+            P998244353 - 1
+        );
+        assert_eq!(group.root_of_unity(3), None);
+        for k in [119, 7 << 20, 17 * 3] {
+            let expected = (P998244353 - 1).is_multiple_of(k).then_some(k);
+            let order = group
+                .root_of_unity(k)
+                .map(|r| group.multiplicative_order(r));
+            assert_eq!(order, expected.map(Some), "k = {k}");
+        }
+
+        let goldilocks = UnitGroup::new(GOLDILOCKS);
+        let root = goldilocks.root_of_unity(1 << 32).unwrap();
+        assert_eq!(goldilocks.multiplicative_order(root), Some(1 << 32));
+        let root = goldilocks.root_of_unity(3 * 65_537).unwrap();
+        assert_eq!(goldilocks.multiplicative_order(root), Some(3 * 65_537));
+
+        // λ(15) = 4 although φ(15) = 8
+        let composite = UnitGroup::new(15);
+        assert!(!composite.is_cyclic());
+        assert_eq!(composite.exponent(), 4);
+        let root = composite.root_of_unity(4).unwrap();
+        assert_eq!(composite.multiplicative_order(root), Some(4));
+        assert_eq!(composite.root_of_unity(8), None);
+
+        // λ(63) = lcm(6, 6) = 6
+        let composite = UnitGroup::new(63);
+        for k in [3, 6] {
+            let root = composite.root_of_unity(k).unwrap();
+            assert_eq!(composite.multiplicative_order(root), Some(k));
+        }
+        assert_eq!(composite.root_of_unity(9), None);
+    }
+}
@@ -1,31 +1,75 @@
 // Hardware acceleration through CPU intrinsics
 
+use crate::{MontgomeryContext, Uint};
+
 /// Detection of hardware features for acceleration.
+///
+/// Features are probed once per process and cached, so the checks are cheap enough
+/// to sit on dispatch paths.
 pub mod feature_detection {
-    #[cfg(target_arch = "aarch64")]
-    use std::arch::aarch64::*;
+    use std::sync::OnceLock;
+
+    /// The instruction set extensions the accelerated paths can use.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    #[allow(clippy::struct_excessive_bools)] // one flag per CPU feature
+    pub struct CpuFeatures {
+        /// BMI2, for MULX (x86_64).
+        pub bmi2: bool,
+        /// ADX, for the ADCX/ADOX carry chains (x86_64).
+        pub adx: bool,
+        /// PCLMULQDQ carryless multiplication (x86_64).
+        pub pclmulqdq: bool,
+        /// AVX2 (x86_64).
+        pub avx2: bool,
+        /// AVX-512F with the IFMA 52-bit multiply-add extension (x86_64).
+        pub avx512ifma: bool,
+        /// PMULL polynomial multiplication (aarch64).
+        pub pmull: bool,
+    }
+
+    /// Returns the features of the running CPU, detected on the first call.
+    #[must_use]
+    pub fn cpu_features() -> CpuFeatures {
+        static FEATURES: OnceLock<CpuFeatures> = OnceLock::new();
+        *FEATURES.get_or_init(detect)
+    }
+
     #[cfg(target_arch = "x86_64")]
-    use std::arch::x86_64::*;
+    fn detect() -> CpuFeatures {
+        CpuFeatures {
+            bmi2: is_x86_feature_detected!("bmi2"),
+            adx: is_x86_feature_detected!("adx"),
+            pclmulqdq: is_x86_feature_detected!("pclmulqdq") && is_x86_feature_detected!("sse4.1"),
+            avx2: is_x86_feature_detected!("avx2"),
+            avx512ifma: is_x86_feature_detected!("avx512f")
+                && is_x86_feature_detected!("avx512ifma"),
+            pmull: false,
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    fn detect() -> CpuFeatures {
+        CpuFeatures {
+            pmull: std::arch::is_aarch64_feature_detected!("pmull"),
+            ..CpuFeatures::default()
+        }
+    }
+
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    fn detect() -> CpuFeatures {
+        CpuFeatures::default()
+    }
 
     /// Checks if hardware acceleration features are available.
+    ///
+    /// On x86_64 this needs BMI2 and ADX for MULX-based Montgomery reduction. Other
+    /// targets have no kernel that beats the portable code and always report `false`.
+    #[must_use]
     pub fn is_hardware_acceleration_available() -> bool {
-        #[cfg(target_arch = "x86_64")]
-        {
-            // Check for ADX and BMI2 support on x86_64, which are needed for mulx
-            unsafe {
-                let cpuid = __cpuid(7);
-                let has_bmi2 = (cpuid.ebx & (1 << 8)) != 0;
-                let has_adx = (cpuid.ebx & (1 << 19)) != 0;
-                has_bmi2 && has_adx
-            }
-        }
-        #[cfg(target_arch = "aarch64")]
-        {
-            // On ARM, we can use NEON instructions which are widely available
-            true
-        }
-        #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
-        {
+        if cfg!(target_arch = "x86_64") {
+            let features = cpu_features();
+            features.bmi2 && features.adx
+        } else {
             false
         }
     }
@@ -33,165 +77,871 @@ pub mod feature_detection {
 
 /// Low-level functions using CPU intrinsics for accelerated modular arithmetic.
 pub mod arithmetic {
-    #[cfg(target_arch = "aarch64")]
-    use std::arch::aarch64::*;
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    use super::feature_detection::cpu_features;
     #[cfg(target_arch = "x86_64")]
-    use std::arch::x86_64::*;
+    use std::arch::x86_64::{
+        _addcarryx_u64, _mm_clmulepi64_si128, _mm_extract_epi64, _mm_set_epi64x, _mulx_u64,
+        _subborrow_u64,
+    };
 
-    /// Performs a multiply-add operation with carry using hardware intrinsics.
+    /// Performs a multiply-add operation with carry, using MULX and ADX when the CPU
+    /// has them.
     ///
     /// Returns (a * b + c + carry) as a tuple (high, low) where high is the high bits
     /// and low is the low bits of the result.
+    #[inline]
+    #[must_use]
+    pub fn mul_add_carry(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+        #[cfg(target_arch = "x86_64")]
+        if cpu_features().bmi2 && cpu_features().adx {
+            // SAFETY: BMI2 and ADX were detected at runtime
+            return unsafe { mul_add_carry_mulx(a, b, c, carry) };
+        }
+        crate::wide::mul_add_carry(a, b, c, carry)
+    }
+
     #[cfg(target_arch = "x86_64")]
-    pub unsafe fn mul_add_carry(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
-        if feature_detection::is_hardware_acceleration_available() {
-            // Using mulx and adcx from BMI2 and ADX instruction sets
-            let mut lo: u64 = 0;
-            let mut hi: u64 = 0;
-            let mut cin: u8 = 0;
+    #[target_feature(enable = "bmi2,adx")]
+    #[inline]
+    fn mul_add_carry_mulx(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+        let mut hi = 0;
+        let mut lo = _mulx_u64(a, b, &mut hi);
+        // lo + c and lo + carry, each with a carry out; a * b + c + carry < 2^128,
+        // so the carries cannot overflow hi
+        let carry_1 = _addcarryx_u64(0, lo, c, &mut lo);
+        let carry_2 = _addcarryx_u64(0, lo, carry, &mut lo);
+        (hi + u64::from(carry_1) + u64::from(carry_2), lo)
+    }
 
-            // Use _mulx_u64 for a * b -> (hi, lo)
-            lo = _mulx_u64(a, b, &mut hi);
+    /// Performs a carryless multiplication, using PCLMULQDQ on x86_64 or PMULL on
+    /// aarch64 when available and the portable shift-and-xor loop otherwise.
+    ///
+    /// This is useful for certain cryptographic operations like GCM.
+    #[must_use]
+    pub fn carryless_mul(a: u64, b: u64) -> (u64, u64) {
+        #[cfg(target_arch = "x86_64")]
+        if cpu_features().pclmulqdq {
+            // SAFETY: PCLMULQDQ and SSE4.1 were detected at runtime
+            return unsafe { carryless_mul_pclmulqdq(a, b) };
+        }
+        #[cfg(target_arch = "aarch64")]
+        if cpu_features().pmull {
+            // SAFETY: PMULL was detected at runtime
+            return unsafe { carryless_mul_pmull(a, b) };
+        }
+        crate::wide::carryless_mul_portable(a, b)
+    }
 
-            // Use _addcarryx_u64 for lo + c -> lo, with carry out
-            cin = _addcarryx_u64(0, lo, c, &mut lo);
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "pclmulqdq,sse4.1")]
+    fn carryless_mul_pclmulqdq(a: u64, b: u64) -> (u64, u64) {
+        let a_xmm = _mm_set_epi64x(0, a.cast_signed());
+        let b_xmm = _mm_set_epi64x(0, b.cast_signed());
+        let result = _mm_clmulepi64_si128::<0>(a_xmm, b_xmm);
+        let lo = _mm_extract_epi64::<0>(result).cast_unsigned();
+        let hi = _mm_extract_epi64::<1>(result).cast_unsigned();
+        (hi, lo)
+    }
 
-            // Use _addcarryx_u64 for hi + carry_in + carry -> hi
-            _addcarryx_u64(cin, hi, carry, &mut hi);
+    #[cfg(target_arch = "aarch64")]
+    #[target_feature(enable = "neon,aes")]
+    #[allow(clippy::cast_possible_truncation)] // splits the 128-bit product into halves
+    fn carryless_mul_pmull(a: u64, b: u64) -> (u64, u64) {
+        #[allow(unused_unsafe)] // the intrinsic is safe to call on newer toolchains
+        let product = unsafe { std::arch::aarch64::vmull_p64(a, b) };
+        ((product >> 64) as u64, product as u64)
+    }
 
-            (hi, lo)
+    /// Montgomery product a * b * 2^-64 mod n with MULX and ADX, for residues below
+    /// the odd modulus n and n_prime = -n⁻¹ mod 2^64.
+    ///
+    /// # Safety
+    ///
+    /// The CPU must support BMI2 and ADX.
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "bmi2,adx")]
+    #[must_use]
+    pub unsafe fn montgomery_mul_mulx(a: u64, b: u64, modulus: u64, n_prime: u64) -> u64 {
+        let mut hi = 0;
+        let lo = _mulx_u64(a, b, &mut hi);
+        let m = lo.wrapping_mul(n_prime);
+        let mut mn_hi = 0;
+        let mn_lo = _mulx_u64(m, modulus, &mut mn_hi);
+        // lo + mn_lo is 0 mod 2^64 by the choice of m; only its carry matters
+        let mut low = 0;
+        let carry = _addcarryx_u64(0, lo, mn_lo, &mut low);
+        let mut t = 0;
+        let overflow = _addcarryx_u64(carry, hi, mn_hi, &mut t);
+        // t + overflow·2^64 < 2n: subtract n once if it is at least n
+        let mut reduced = 0;
+        let borrow = _subborrow_u64(0, t, modulus, &mut reduced);
+        if overflow != 0 || borrow == 0 {
+            reduced
         } else {
-            // Fallback implementation
-            let a_b = a as u128 * b as u128;
-            let a_b_c = a_b + c as u128;
-            let result = a_b_c + carry as u128;
-            ((result >> 64) as u64, result as u64)
+            t
         }
     }
 
-    /// Performs a carryless multiplication using hardware intrinsics.
+    /// Montgomery products a[i] = a[i] * b[i] * 2^-64 mod n with MULX and ADX.
     ///
-    /// This is useful for certain cryptographic operations like GCM.
+    /// # Safety
+    ///
+    /// The CPU must support BMI2 and ADX.
     #[cfg(target_arch = "x86_64")]
-    pub unsafe fn carryless_mul(a: u64, b: u64) -> (u64, u64) {
-        if is_pclmulqdq_available() {
-            // Using _mm_clmulepi64_si128 from PCLMULQDQ
-            let a_xmm = _mm_set_epi64x(0, a as i64);
-            let b_xmm = _mm_set_epi64x(0, b as i64);
-
-            // Perform carryless multiplication
-            let result = _mm_clmulepi64_si128(a_xmm, b_xmm, 0);
-
-            // Extract the 128-bit result
-            let lo = _mm_extract_epi64(result, 0) as u64;
-            let hi = _mm_extract_epi64(result, 1) as u64;
+    #[target_feature(enable = "bmi2,adx")]
+    pub unsafe fn montgomery_mul_slices_mulx(a: &mut [u64], b: &[u64], modulus: u64, n_prime: u64) {
+        for (x, &y) in a.iter_mut().zip(b) {
+            *x = montgomery_mul_mulx(*x, y, modulus, n_prime);
+        }
+    }
 
-            (hi, lo)
+    /// Runs the multi-limb CIOS Montgomery product of `montgomery_mul_limbs_mulx` when
+    /// the CPU has BMI2 and ADX.
+    ///
+    /// Returns false without touching `out` otherwise, so the caller can fall back to
+    /// the portable loop. The features are checked once per product, not per limb.
+    #[cfg(target_arch = "x86_64")]
+    pub fn montgomery_mul_limbs(
+        a: &[u64],
+        b: &[u64],
+        modulus: &[u64],
+        n_prime: u64,
+        out: &mut [u64],
+    ) -> bool {
+        let features = cpu_features();
+        if features.bmi2 && features.adx {
+            // SAFETY: BMI2 and ADX were detected at runtime
+            unsafe { montgomery_mul_limbs_mulx(a, b, modulus, n_prime, out) };
+            true
         } else {
-            // Fallback implementation for carryless multiplication
-            carryless_mul_software(a, b)
+            false
         }
     }
 
-    /// Check if PCLMULQDQ instruction is available
+    /// Montgomery product a * b * R⁻¹ mod N of multi-limb residues with MULX and ADX.
+    ///
+    /// All operands are little-endian limbs of the same length as the odd modulus N,
+    /// with a, b < N, R = 2^(64 * limbs) and n_prime = -N⁻¹ mod 2^64; the result is
+    /// written to `out`. This is the portable CIOS loop with both rows of each step
+    /// run by `mul_add_row_adx`.
+    ///
+    /// # Safety
+    ///
+    /// The CPU must support BMI2 and ADX.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `out` is shorter than the modulus.
     #[cfg(target_arch = "x86_64")]
-    unsafe fn is_pclmulqdq_available() -> bool {
-        let cpuid = __cpuid(1);
-        (cpuid.ecx & (1 << 1)) != 0 // Check for PCLMULQDQ bit
+    #[target_feature(enable = "bmi2,adx")]
+    #[allow(clippy::many_single_char_names)] // s, t, m, a, b as in the CIOS description
+    pub unsafe fn montgomery_mul_limbs_mulx(
+        a: &[u64],
+        b: &[u64],
+        modulus: &[u64],
+        n_prime: u64,
+        out: &mut [u64],
+    ) {
+        let s = modulus.len();
+        assert!(a.len() >= s, "Length mismatch in montgomery_mul_limbs");
+        let t = &mut out[..s];
+        t.fill(0);
+        let mut t_s = 0u64;
+
+        for &b_i in b {
+            // t += a * b_i
+            let t_ptr = t.as_mut_ptr();
+            // SAFETY: a and t hold s words, and the row reads each word of t before
+            // writing it back
+            let carry = unsafe { mul_add_row_adx(t_ptr, t_ptr, a.as_ptr(), b_i, s, 0) };
+            let (t_s0, overflow) = t_s.overflowing_add(carry);
+            let t_s1 = u64::from(overflow);
+
+            // t = (t + m * N) / 2^64, where m makes the lowest word vanish, so the
+            // first column only contributes its high word and a carry when t[0] != 0
+            let m = t[0].wrapping_mul(n_prime);
+            let mut high = 0;
+            _mulx_u64(m, modulus[0], &mut high);
+            let carry = high + u64::from(t[0] != 0);
+            // SAFETY: the row reads t[1..s] and N[1..s] and writes t[0..s - 1], each
+            // word of t before the one read after it
+            let carry = unsafe {
+                mul_add_row_adx(t_ptr.add(1), t_ptr, modulus[1..].as_ptr(), m, s - 1, carry)
+            };
+            let (top, overflow) = t_s0.overflowing_add(carry);
+            t[s - 1] = top;
+            t_s = t_s1 + u64::from(overflow);
+        }
+
+        crate::montgomery::subtract_modulus_once(t, t_s, modulus);
     }
 
-    /// Software implementation of carryless multiplication for fallback
+    /// Writes src[j] + a[j] * b plus the carries of the row to dst[j] for j < len,
+    /// starting from the carry word `carry`, and returns the carry word out.
+    ///
+    /// ADCX adds the low words of the products on the carry flag while ADOX adds the
+    /// high words of the previous column on the overflow flag, so the two chains run
+    /// side by side. The loops only touch the flags through those two, using LEA and
+    /// JRCXZ for the bookkeeping. The carry out is below 2^64 because the row's value
+    /// is below 2^(64 * (len + 1)).
+    ///
+    /// # Safety
+    ///
+    /// The CPU must support BMI2 and ADX; `src` and `a` must be readable and `dst`
+    /// writable for `len` words, and `dst` may only overlap `src` at or below the
+    /// word being read.
     #[cfg(target_arch = "x86_64")]
-    fn carryless_mul_software(a: u64, b: u64) -> (u64, u64) {
-        let mut result_hi = 0u64;
-        let mut result_lo = 0u64;
-
-        for i in 0..64 {
-            if (b >> i) & 1 != 0 {
-                let a_shifted = a << i;
-                if i < 64 {
-                    result_lo ^= a_shifted;
-                } else {
-                    result_hi ^= a_shifted >> 64;
-                    result_lo ^= a_shifted << (128 - i);
+    #[inline]
+    unsafe fn mul_add_row_adx(
+        src: *const u64,
+        dst: *mut u64,
+        a: *const u64,
+        b: u64,
+        len: usize,
+        carry: u64,
+    ) -> u64 {
+        let mut carry = carry;
+        // The first len % 4 words go one at a time, the rest four at a time
+        let head = len % 4;
+        let rest = len - head;
+        // SAFETY: the caller guarantees the features and the bounds of the pointers
+        unsafe {
+            std::arch::asm!(
+                // Clears both CF and OF
+                "xor {zero:e}, {zero:e}",
+                // Index each run from its end, counting RCX up to zero
+                "2:",
+                "jrcxz 3f",
+                "mulx {high}, {low}, qword ptr [{a} + 8 * rcx]",
+                "adcx {low}, qword ptr [{src} + 8 * rcx]",
+                "adox {low}, {carry}",
+                "mov qword ptr [{dst} + 8 * rcx], {low}",
+                "mov {carry}, {high}",
+                "lea rcx, [rcx + 1]",
+                "jmp 2b",
+                "3:",
+                "lea {a}, [{a} + 8 * {rest}]",
+                "lea {src}, [{src} + 8 * {rest}]",
+                "lea {dst}, [{dst} + 8 * {rest}]",
+                "mov rcx, {neg_rest}",
+                // Alternating which register holds the pending high word saves a move
+                "4:",
+                "jrcxz 5f",
+                "mulx {high}, {low}, qword ptr [{a} + 8 * rcx]",
+                "adcx {low}, qword ptr [{src} + 8 * rcx]",
+                "adox {low}, {carry}",
+                "mov qword ptr [{dst} + 8 * rcx], {low}",
+                "mulx {carry}, {low}, qword ptr [{a} + 8 * rcx + 8]",
+                "adcx {low}, qword ptr [{src} + 8 * rcx + 8]",
+                "adox {low}, {high}",
+                "mov qword ptr [{dst} + 8 * rcx + 8], {low}",
+                "mulx {high}, {low}, qword ptr [{a} + 8 * rcx + 16]",
+                "adcx {low}, qword ptr [{src} + 8 * rcx + 16]",
+                "adox {low}, {carry}",
+                "mov qword ptr [{dst} + 8 * rcx + 16], {low}",
+                "mulx {carry}, {low}, qword ptr [{a} + 8 * rcx + 24]",
+                "adcx {low}, qword ptr [{src} + 8 * rcx + 24]",
+                "adox {low}, {high}",
+                "mov qword ptr [{dst} + 8 * rcx + 24], {low}",
+                "lea rcx, [rcx + 4]",
+                "jmp 4b",
+                "5:",
+                "adcx {carry}, {zero}",
+                "adox {carry}, {zero}",
+                a = inout(reg) a.add(head) => _,
+                src = inout(reg) src.add(head) => _,
+                dst = inout(reg) dst.add(head) => _,
+                rest = in(reg) rest,
+                neg_rest = in(reg) rest.wrapping_neg(),
+                carry = inout(reg) carry,
+                high = out(reg) _,
+                low = out(reg) _,
+                zero = out(reg) _,
+                inout("rcx") head.wrapping_neg() => _,
+                in("rdx") b,
+                options(nostack),
+            );
+        }
+        carry
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_mul_add_carry_matches_portable() {
+            let mut x = 0x0123_4567_89AB_CDEFu64;
+            for _ in 0..200 {
+                x = x.wrapping_mul(0x5851_F42D_4C95_7F2D).wrapping_add(1);
+                let (b, c, carry) = (x.rotate_left(13), x.rotate_left(29), x.rotate_left(41));
+                let expected = crate::wide::mul_add_carry(x, b, c, carry);
+                assert_eq!(mul_add_carry(x, b, c, carry), expected);
+            }
+            assert_eq!(
+                mul_add_carry(u64::MAX, u64::MAX, u64::MAX, u64::MAX),
+                (u64::MAX, u64::MAX)
+            );
+        }
+
+        #[test]
+        #[cfg(target_arch = "x86_64")]
+        fn test_montgomery_limbs_kernel_matches_portable() {
+            if !(cpu_features().bmi2 && cpu_features().adx) {
+                return;
+            }
+            let mut x = 0x0123_4567_89AB_CDEFu64;
+            let mut next = || {
+                x = x.wrapping_mul(0x5851_F42D_4C95_7F2D).wrapping_add(1);
+                x
+            };
+            for len in [1, 2, 4, 7, 16] {
+                // All-ones is the widest odd modulus and pushes every carry chain
+                let widest = vec![u64::MAX; len];
+                for round in 0..50 {
+                    let mut modulus: Vec<u64> = (0..len).map(|_| next()).collect();
+                    modulus[0] |= 1;
+                    modulus[len - 1] |= 1 << 63;
+                    if round == 0 {
+                        modulus.clone_from(&widest);
+                    }
+                    // Top limbs below the modulus keep a, b < N
+                    let mut a: Vec<u64> = (0..len).map(|_| next()).collect();
+                    let mut b: Vec<u64> = (0..len).map(|_| next()).collect();
+                    a[len - 1] = modulus[len - 1] - 1;
+                    b[len - 1] %= modulus[len - 1];
+                    let n_prime = crate::MontgomeryContext::<u64>::compute_n_prime(modulus[0]);
+                    let mut expected = vec![0; len];
+                    crate::montgomery::montgomery_mul_limbs_portable(
+                        &a,
+                        &b,
+                        &modulus,
+                        n_prime,
+                        &mut expected,
+                    );
+                    let mut product = vec![0; len];
+                    // SAFETY: BMI2 and ADX were detected above
+                    unsafe { montgomery_mul_limbs_mulx(&a, &b, &modulus, n_prime, &mut product) };
+                    assert_eq!(product, expected);
+                }
+            }
+        }
+
+        #[test]
+        fn test_carryless_mul_matches_portable() {
+            let mut x = 0x0123_4567_89AB_CDEFu64;
+            for _ in 0..200 {
+                x = x.wrapping_mul(0x5851_F42D_4C95_7F2D).wrapping_add(1);
+                let y = x.rotate_left(23) | 1 << 63;
+                let expected = crate::wide::carryless_mul_portable(x, y);
+                assert_eq!(carryless_mul(x, y), expected);
+            }
+            // Products that spill past bit 63 exercise the high word
+            assert_eq!(
+                carryless_mul(u64::MAX, u64::MAX),
+                (0x5555_5555_5555_5555, 0x5555_5555_5555_5555)
+            );
+        }
+
+        #[test]
+        #[cfg(target_arch = "x86_64")]
+        fn test_montgomery_mulx_matches_portable() {
+            if !(cpu_features().bmi2 && cpu_features().adx) {
+                return;
+            }
+            for modulus in [3u64, 998_244_353, (1 << 52) + 21, u64::MAX - 58] {
+                let ctx = crate::MontgomeryContext::new(modulus);
+                let n_prime = ctx.n_prime();
+                let mut x = modulus / 7;
+                for _ in 0..200 {
+                    x = x.wrapping_mul(0x5851_F42D_4C95_7F2D).wrapping_add(1);
+                    let (a, b) = (x % modulus, x.rotate_left(29) % modulus);
+                    let expected = crate::MontgomeryContext::<u64>::montgomery_reduce(
+                        u128::from(a) * u128::from(b),
+                        modulus,
+                        n_prime,
+                    );
+                    // SAFETY: BMI2 and ADX were detected above
+                    let mulx = unsafe { montgomery_mul_mulx(a, b, modulus, n_prime) };
+                    assert_eq!(mulx, expected);
                 }
             }
         }
+    }
+}
 
-        (result_hi, result_lo)
+/// Vectorized Montgomery multiplication over slices.
+///
+/// Each entry point multiplies Montgomery-form residues modulo an odd n with the
+/// R = 2^64 convention of `MontgomeryContext<u64>`, so results match the scalar
+/// `montgomery_product` exactly. They return false without touching the data when
+/// the CPU lacks the instructions or the modulus is too wide for the lanes:
+/// AVX-512 IFMA handles n < 2^52 in eight 52-bit lanes, AVX2 handles n < 2^32 in
+/// four 32-bit lanes.
+#[cfg(target_arch = "x86_64")]
+pub mod simd {
+    use super::feature_detection::cpu_features;
+    use crate::MontgomeryContext;
+    use std::arch::x86_64::{
+        __m256i, __m512i, _mm256_add_epi64, _mm256_and_si256, _mm256_andnot_si256,
+        _mm256_cmpeq_epi64, _mm256_cmpgt_epi64, _mm256_loadu_si256, _mm256_mul_epu32,
+        _mm256_set1_epi64x, _mm256_setzero_si256, _mm256_srli_epi64, _mm256_storeu_si256,
+        _mm256_sub_epi64, _mm512_add_epi64, _mm512_cmpge_epu64_mask, _mm512_loadu_si512,
+        _mm512_madd52hi_epu64, _mm512_madd52lo_epu64, _mm512_mask_sub_epi64, _mm512_set1_epi64,
+        _mm512_setzero_si512, _mm512_srli_epi64, _mm512_storeu_si512,
+    };
+
+    const IFMA_MAX_MODULUS: u64 = 1 << 52;
+    const AVX2_MAX_MODULUS: u64 = 1 << 32;
+
+    /// Computes a[i] = a[i] * b[i] * R⁻¹ mod n for residues below n.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices have different lengths.
+    pub fn montgomery_mul_slices(a: &mut [u64], b: &[u64], modulus: u64, n_prime: u64) -> bool {
+        assert_eq!(a.len(), b.len(), "Length mismatch in montgomery_mul_slices");
+        if modulus < IFMA_MAX_MODULUS && cpu_features().avx512ifma {
+            // SAFETY: the required features were detected at runtime
+            unsafe { mul_slices_ifma(a, b, modulus, n_prime) };
+            true
+        } else if modulus < AVX2_MAX_MODULUS && cpu_features().avx2 {
+            // SAFETY: the required features were detected at runtime
+            unsafe { mul_slices_avx2(a, b, modulus, n_prime) };
+            true
+        } else {
+            false
+        }
     }
 
-    // ARM NEON implementations
-    #[cfg(target_arch = "aarch64")]
-    pub unsafe fn mul_add_carry(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
-        // On ARM, we can use the vmull_u64 instruction for 64x64->128 bit multiplication
-        // and then add c and carry to the result
-        // Note: The implementation would use NEON intrinsics in a real-world scenario
-        // This is a software fallback similar to the x86_64 fallback
-        let a_b = a as u128 * b as u128;
-        let a_b_c = a_b + c as u128;
-        let result = a_b_c + carry as u128;
-        ((result >> 64) as u64, result as u64)
-    }
-
-    /// Accelerated modular reduction for ARM
-    #[cfg(target_arch = "aarch64")]
-    pub unsafe fn modular_reduction_arm(value: u128, modulus: u64) -> u64 {
-        // For ARM, we can use efficient NEON operations
-        // This is a placeholder for ARM-specific optimizations
-        (value % modulus as u128) as u64
+    /// Computes a[i] = a[i] * scalar * R⁻¹ mod n for residues below n.
+    pub fn montgomery_scale_slice(a: &mut [u64], scalar: u64, modulus: u64, n_prime: u64) -> bool {
+        if modulus < IFMA_MAX_MODULUS && cpu_features().avx512ifma {
+            // SAFETY: the required features were detected at runtime
+            unsafe { scale_slice_ifma(a, scalar, modulus, n_prime) };
+            true
+        } else if modulus < AVX2_MAX_MODULUS && cpu_features().avx2 {
+            // SAFETY: the required features were detected at runtime
+            unsafe { scale_slice_avx2(a, scalar, modulus, n_prime) };
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the widest vector kernel for Montgomery products over slices that the
+    /// CPU supports, with the exclusive bound on the moduli it handles.
+    pub(super) fn mul_slices_kernel() -> Option<(u64, super::MontgomerySlicesFn)> {
+        if cpu_features().avx512ifma {
+            // SAFETY: the kernel is only returned once AVX-512 IFMA is detected
+            Some((IFMA_MAX_MODULUS, |a, b, n, n_prime| unsafe {
+                mul_slices_ifma(a, b, n, n_prime);
+            }))
+        } else if cpu_features().avx2 {
+            // SAFETY: the kernel is only returned once AVX2 is detected
+            Some((AVX2_MAX_MODULUS, |a, b, n, n_prime| unsafe {
+                mul_slices_avx2(a, b, n, n_prime);
+            }))
+        } else {
+            None
+        }
     }
+
+    fn scalar_product(a: u64, b: u64, modulus: u64, n_prime: u64) -> u64 {
+        MontgomeryContext::<u64>::montgomery_reduce(u128::from(a) * u128::from(b), modulus, n_prime)
+    }
+
+    /// Constants for the AVX2 lanes: n, -n⁻¹ mod 2^32 and the low-half mask.
+    struct Avx2Constants {
+        modulus: __m256i,
+        n_prime: __m256i,
+        low_mask: __m256i,
+        one: __m256i,
+    }
+
+    #[target_feature(enable = "avx2")]
+    fn avx2_constants(modulus: u64, n_prime: u64) -> Avx2Constants {
+        Avx2Constants {
+            modulus: _mm256_set1_epi64x(modulus.cast_signed()),
+            // -n⁻¹ mod 2^64 reduces to -n⁻¹ mod 2^32
+            #[allow(clippy::cast_possible_truncation)] // the AVX2 lanes reduce modulo 2^32
+            n_prime: _mm256_set1_epi64x(i64::from(n_prime as u32)),
+            low_mask: _mm256_set1_epi64x(i64::from(u32::MAX)),
+            one: _mm256_set1_epi64x(1),
+        }
+    }
+
+    /// REDC with R = 2^32 of a 64-bit t < n·2^32, giving t·2^-32 mod n in each lane.
+    #[target_feature(enable = "avx2")]
+    fn redc32(t: __m256i, c: &Avx2Constants) -> __m256i {
+        let m = _mm256_mul_epu32(t, c.n_prime);
+        let mn = _mm256_mul_epu32(m, c.modulus);
+        // t + m·n can reach 2^65, so add the high halves and recover the carry out of
+        // the low halves, which sum to 0 or 2^32
+        let low_zero = _mm256_cmpeq_epi64(_mm256_and_si256(t, c.low_mask), _mm256_setzero_si256());
+        let carry = _mm256_add_epi64(c.one, low_zero);
+        let u = _mm256_add_epi64(
+            _mm256_add_epi64(_mm256_srli_epi64::<32>(t), _mm256_srli_epi64::<32>(mn)),
+            carry,
+        );
+        // u < 2n < 2^33, so the signed comparison is exact
+        let below = _mm256_cmpgt_epi64(c.modulus, u);
+        _mm256_sub_epi64(u, _mm256_andnot_si256(below, c.modulus))
+    }
+
+    /// a·b·2^-64 mod n as two 32-bit reductions of the 64-bit lane products.
+    #[target_feature(enable = "avx2")]
+    fn product_avx2(a: __m256i, b: __m256i, c: &Avx2Constants) -> __m256i {
+        redc32(redc32(_mm256_mul_epu32(a, b), c), c)
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn mul_slices_avx2(a: &mut [u64], b: &[u64], modulus: u64, n_prime: u64) {
+        let c = avx2_constants(modulus, n_prime);
+        let mut a_chunks = a.chunks_exact_mut(4);
+        let mut b_chunks = b.chunks_exact(4);
+        for (x, y) in a_chunks.by_ref().zip(b_chunks.by_ref()) {
+            let xv = _mm256_loadu_si256(x.as_ptr().cast());
+            let yv = _mm256_loadu_si256(y.as_ptr().cast());
+            _mm256_storeu_si256(x.as_mut_ptr().cast(), product_avx2(xv, yv, &c));
+        }
+        for (x, y) in a_chunks
+            .into_remainder()
+            .iter_mut()
+            .zip(b_chunks.remainder())
+        {
+            *x = scalar_product(*x, *y, modulus, n_prime);
+        }
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn scale_slice_avx2(a: &mut [u64], scalar: u64, modulus: u64, n_prime: u64) {
+        let c = avx2_constants(modulus, n_prime);
+        let sv = _mm256_set1_epi64x(scalar.cast_signed());
+        let mut chunks = a.chunks_exact_mut(4);
+        for x in chunks.by_ref() {
+            let xv = _mm256_loadu_si256(x.as_ptr().cast());
+            _mm256_storeu_si256(x.as_mut_ptr().cast(), product_avx2(xv, sv, &c));
+        }
+        for x in chunks.into_remainder() {
+            *x = scalar_product(*x, scalar, modulus, n_prime);
+        }
+    }
+
+    /// Constants for the IFMA lanes: n, -n⁻¹ mod 2^52 and 2^40 mod n, which turns a
+    /// product reduced with R = 2^52 into one reduced with R = 2^64.
+    struct IfmaConstants {
+        modulus: __m512i,
+        n_prime: __m512i,
+        rescale: __m512i,
+    }
+
+    #[target_feature(enable = "avx512f,avx512ifma")]
+    #[allow(clippy::cast_possible_truncation)] // 2^40 mod n is below n
+    fn ifma_constants(modulus: u64, n_prime: u64) -> IfmaConstants {
+        IfmaConstants {
+            modulus: _mm512_set1_epi64(modulus.cast_signed()),
+            n_prime: _mm512_set1_epi64((n_prime & (IFMA_MAX_MODULUS - 1)).cast_signed()),
+            rescale: _mm512_set1_epi64(
+                (((1u128 << 40) % u128::from(modulus)) as u64).cast_signed(),
+            ),
+        }
+    }
+
+    /// Montgomery product with R = 2^52 of lanes below n < 2^52.
+    #[target_feature(enable = "avx512f,avx512ifma")]
+    #[allow(clippy::many_single_char_names)] // a, b, c, the products and their halves as in REDC
+    fn redc52_product(a: __m512i, b: __m512i, c: &IfmaConstants) -> __m512i {
+        let zero = _mm512_setzero_si512();
+        let lo = _mm512_madd52lo_epu64(zero, a, b);
+        let hi = _mm512_madd52hi_epu64(zero, a, b);
+        let m = _mm512_madd52lo_epu64(zero, lo, c.n_prime);
+        // lo + low52(m·n) is 0 or 2^52; its bit 52 carries into the high half
+        let lo = _mm512_madd52lo_epu64(lo, m, c.modulus);
+        let hi = _mm512_madd52hi_epu64(hi, m, c.modulus);
+        let u = _mm512_add_epi64(hi, _mm512_srli_epi64::<52>(lo));
+        let ge = _mm512_cmpge_epu64_mask(u, c.modulus);
+        _mm512_mask_sub_epi64(u, ge, u, c.modulus)
+    }
+
+    #[target_feature(enable = "avx512f,avx512ifma")]
+    fn product_ifma(a: __m512i, b: __m512i, c: &IfmaConstants) -> __m512i {
+        // a·b·2^-52·2^40·2^-52 = a·b·2^-64
+        redc52_product(redc52_product(a, b, c), c.rescale, c)
+    }
+
+    #[target_feature(enable = "avx512f,avx512ifma")]
+    unsafe fn mul_slices_ifma(a: &mut [u64], b: &[u64], modulus: u64, n_prime: u64) {
+        let c = ifma_constants(modulus, n_prime);
+        let mut a_chunks = a.chunks_exact_mut(8);
+        let mut b_chunks = b.chunks_exact(8);
+        for (x, y) in a_chunks.by_ref().zip(b_chunks.by_ref()) {
+            let xv = _mm512_loadu_si512(x.as_ptr().cast());
+            let yv = _mm512_loadu_si512(y.as_ptr().cast());
+            _mm512_storeu_si512(x.as_mut_ptr().cast(), product_ifma(xv, yv, &c));
+        }
+        for (x, y) in a_chunks
+            .into_remainder()
+            .iter_mut()
+            .zip(b_chunks.remainder())
+        {
+            *x = scalar_product(*x, *y, modulus, n_prime);
+        }
+    }
+
+    #[target_feature(enable = "avx512f,avx512ifma")]
+    unsafe fn scale_slice_ifma(a: &mut [u64], scalar: u64, modulus: u64, n_prime: u64) {
+        let c = ifma_constants(modulus, n_prime);
+        let sv = _mm512_set1_epi64(scalar.cast_signed());
+        let mut chunks = a.chunks_exact_mut(8);
+        for x in chunks.by_ref() {
+            let xv = _mm512_loadu_si512(x.as_ptr().cast());
+            _mm512_storeu_si512(x.as_mut_ptr().cast(), product_ifma(xv, sv, &c));
+        }
+        for x in chunks.into_remainder() {
+            *x = scalar_product(*x, scalar, modulus, n_prime);
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        // Runs every kernel the CPU supports, not just the preferred one
+        #[test]
+        fn test_kernels_match_scalar() {
+            for modulus in [3, 998_244_353, (1 << 32) - 5, (1 << 52) - 47] {
+                let n_prime = MontgomeryContext::new(modulus).n_prime();
+                let a: Vec<u64> = (0..21u64)
+                    .map(|i| (i * 0x9E37_79B9_7F4A) % modulus)
+                    .collect();
+                let b: Vec<u64> = (0..21u64).map(|i| (i * i + 7) % modulus).collect();
+                let product: Vec<u64> = a
+                    .iter()
+                    .zip(&b)
+                    .map(|(&x, &y)| scalar_product(x, y, modulus, n_prime))
+                    .collect();
+                let scaled: Vec<u64> = a
+                    .iter()
+                    .map(|&x| scalar_product(x, b[1], modulus, n_prime))
+                    .collect();
+
+                if modulus < AVX2_MAX_MODULUS && cpu_features().avx2 {
+                    let (mut x, mut y) = (a.clone(), a.clone());
+                    // SAFETY: AVX2 was detected above
+                    unsafe {
+                        mul_slices_avx2(&mut x, &b, modulus, n_prime);
+                        scale_slice_avx2(&mut y, b[1], modulus, n_prime);
+                    }
+                    assert_eq!((x, y), (product.clone(), scaled.clone()));
+                }
+                if cpu_features().avx512ifma {
+                    let (mut x, mut y) = (a.clone(), a.clone());
+                    // SAFETY: AVX-512 IFMA was detected above
+                    unsafe {
+                        mul_slices_ifma(&mut x, &b, modulus, n_prime);
+                        scale_slice_ifma(&mut y, b[1], modulus, n_prime);
+                    }
+                    assert_eq!((x, y), (product, scaled));
+                }
+            }
+        }
+    }
+}
+
+/// The scalar Montgomery kernels a `ModularArithmeticAccelerated` context
+/// dispatches to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// Portable Rust: the generic single- and multi-limb Montgomery reductions.
+    Portable,
+    /// x86_64 with BMI2 and ADX: MULX/ADX Montgomery reduction and CIOS products.
+    X86Mulx,
 }
 
-/// Provides modular arithmetic operations using hardware acceleration when available
+type MontgomeryMulFn = fn(u64, u64, u64, u64) -> u64;
+type MontgomerySlicesFn = fn(&mut [u64], &[u64], u64, u64);
+type MontgomeryLimbsFn = fn(&[u64], &[u64], &[u64], u64, &mut [u64]);
+
+/// Provides modular arithmetic operations using hardware acceleration when available.
+///
+/// The kernels are chosen once, when the context is created, and called through
+/// function pointers, so no operation repeats the feature checks.
+#[derive(Debug, Clone, Copy)]
 pub struct ModularArithmeticAccelerated {
     pub use_acceleration: bool,
+    backend: Backend,
+    montgomery_mul: MontgomeryMulFn,
+    montgomery_mul_slices: MontgomerySlicesFn,
+    montgomery_mul_limbs: MontgomeryLimbsFn,
+    // A vector kernel for slices and the bound below which the modulus fits its lanes
+    vector_mul_slices: Option<(u64, MontgomerySlicesFn)>,
+}
+
+impl Default for ModularArithmeticAccelerated {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ModularArithmeticAccelerated {
     /// Creates a new accelerated arithmetic helper, automatically detecting
     /// hardware support.
+    #[must_use]
     pub fn new() -> Self {
+        #[cfg(target_arch = "x86_64")]
+        {
+            let backend = if feature_detection::is_hardware_acceleration_available() {
+                Backend::X86Mulx
+            } else {
+                Backend::Portable
+            };
+            // The vector kernels only need AVX2 or IFMA, not the scalar backend's
+            // BMI2 and ADX
+            Self::with_kernels(backend, simd::mul_slices_kernel())
+        }
+        #[cfg(not(target_arch = "x86_64"))]
+        {
+            Self::portable()
+        }
+    }
+
+    /// Creates a helper that always uses the portable kernels, as a baseline.
+    #[must_use]
+    pub fn portable() -> Self {
+        Self::with_kernels(Backend::Portable, None)
+    }
+
+    fn with_kernels(
+        backend: Backend,
+        vector_mul_slices: Option<(u64, MontgomerySlicesFn)>,
+    ) -> Self {
+        let (montgomery_mul, montgomery_mul_slices, montgomery_mul_limbs): (
+            MontgomeryMulFn,
+            MontgomerySlicesFn,
+            MontgomeryLimbsFn,
+        ) = match backend {
+            #[cfg(target_arch = "x86_64")]
+            Backend::X86Mulx => (
+                // SAFETY: this backend is only selected once BMI2 and ADX are detected
+                |a, b, n, n_prime| unsafe { arithmetic::montgomery_mul_mulx(a, b, n, n_prime) },
+                |a, b, n, n_prime| unsafe {
+                    arithmetic::montgomery_mul_slices_mulx(a, b, n, n_prime);
+                },
+                |a, b, n, n_prime, out| unsafe {
+                    arithmetic::montgomery_mul_limbs_mulx(a, b, n, n_prime, out);
+                },
+            ),
+            _ => (
+                portable_montgomery_mul,
+                |a, b, n, n_prime| {
+                    for (x, &y) in a.iter_mut().zip(b) {
+                        *x = portable_montgomery_mul(*x, y, n, n_prime);
+                    }
+                },
+                crate::montgomery::montgomery_mul_limbs_portable,
+            ),
+        };
         Self {
-            use_acceleration: feature_detection::is_hardware_acceleration_available(),
+            use_acceleration: backend != Backend::Portable || vector_mul_slices.is_some(),
+            backend,
+            montgomery_mul,
+            montgomery_mul_slices,
+            montgomery_mul_limbs,
+            vector_mul_slices,
         }
     }
 
+    /// Returns the scalar kernels this context dispatches to.
+    #[must_use]
+    pub const fn backend(&self) -> Backend {
+        self.backend
+    }
+
     /// Multiply two u64 values with modular reduction.
+    ///
+    /// Every backend uses the `u128` remainder here: it already compiles to the
+    /// hardware divide, and a hand-written DIV measured no faster.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the modulus is zero.
+    #[must_use]
     pub fn mul_mod(&self, a: u64, b: u64, modulus: u64) -> u64 {
-        if self.use_acceleration {
-            unsafe {
-                // Use hardware acceleration when available
-                #[cfg(target_arch = "x86_64")]
-                {
-                    let (hi, lo) = arithmetic::mul_add_carry(a, b, 0, 0);
-                    self.reduce_mod(hi, lo, modulus)
-                }
-                #[cfg(target_arch = "aarch64")]
-                {
-                    let result = a as u128 * b as u128;
-                    arithmetic::modular_reduction_arm(result, modulus)
-                }
-                #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
-                {
-                    // Fallback for other architectures
-                    ((a as u128 * b as u128) % modulus as u128) as u64
-                }
-            }
-        } else {
-            // Standard software implementation
-            ((a as u128 * b as u128) % modulus as u128) as u64
-        }
+        portable_mul_mod(a, b, modulus)
     }
 
-    /// Reduce a 128-bit value modulo a 64-bit modulus.
-    #[cfg(target_arch = "x86_64")]
-    fn reduce_mod(&self, hi: u64, lo: u64, modulus: u64) -> u64 {
-        // Combine hi and lo into a 128-bit value
-        let value = (hi as u128) << 64 | lo as u128;
-        // Reduce modulo the modulus
-        (value % modulus as u128) as u64
+    /// Computes a * b mod n for plain residues with the Montgomery kernel.
+    ///
+    /// The context's precomputed R² lifts b to bR, and a second product drops the R
+    /// again, so unlike `mul_mod` no division is needed. Operands may be any `u64`.
+    #[must_use]
+    pub fn mul_mod_montgomery(&self, a: u64, b: u64, ctx: &MontgomeryContext<u64>) -> u64 {
+        let (n, n_prime) = (ctx.modulus(), ctx.n_prime());
+        // b·R²·R⁻¹ = bR < n, and a·bR < nR keeps the second product in range
+        let b_r = (self.montgomery_mul)(b, ctx.r_squared(), n, n_prime);
+        (self.montgomery_mul)(a, b_r, n, n_prime)
+    }
+
+    /// Computes the Montgomery product a * b * R⁻¹ mod n of two Montgomery-form
+    /// residues.
+    #[must_use]
+    pub fn montgomery_mul(&self, a: u64, b: u64, ctx: &MontgomeryContext<u64>) -> u64 {
+        (self.montgomery_mul)(a, b, ctx.modulus(), ctx.n_prime())
+    }
+
+    /// Computes the Montgomery products a[i] = a[i] * b[i] * R⁻¹ mod n for residues
+    /// below n.
+    ///
+    /// Moduli below 2^52 (AVX-512 IFMA) or 2^32 (AVX2) use the `simd` kernels when
+    /// the CPU has them; wider moduli use the scalar backend.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices have different lengths. Debug builds also panic on an
+    /// input that is not below n.
+    pub fn montgomery_mul_slices(&self, a: &mut [u64], b: &[u64], ctx: &MontgomeryContext<u64>) {
+        assert_eq!(a.len(), b.len(), "Length mismatch in montgomery_mul_slices");
+        debug_assert!(
+            a.iter().chain(b).all(|&x| x < ctx.modulus()),
+            "montgomery_mul_slices needs residues below the modulus"
+        );
+        let kernel = match self.vector_mul_slices {
+            Some((max_modulus, kernel)) if ctx.modulus() < max_modulus => kernel,
+            _ => self.montgomery_mul_slices,
+        };
+        kernel(a, b, ctx.modulus(), ctx.n_prime());
     }
+
+    /// Computes the Montgomery product a * b * R⁻¹ mod N of two multi-limb
+    /// Montgomery-form residues below N.
+    #[must_use]
+    pub fn montgomery_mul_uint<const LIMBS: usize>(
+        &self,
+        a: &Uint<LIMBS>,
+        b: &Uint<LIMBS>,
+        ctx: &MontgomeryContext<Uint<LIMBS>>,
+    ) -> Uint<LIMBS> {
+        let mut product = [0; LIMBS];
+        (self.montgomery_mul_limbs)(
+            a.as_words(),
+            b.as_words(),
+            ctx.modulus().as_words(),
+            ctx.limb_n_prime(),
+            &mut product,
+        );
+        Uint::from_words(product)
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)] // the remainder is below the modulus
+fn portable_mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    ((u128::from(a) * u128::from(b)) % u128::from(modulus)) as u64
+}
+
+fn portable_montgomery_mul(a: u64, b: u64, modulus: u64, n_prime: u64) -> u64 {
+    MontgomeryContext::<u64>::montgomery_reduce(u128::from(a) * u128::from(b), modulus, n_prime)
 }
 
 #[cfg(test)]
@@ -211,12 +961,112 @@ mod tests {
         assert_eq!(result, 1); // 5 * 7 mod 17 = 35 mod 17 = 1
 
         // Test with large numbers
-        let large_a = 0xABCDEF0123456789u64;
-        let large_b = 0x123456789ABCDEFu64;
-        let large_prime = 0xFFFFFFFFFFFFFFFBu64; // 2^64 - 5
+        let large_a = 0xABCD_EF01_2345_6789_u64;
+        let large_b = 0x0123_4567_89AB_CDEF_u64;
+        let large_prime = 0xFFFF_FFFF_FFFF_FFFB_u64; // 2^64 - 5
 
         let result_large = accel.mul_mod(large_a, large_b, large_prime);
-        let expected = ((large_a as u128 * large_b as u128) % large_prime as u128) as u64;
+        #[allow(clippy::cast_possible_truncation)] // the remainder is below the prime
+        let expected =
+            ((u128::from(large_a) * u128::from(large_b)) % u128::from(large_prime)) as u64;
         assert_eq!(result_large, expected);
     }
+
+    #[test]
+    fn test_montgomery_mul_slices_vector_path() {
+        // The benchmark's NTT prime fits both the AVX2 and the IFMA lanes
+        let accel = ModularArithmeticAccelerated::new();
+        let ctx = MontgomeryContext::new(crate::ntt::P998244353);
+        let features = feature_detection::cpu_features();
+        if cfg!(target_arch = "x86_64") && (features.avx2 || features.avx512ifma) {
+            assert!(accel.vector_mul_slices.is_some());
+        }
+
+        // An odd length leaves a tail after the full vectors
+        let n = ctx.modulus();
+        let a: Vec<u64> = (0..4099u64)
+            .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15) % n)
+            .collect();
+        let b: Vec<u64> = a.iter().rev().map(|x| (x ^ 0x5555_5555) % n).collect();
+        let mut product = a.clone();
+        accel.montgomery_mul_slices(&mut product, &b, &ctx);
+        for ((&x, &y), &z) in a.iter().zip(&b).zip(&product) {
+            assert_eq!(z, portable_montgomery_mul(x, y, n, ctx.n_prime()));
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "montgomery_mul_slices needs residues below the modulus")]
+    fn test_montgomery_mul_slices_rejects_unreduced_input() {
+        let ctx = MontgomeryContext::new(crate::ntt::P998244353);
+        let mut a = vec![ctx.modulus(); 8];
+        ModularArithmeticAccelerated::new().montgomery_mul_slices(&mut a, &[1; 8], &ctx);
+    }
+
+    #[test]
+    fn test_backends_agree() {
+        let portable = ModularArithmeticAccelerated::portable();
+        let accel = ModularArithmeticAccelerated::new();
+        assert_eq!(portable.backend(), Backend::Portable);
+        assert_eq!(
+            accel.use_acceleration,
+            accel.backend() != Backend::Portable || accel.vector_mul_slices.is_some()
+        );
+
+        // Moduli on both sides of the AVX2 and IFMA lane widths
+        for modulus in [
+            3u64,
+            998_244_353,
+            (1 << 32) + 15,
+            (1 << 52) - 47,
+            (1 << 52) + 21,
+            u64::MAX - 58,
+        ] {
+            let ctx = MontgomeryContext::new(modulus);
+            let a: Vec<u64> = (1..=40u64)
+                .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15) % modulus)
+                .collect();
+            let b: Vec<u64> = a.iter().map(|x| x.rotate_left(11) % modulus).collect();
+            for (&x, &y) in a.iter().zip(&b) {
+                assert_eq!(
+                    accel.mul_mod(x, y, modulus),
+                    portable.mul_mod(x, y, modulus)
+                );
+                assert_eq!(
+                    accel.montgomery_mul(x, y, &ctx),
+                    portable.montgomery_mul(x, y, &ctx)
+                );
+                assert_eq!(
+                    accel.mul_mod_montgomery(x, y, &ctx),
+                    portable.mul_mod(x, y, modulus)
+                );
+            }
+            // Unreduced operands still give the right remainder
+            let expected = portable.mul_mod(u64::MAX, u64::MAX, modulus);
+            assert_eq!(accel.mul_mod(u64::MAX, u64::MAX, modulus), expected);
+            assert_eq!(accel.mul_mod_montgomery(u64::MAX, u64::MAX, &ctx), expected);
+            assert_eq!(
+                portable.mul_mod_montgomery(u64::MAX, u64::MAX, &ctx),
+                expected
+            );
+
+            let (mut fast, mut slow) = (a.clone(), a.clone());
+            accel.montgomery_mul_slices(&mut fast, &b, &ctx);
+            portable.montgomery_mul_slices(&mut slow, &b, &ctx);
+            assert_eq!(fast, slow);
+        }
+
+        // Multi-limb products go through the CIOS kernels
+        let ctx = MontgomeryContext::new(crate::U256::from_be_hex(
+            "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+        ));
+        let mut x = ctx.reduce(&crate::U256::MAX);
+        let y = ctx.reduce(&crate::U256::from_words([3, 1 << 40, 5, 1 << 62]));
+        for _ in 0..40 {
+            let product = accel.montgomery_mul_uint(&x, &y, &ctx);
+            assert_eq!(product, portable.montgomery_mul_uint(&x, &y, &ctx));
+            x = product;
+        }
+    }
 }
@@ -1,20 +1,64 @@
 // Modularity: A Rust library for performant modular arithmetic
 
-use num_traits::{One, Zero};
+// The lint groups in .cargo/config.toml are passed on the command line and take
+// precedence over the allow-list in Cargo.toml, so restate the numeric-code exceptions.
+#![allow(clippy::doc_markdown, clippy::similar_names)]
+
+use num_integer::Integer;
 use std::fmt::Debug;
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 mod barrett;
+mod batch;
+#[cfg(feature = "arbitrary-precision")]
+mod bignum;
+mod const_mod;
+mod crt;
+pub mod ct;
+pub mod dlog;
+mod error;
+pub mod factor;
+pub mod field;
+pub mod gf2n;
+pub mod group;
 #[cfg(feature = "hardware-acceleration")]
-mod intrinsics;
+pub mod intrinsics;
 mod montgomery;
+mod montgomery_int;
+pub mod ntt;
+mod poly;
+mod pow;
+pub mod prime;
+pub mod ring;
+pub mod rns;
+pub mod sqrt;
+mod storage;
+pub mod tower;
+mod uint;
+mod wide;
 
 pub use barrett::BarrettContext;
 pub use barrett::BarrettReduction;
-#[cfg(feature = "hardware-acceleration")]
-pub use intrinsics;
+pub use barrett::BarrettWord;
+pub use batch::{batch_inverse, checked_batch_inverse};
+pub use const_mod::ConstModInt;
+pub use crt::crt;
+#[cfg(feature = "arbitrary-precision")]
+pub use crt::crt_biguint;
+pub use crt::CrtBasis;
+pub use error::ModularError;
 pub use montgomery::MontgomeryArithmetic;
 pub use montgomery::MontgomeryContext;
+pub use montgomery::MontgomeryWord;
+pub use montgomery_int::MontgomeryInt;
+pub use poly::Poly;
+pub use pow::pow_with;
+pub use pow::Exponent;
+pub use pow::PowDomain;
+pub use pow::PowStrategy;
+pub use storage::ModularStorage;
+pub use uint::Uint;
+pub use uint::{U1024, U256, U384, U512};
 
 /// Represents an integer modulo a given modulus.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -23,92 +67,47 @@ pub struct ModularInt<T> {
     modulus: T,
 }
 
-/// Common methods shared by all ModularInt types
-impl<T> ModularInt<T>
-where
-    T: Copy
-        + PartialEq
-        + PartialOrd
-        + Eq
-        + Zero
-        + Add<Output = T>
-        + Sub<Output = T>
-        + Mul<Output = T>
-        + Debug,
-{
+// Accessors shared by every primitive storage type
+impl<T: ModularStorage> ModularInt<T> {
     /// Returns the value of the modular integer.
-    pub fn value(&self) -> T {
+    #[must_use]
+    pub const fn value(&self) -> T {
         self.value
     }
 
     /// Returns the modulus of the modular integer.
-    pub fn modulus(&self) -> T {
+    #[must_use]
+    pub const fn modulus(&self) -> T {
         self.modulus
     }
 }
 
 // Implement Add trait for ModularInt
-impl<T> Add for ModularInt<T>
-where
-    T: Copy
-        + PartialEq
-        + PartialOrd
-        + Eq
-        + Zero
-        + Add<Output = T>
-        + Sub<Output = T>
-        + Mul<Output = T>
-        + Debug,
-{
+impl<T: ModularStorage> Add for ModularInt<T> {
     type Output = Self;
 
     fn add(self, other: Self) -> Self::Output {
         assert_eq!(self.modulus, other.modulus, "Modulus mismatch in addition");
-        let mut result = self.clone();
-        result.value = self.value + other.value;
-        // Note: reduction is type-specific and needs to be handled by specialized implementations
-        // The generic implementation without reduction is incomplete
-        todo!("Implement addition for specific numeric types");
+        Self {
+            value: T::add_mod(self.value, other.value, self.modulus),
+            modulus: self.modulus,
+        }
     }
 }
 
 // Implement AddAssign trait for ModularInt
-impl<T> AddAssign for ModularInt<T>
-where
-    T: Copy
-        + PartialEq
-        + PartialOrd
-        + Eq
-        + Zero
-        + Add<Output = T>
-        + Sub<Output = T>
-        + Mul<Output = T>
-        + Debug,
-{
+impl<T: ModularStorage> AddAssign for ModularInt<T> {
     fn add_assign(&mut self, other: Self) {
         assert_eq!(
             self.modulus, other.modulus,
             "Modulus mismatch in add_assign"
         );
-        self.value = self.value + other.value;
-        // Note: reduction is type-specific and needs to be handled by specialized implementations
-        todo!("Implement add_assign for specific numeric types");
+        self.value = T::add_mod(self.value, other.value, self.modulus);
     }
 }
 
 // Implement Sub trait for ModularInt
-impl<T> Sub for ModularInt<T>
-where
-    T: Copy
-        + PartialEq
-        + PartialOrd
-        + Eq
-        + Zero
-        + Add<Output = T>
-        + Sub<Output = T>
-        + Mul<Output = T>
-        + Debug,
-{
+impl<T: ModularStorage> Sub for ModularInt<T> {
     type Output = Self;
 
     fn sub(self, other: Self) -> Self::Output {
@@ -116,49 +115,26 @@ where
             self.modulus, other.modulus,
             "Modulus mismatch in subtraction"
         );
-        // Handle subtraction based on the type
-        // This is a simplified implementation
-        todo!("Implement subtraction for specific numeric types");
+        Self {
+            value: T::sub_mod(self.value, other.value, self.modulus),
+            modulus: self.modulus,
+        }
     }
 }
 
 // Implement SubAssign trait for ModularInt
-impl<T> SubAssign for ModularInt<T>
-where
-    T: Copy
-        + PartialEq
-        + PartialOrd
-        + Eq
-        + Zero
-        + Add<Output = T>
-        + Sub<Output = T>
-        + Mul<Output = T>
-        + Debug,
-{
+impl<T: ModularStorage> SubAssign for ModularInt<T> {
     fn sub_assign(&mut self, other: Self) {
         assert_eq!(
             self.modulus, other.modulus,
             "Modulus mismatch in sub_assign"
         );
-        // Handle subtraction based on the type
-        // This is a simplified implementation
-        todo!("Implement subtraction_assign for specific numeric types");
+        self.value = T::sub_mod(self.value, other.value, self.modulus);
     }
 }
 
 // Implement Mul trait for ModularInt
-impl<T> Mul for ModularInt<T>
-where
-    T: Copy
-        + PartialEq
-        + PartialOrd
-        + Eq
-        + Zero
-        + Add<Output = T>
-        + Sub<Output = T>
-        + Mul<Output = T>
-        + Debug,
-{
+impl<T: ModularStorage> Mul for ModularInt<T> {
     type Output = Self;
 
     fn mul(self, other: Self) -> Self::Output {
@@ -166,60 +142,38 @@ where
             self.modulus, other.modulus,
             "Modulus mismatch in multiplication"
         );
-        let mut result = self.clone();
-        result.value = self.value * other.value;
-        // Note: reduction is type-specific and needs to be handled by specialized implementations
-        todo!("Implement multiplication for specific numeric types");
+        Self {
+            value: T::mul_mod(self.value, other.value, self.modulus),
+            modulus: self.modulus,
+        }
     }
 }
 
 // Implement MulAssign trait for ModularInt
-impl<T> MulAssign for ModularInt<T>
-where
-    T: Copy
-        + PartialEq
-        + PartialOrd
-        + Eq
-        + Zero
-        + Add<Output = T>
-        + Sub<Output = T>
-        + Mul<Output = T>
-        + Debug,
-{
+impl<T: ModularStorage> MulAssign for ModularInt<T> {
     fn mul_assign(&mut self, other: Self) {
         assert_eq!(
             self.modulus, other.modulus,
             "Modulus mismatch in mul_assign"
         );
-        self.value = self.value * other.value;
-        // Note: reduction is type-specific and needs to be handled by specialized implementations
-        todo!("Implement mul_assign for specific numeric types");
+        self.value = T::mul_mod(self.value, other.value, self.modulus);
     }
 }
 
 // Implement Neg trait for ModularInt
-impl<T> Neg for ModularInt<T>
-where
-    T: Copy
-        + PartialEq
-        + PartialOrd
-        + Eq
-        + Zero
-        + Add<Output = T>
-        + Sub<Output = T>
-        + Mul<Output = T>
-        + Debug,
-{
+impl<T: ModularStorage> Neg for ModularInt<T> {
     type Output = Self;
 
     fn neg(self) -> Self::Output {
-        // Implementation depends on the type
-        todo!("Implement negation for specific numeric types");
+        Self {
+            value: T::neg_mod(self.value, self.modulus),
+            modulus: self.modulus,
+        }
     }
 }
 
-// Implementation for u64
-impl ModularInt<u64> {
+// Construction shared by every storage type, including BigUint
+impl<T: Integer + Clone> ModularInt<T> {
     /// Creates a new ModularInt with the given value and modulus.
     ///
     /// # Arguments
@@ -230,72 +184,92 @@ impl ModularInt<u64> {
     /// # Panics
     ///
     /// Panics if the modulus is zero.
-    pub fn new(value: u64, modulus: u64) -> Self {
-        assert!(modulus > 0, "Modulus cannot be zero");
-        let mut result = Self { value, modulus };
-        result.reduce();
-        result
+    #[must_use]
+    pub fn new(value: T, modulus: T) -> Self {
+        match Self::try_new(value, modulus) {
+            Ok(result) => result,
+            Err(err) => panic!("{err}"),
+        }
     }
 
-    /// Reduces the value to be within the range [0, modulus).
-    fn reduce(&mut self) {
-        if self.value >= self.modulus {
-            self.value %= self.modulus;
+    /// Creates a new ModularInt, returning an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::ZeroModulus` if the modulus is zero.
+    pub fn try_new(value: T, modulus: T) -> Result<Self, ModularError> {
+        if modulus.is_zero() {
+            return Err(ModularError::ZeroModulus);
         }
+        let value = if value >= modulus {
+            value % modulus.clone()
+        } else {
+            value
+        };
+        Ok(Self { value, modulus })
     }
+}
 
+// Arithmetic shared by every primitive storage type
+impl<T: ModularStorage> ModularInt<T> {
     /// Performs modular addition.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the moduli differ.
+    #[must_use]
     pub fn add_mod(&self, other: &Self) -> Self {
-        assert_eq!(self.modulus, other.modulus, "Modulus mismatch in add_mod");
-        let sum = self.value.wrapping_add(other.value);
-        if sum >= self.modulus || sum < self.value {
-            // Check for overflow
-            Self::new(sum % self.modulus, self.modulus)
-        } else {
-            Self::new(sum, self.modulus)
-        }
+        *self + *other
     }
 
     /// Performs modular subtraction.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the moduli differ.
+    #[must_use]
     pub fn sub_mod(&self, other: &Self) -> Self {
-        assert_eq!(self.modulus, other.modulus, "Modulus mismatch in sub_mod");
-        if self.value >= other.value {
-            Self::new(self.value - other.value, self.modulus)
-        } else {
-            Self::new(self.modulus - (other.value - self.value), self.modulus)
-        }
+        *self - *other
     }
 
     /// Performs modular multiplication.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the moduli differ.
+    #[must_use]
     pub fn mul_mod(&self, other: &Self) -> Self {
-        assert_eq!(self.modulus, other.modulus, "Modulus mismatch in mul_mod");
-        Self::new(
-            (self.value as u128 * other.value as u128 % self.modulus as u128) as u64,
-            self.modulus,
-        )
+        *self * *other
     }
 
-    /// Computes the modular exponentiation: self^exponent mod modulus.
+    /// Performs modular addition, returning an error on modulus mismatch.
     ///
-    /// Uses the square-and-multiply algorithm for efficient computation.
-    pub fn pow_mod(&self, exponent: u64) -> Self {
-        if exponent == 0 {
-            return Self::new(1, self.modulus);
-        }
-
-        let mut base = *self;
-        let mut result = Self::new(1, self.modulus);
-        let mut exp = exponent;
+    /// # Errors
+    ///
+    /// Returns `ModularError::ModulusMismatch` if the moduli differ.
+    pub fn checked_add(&self, other: &Self) -> Result<Self, ModularError> {
+        self.check_modulus(other)?;
+        Ok(*self + *other)
+    }
 
-        while exp > 0 {
-            if exp & 1 == 1 {
-                result = result.mul_mod(&base);
-            }
-            base = base.mul_mod(&base);
-            exp >>= 1;
-        }
+    /// Performs modular subtraction, returning an error on modulus mismatch.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::ModulusMismatch` if the moduli differ.
+    pub fn checked_sub(&self, other: &Self) -> Result<Self, ModularError> {
+        self.check_modulus(other)?;
+        Ok(*self - *other)
+    }
 
-        result
+    /// Performs modular multiplication, returning an error on modulus mismatch.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::ModulusMismatch` if the moduli differ.
+    pub fn checked_mul(&self, other: &Self) -> Result<Self, ModularError> {
+        self.check_modulus(other)?;
+        Ok(*self * *other)
     }
 
     /// Computes the modular inverse: self^(-1) mod modulus.
@@ -305,105 +279,104 @@ impl ModularInt<u64> {
     /// # Panics
     ///
     /// Panics if the inverse does not exist (i.e., if gcd(self.value, modulus) != 1).
+    #[must_use]
     pub fn inverse_mod(&self) -> Self {
-        use num_integer::gcd;
-
-        if self.value == 0 {
-            panic!("Cannot compute the inverse of 0");
-        }
-
-        if gcd(self.value, self.modulus) != 1 {
-            panic!("The inverse does not exist because gcd(value, modulus) != 1");
+        match self.checked_inverse() {
+            Ok(inverse) => inverse,
+            Err(err) => panic!("{err}"),
         }
+    }
 
-        // Extended Euclidean Algorithm
-        let (mut s, mut old_s) = (0i64, 1i64);
-        let (mut t, mut old_t) = (1i64, 0i64);
-        let (mut r, mut old_r) = (self.modulus as i64, self.value as i64);
+    /// Computes the modular inverse, returning an error if it does not exist.
+    ///
+    /// Uses the extended Euclidean algorithm with the Bezout coefficient kept reduced
+    /// modulo the modulus, so no signed or wider intermediate type is needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::NotInvertible` if gcd(self.value, modulus) != 1.
+    pub fn checked_inverse(&self) -> Result<Self, ModularError> {
+        let modulus = self.modulus;
 
-        while r != 0 {
-            let quotient = old_r / r;
+        // Invariant: r ≡ s * value (mod modulus) for both (old_r, old_s) and (r, s)
+        let (mut old_r, mut r) = (modulus, self.value);
+        let (mut old_s, mut s) = (T::zero(), T::reduce(T::one(), modulus));
 
-            let temp = old_r;
-            old_r = r;
-            r = temp - quotient * r;
+        while !r.is_zero() {
+            let quotient = T::reduce(old_r / r, modulus);
 
-            let temp = old_s;
-            old_s = s;
-            s = temp - quotient * s;
+            let temp = r;
+            r = old_r % r;
+            old_r = temp;
 
-            let temp = old_t;
-            old_t = t;
-            t = temp - quotient * t;
+            let temp = s;
+            s = T::sub_mod(old_s, T::mul_mod(quotient, s, modulus), modulus);
+            old_s = temp;
         }
 
-        // Ensure we have the correct result
-        if old_r != 1 {
-            panic!("The inverse does not exist");
+        // old_r is now gcd(value, modulus)
+        if old_r != T::one() {
+            return Err(ModularError::NotInvertible);
         }
 
-        // Convert result to positive
-        let result = if old_s < 0 {
-            old_s + self.modulus as i64
-        } else {
-            old_s
-        };
-
-        Self::new(result as u64, self.modulus)
+        Ok(Self {
+            value: old_s,
+            modulus,
+        })
     }
-}
 
-// Implementation for u32
-impl ModularInt<u32> {
-    /// Creates a new ModularInt with the given value and modulus.
-    ///
-    /// # Arguments
+    /// Computes the modular exponentiation: self^exponent mod modulus.
     ///
-    /// * `value` - The integer value.
-    /// * `modulus` - The modulus.
+    /// Accepts any `Exponent`, including `u64` limb slices and `BigUint`. Odd `u64`
+    /// and `u128` moduli run the loop in Montgomery form.
+    #[must_use]
+    pub fn pow<E: Exponent>(&self, exponent: E) -> Self {
+        self.pow_with(exponent, PowStrategy::Auto)
+    }
+
+    /// Computes self^exponent mod modulus with an explicit exponentiation strategy.
     ///
     /// # Panics
     ///
-    /// Panics if the modulus is zero.
-    pub fn new(value: u32, modulus: u32) -> Self {
-        assert!(modulus > 0, "Modulus cannot be zero");
-        let mut result = Self { value, modulus };
-        result.reduce();
-        result
+    /// Panics if a window strategy is given a width outside 1 to 8.
+    #[must_use]
+    pub fn pow_with<E: Exponent>(&self, exponent: E, strategy: PowStrategy) -> Self {
+        Self {
+            value: T::pow_mod(self.value, &exponent, self.modulus, strategy),
+            modulus: self.modulus,
+        }
     }
 
-    fn reduce(&mut self) {
-        if self.value >= self.modulus {
-            self.value %= self.modulus;
-        }
+    /// Computes the modular exponentiation for a `u64` exponent.
+    #[must_use]
+    pub fn pow_mod(&self, exponent: u64) -> Self {
+        self.pow(exponent)
     }
 
-    pub fn add_mod(&self, other: &Self) -> Self {
-        assert_eq!(self.modulus, other.modulus, "Modulus mismatch in add_mod");
-        let sum = self.value.wrapping_add(other.value);
-        if sum >= self.modulus || sum < self.value {
-            Self::new(sum % self.modulus, self.modulus)
+    /// Computes self^exponent mod modulus for a signed exponent.
+    ///
+    /// A negative exponent raises the inverse to the absolute value of the exponent.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::NotInvertible` if the exponent is negative and the
+    /// inverse does not exist.
+    pub fn checked_pow(&self, exponent: i64) -> Result<Self, ModularError> {
+        if exponent < 0 {
+            Ok(self.checked_inverse()?.pow(exponent.unsigned_abs()))
         } else {
-            Self::new(sum, self.modulus)
+            Ok(self.pow(exponent.unsigned_abs()))
         }
     }
 
-    pub fn sub_mod(&self, other: &Self) -> Self {
-        assert_eq!(self.modulus, other.modulus, "Modulus mismatch in sub_mod");
-        if self.value >= other.value {
-            Self::new(self.value - other.value, self.modulus)
+    /// Returns an error unless both operands share a modulus.
+    fn check_modulus(&self, other: &Self) -> Result<(), ModularError> {
+        if self.modulus == other.modulus {
+            Ok(())
         } else {
-            Self::new(self.modulus - (other.value - self.value), self.modulus)
+            Err(ModularError::ModulusMismatch)
         }
     }
-
-    pub fn mul_mod(&self, other: &Self) -> Self {
-        assert_eq!(self.modulus, other.modulus, "Modulus mismatch in mul_mod");
-        Self::new(
-            (self.value as u64 * other.value as u64 % self.modulus as u64) as u32,
-            self.modulus,
-        )
-    }
 }
 
 #[cfg(test)]
@@ -421,6 +394,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::many_single_char_names)] // a through e are the operands and results
     fn test_modular_arithmetic_u64() {
         let a = ModularInt::<u64>::new(5u64, 17);
         let b = ModularInt::<u64>::new(7u64, 17);
@@ -445,6 +419,32 @@ mod tests {
         assert_eq!(c.value(), 1); // 2^8 % 17 = 256 % 17 = 1
     }
 
+    #[test]
+    fn test_pow_every_backend() {
+        // 3^(p - 1) = 1 for prime p, on every width
+        assert_eq!(ModularInt::<u8>::new(3, 251).pow(250u8).value(), 1);
+        assert_eq!(ModularInt::<u32>::new(3, 65_521).pow_mod(65_520).value(), 1);
+        assert_eq!(
+            ModularInt::<usize>::new(3, 65_521).pow(65_520usize).value(),
+            1
+        );
+
+        let p = u128::MAX - 158; // 2^128 - 159
+        assert_eq!(ModularInt::<u128>::new(3, p).pow(p - 1).value(), 1);
+        assert_eq!(ModularInt::<u128>::new(3, p - 1).pow(p - 2).value(), {
+            // Even modulus takes the plain path; cross-check against a fixed window
+            ModularInt::<u128>::new(3, p - 1)
+                .pow_with(p - 2, PowStrategy::FixedWindow(4))
+                .value()
+        });
+
+        // A 128-bit exponent as u64 limbs: 2^64 * (p - 1) + (p - 1) = (2^64 + 1)(p - 1)
+        let q = 0xFFFF_FFFF_FFFF_FFC5u64; // 2^64 - 59
+        let a = ModularInt::<u64>::new(0x1234_5678, q);
+        assert_eq!(a.pow([q - 1, q - 1]).value(), 1);
+        assert_eq!(a.pow(&[q, 0, 0][..]), a);
+    }
+
     #[test]
     fn test_modular_inverse() {
         let a = ModularInt::<u64>::new(3u64, 17);
@@ -455,4 +455,128 @@ mod tests {
         let d = c.inverse_mod();
         assert_eq!(d.value(), 7); // 5 * 7 % 17 = 35 % 17 = 1
     }
+
+    #[test]
+    fn test_operator_overloads_u64() {
+        let a = ModularInt::<u64>::new(5u64, 17);
+        let b = ModularInt::<u64>::new(7u64, 17);
+
+        assert_eq!((a + b).value(), 12);
+        assert_eq!((a - b).value(), 15);
+        assert_eq!((a * b).value(), 1);
+        assert_eq!((-a).value(), 12);
+        assert_eq!((-ModularInt::<u64>::new(0, 17)).value(), 0);
+
+        let mut c = a;
+        c += b;
+        assert_eq!(c.value(), 12);
+        c -= b;
+        assert_eq!(c, a);
+        c *= b;
+        assert_eq!(c.value(), 1);
+    }
+
+    #[test]
+    fn test_operator_overloads_near_type_max() {
+        let m = u64::MAX;
+        let a = ModularInt::<u64>::new(m - 1, m);
+        assert_eq!((a + a).value(), m - 2);
+        assert_eq!((a * a).value(), 1); // (-1)^2
+
+        let m = u8::MAX;
+        let a = ModularInt::<u8>::new(254, m);
+        assert_eq!((a + a).value(), 253);
+        assert_eq!((a * a).value(), 1);
+
+        let m = u128::MAX;
+        let a = ModularInt::<u128>::new(m - 1, m);
+        assert_eq!((a + a).value(), m - 2);
+        assert_eq!((a * a).value(), 1);
+    }
+
+    #[test]
+    fn test_operators_every_width() {
+        assert_eq!(
+            (ModularInt::<u16>::new(300, 1000) * ModularInt::new(7, 1000)).value(),
+            100
+        );
+        assert_eq!(
+            (ModularInt::<u32>::new(3, 10) - ModularInt::new(4, 10)).value(),
+            9
+        );
+        assert_eq!(
+            (ModularInt::<usize>::new(9, 10) + ModularInt::new(4, 10)).value(),
+            3
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Modulus mismatch in addition")]
+    fn test_operator_modulus_mismatch() {
+        let _ = ModularInt::<u64>::new(1, 17) + ModularInt::<u64>::new(1, 19);
+    }
+
+    #[test]
+    fn test_fallible_construction_and_arithmetic() {
+        assert_eq!(
+            ModularInt::<u64>::try_new(5, 0),
+            Err(ModularError::ZeroModulus)
+        );
+
+        let a = ModularInt::<u64>::try_new(5, 17).unwrap();
+        let b = ModularInt::<u64>::new(7, 17);
+        let other = ModularInt::<u64>::new(7, 19);
+
+        assert_eq!(a.checked_add(&b).map(|c| c.value()), Ok(12));
+        assert_eq!(a.checked_sub(&b).map(|c| c.value()), Ok(15));
+        assert_eq!(a.checked_mul(&b).map(|c| c.value()), Ok(1));
+        assert_eq!(a.checked_add(&other), Err(ModularError::ModulusMismatch));
+        assert_eq!(a.checked_sub(&other), Err(ModularError::ModulusMismatch));
+        assert_eq!(a.checked_mul(&other), Err(ModularError::ModulusMismatch));
+    }
+
+    #[test]
+    fn test_checked_inverse() {
+        assert_eq!(
+            ModularInt::<u64>::new(6, 9).checked_inverse(),
+            Err(ModularError::NotInvertible)
+        );
+        assert_eq!(
+            ModularInt::<u64>::new(0, 17).checked_inverse(),
+            Err(ModularError::NotInvertible)
+        );
+
+        // Moduli above 2^63 used to overflow the signed extended Euclid
+        let m = u64::MAX;
+        let a = ModularInt::<u64>::new(m - 1, m);
+        assert_eq!(a.checked_inverse(), Ok(a));
+
+        let m = u128::MAX - 158;
+        let a = ModularInt::<u128>::new(0x1234_5678_9ABC_DEF0_1234_5678_9ABC_DEF0, m);
+        assert_eq!((a * a.checked_inverse().unwrap()).value(), 1);
+
+        assert_eq!(ModularInt::<u8>::new(3, 7).inverse_mod().value(), 5);
+    }
+
+    #[test]
+    fn test_checked_pow() {
+        let a = ModularInt::<u64>::new(3, 17);
+        assert_eq!(a.checked_pow(2).map(|c| c.value()), Ok(9));
+        assert_eq!(a.checked_pow(-1).map(|c| c.value()), Ok(6));
+        assert_eq!(a.checked_pow(-2).map(|c| c.value()), Ok(2)); // 6^2 = 36 = 2 mod 17
+        assert_eq!(
+            ModularInt::<u64>::new(3, 9).checked_pow(-1),
+            Err(ModularError::NotInvertible)
+        );
+        assert_eq!(
+            ModularInt::<u64>::new(3, 9).checked_pow(i64::MIN + 1),
+            Err(ModularError::NotInvertible)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the inverse does not exist")]
+    fn test_inverse_mod_panics_when_not_invertible() {
+        let _ = ModularInt::<u64>::new(6, 9).inverse_mod();
+    }
 }
@@ -1,9 +1,12 @@
 // Montgomery reduction implementation
 
-use crate::ModularInt;
+use crate::wide::{add_wide_u128, mul_add_carry, mul_wide_u128};
+use crate::{pow_with, Exponent, ModularError, ModularInt, ModularStorage, PowStrategy, Uint};
+#[cfg(feature = "arbitrary-precision")]
+use num_bigint::BigUint;
+#[cfg(feature = "arbitrary-precision")]
 use num_traits::{One, Zero};
 use std::fmt::Debug;
-use std::ops::{Add, Mul, Sub};
 
 /// Montgomery context for efficient modular multiplication.
 ///
@@ -14,159 +17,541 @@ use std::ops::{Add, Mul, Sub};
 pub struct MontgomeryContext<T> {
     modulus: T,
     r_squared: T, // R² mod N
-    r_inverse: T, // R⁻¹ mod N
     n_prime: T,   // -N⁻¹ mod R
 }
 
 // Generic trait for Montgomery arithmetic operations
 pub trait MontgomeryArithmetic<T> {
     fn to_montgomery(&self, ctx: &MontgomeryContext<T>) -> ModularInt<T>;
+    #[allow(clippy::wrong_self_convention)] // converts the receiver out of Montgomery form
     fn from_montgomery(&self, ctx: &MontgomeryContext<T>) -> ModularInt<T>;
     fn montgomery_mul(&self, other: &ModularInt<T>, ctx: &MontgomeryContext<T>) -> ModularInt<T>;
 }
 
-// Generic implementation with placeholder methods
-impl<T> MontgomeryContext<T>
-where
-    T: Copy
-        + PartialEq
-        + PartialOrd
-        + Eq
-        + Zero
-        + One
-        + Add<Output = T>
-        + Sub<Output = T>
-        + Mul<Output = T>
-        + Debug,
-{
+// Accessors shared by every primitive word type
+impl<T: ModularStorage> MontgomeryContext<T> {
     /// Returns the modulus used in this Montgomery context.
-    pub fn modulus(&self) -> T {
+    #[must_use]
+    pub const fn modulus(&self) -> T {
         self.modulus
     }
+
+    /// Returns R² mod N, which converts a residue into Montgomery form.
+    pub(crate) const fn r_squared(&self) -> T {
+        self.r_squared
+    }
+
+    /// Returns -N⁻¹ mod R, the multiplier used by Montgomery reduction.
+    pub(crate) const fn n_prime(&self) -> T {
+        self.n_prime
+    }
 }
 
-// Implementation for u64
-impl MontgomeryContext<u64> {
+/// Integer types for which a `MontgomeryContext` can be built.
+pub trait MontgomeryWord: Sized {
+    /// Validates the modulus and precomputes the constants for Montgomery arithmetic.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::ZeroModulus` if the modulus is zero and
+    /// `ModularError::EvenModulus` if it is even.
+    fn montgomery_context(modulus: Self) -> Result<MontgomeryContext<Self>, ModularError>;
+
+    /// Computes a * b * R⁻¹ mod N for operands below the modulus.
+    fn montgomery_product(ctx: &MontgomeryContext<Self>, a: &Self, b: &Self) -> Self;
+
+    /// Computes a * R⁻¹ mod N, the Montgomery reduction (REDC) of a single word.
+    fn montgomery_redc(ctx: &MontgomeryContext<Self>, a: &Self) -> Self;
+}
+
+// Construction shared by every supported word type
+impl<T: MontgomeryWord> MontgomeryContext<T> {
     /// Creates a new Montgomery context for the given modulus.
     ///
     /// Precomputes required values for Montgomery arithmetic.
-    pub fn new(modulus: u64) -> Self {
-        assert!(
-            modulus % 2 == 1,
-            "Modulus must be odd for Montgomery reduction"
-        );
-        assert!(modulus > 0, "Modulus must be positive");
+    ///
+    /// # Panics
+    ///
+    /// Panics if the modulus is zero or even.
+    #[must_use]
+    pub fn new(modulus: T) -> Self {
+        match Self::try_new(modulus) {
+            Ok(ctx) => ctx,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Creates a new Montgomery context, returning an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::ZeroModulus` if the modulus is zero and
+    /// `ModularError::EvenModulus` if it is even.
+    pub fn try_new(modulus: T) -> Result<Self, ModularError> {
+        T::montgomery_context(modulus)
+    }
+}
+
+// Implementation for u64
+impl MontgomeryWord for u64 {
+    fn montgomery_context(modulus: Self) -> Result<MontgomeryContext<Self>, ModularError> {
+        if modulus == 0 {
+            return Err(ModularError::ZeroModulus);
+        }
+        if modulus.is_multiple_of(2) {
+            return Err(ModularError::EvenModulus);
+        }
 
         // Compute R = 2^64 mod n
         // For u64, R is implicitly 2^64, which is congruent to 0 mod 2^64
         // So we don't store R explicitly
 
         // Compute R^2 mod n
-        let r_squared = Self::compute_r_squared(modulus);
+        let r_squared = MontgomeryContext::<Self>::compute_r_squared(modulus);
 
         // Compute n' such that n * n' ≡ -1 (mod R)
         // This is equivalent to finding n' such that n * n' ≡ -1 (mod 2^64)
-        let n_prime = Self::compute_n_prime(modulus);
+        let n_prime = MontgomeryContext::<Self>::compute_n_prime(modulus);
 
-        Self {
+        Ok(MontgomeryContext {
             modulus,
             r_squared,
-            r_inverse: 1, // Not actually used in the implementation
             n_prime,
-        }
+        })
     }
 
-    /// Computes R^2 mod n where R = 2^64
-    fn compute_r_squared(modulus: u64) -> u64 {
-        // Start with 1 (which is 1 * R^0)
-        // Square it 64 times, each time multiplying by 2^2 mod n
-        // This gives us (2^2)^64 mod n = 2^128 mod n = R^2 mod n
-        let mut result = 1u128;
-        for _ in 0..128 {
-            result = (result << 1) % modulus as u128;
-        }
-        result as u64
+    fn montgomery_product(ctx: &MontgomeryContext<Self>, a: &Self, b: &Self) -> Self {
+        // Montgomery multiplication is just regular multiplication followed by Montgomery reduction
+        ctx.montgomery_reduction(u128::from(*a) * u128::from(*b))
     }
 
-    /// Computes n' such that n * n' ≡ -1 (mod 2^64)
-    fn compute_n_prime(modulus: u64) -> u64 {
-        // Extended Binary GCD to compute the modular inverse
-        let mut t = 0u64;
-        let mut r = 0u64;
-        let mut new_t = 1u64;
-        let mut new_r = modulus;
-        let mut k = 0;
-        let mut q = 0;
-        let mut temp = 0;
-
-        while new_r != 0 {
-            temp = r;
-            q = temp / new_r;
-            r = new_r;
-            new_r = temp - q * new_r;
+    fn montgomery_redc(ctx: &MontgomeryContext<Self>, a: &Self) -> Self {
+        ctx.montgomery_reduction(u128::from(*a))
+    }
+}
 
-            temp = t;
-            t = new_t;
-            new_t = temp.wrapping_sub(q.wrapping_mul(new_t));
+impl MontgomeryContext<u64> {
+    /// Computes R^2 mod n where R = 2^64
+    #[allow(clippy::cast_possible_truncation)] // the remainder is below the modulus
+    pub(crate) const fn compute_r_squared(modulus: u64) -> u64 {
+        // R^2 mod n = 2^128 mod n = ((2^128 - 1) mod n + 1) mod n, with one reduction
+        // of the full u128 and a second in case the sum equals n
+        let modulus = modulus as u128;
+        ((u128::MAX % modulus + 1) % modulus) as u64
+    }
 
-            k += 1;
+    /// Computes n' such that n * n' ≡ -1 (mod 2^64)
+    pub(crate) const fn compute_n_prime(modulus: u64) -> u64 {
+        // Newton's iteration for the inverse modulo 2^64: every odd n is its own inverse
+        // modulo 8, and each step x <- x * (2 - n * x) doubles the number of correct bits,
+        // so five steps take 3 correct bits to 96 >= 64.
+        let mut inverse = modulus;
+        let mut i = 0;
+        while i < 5 {
+            inverse = inverse.wrapping_mul(2u64.wrapping_sub(modulus.wrapping_mul(inverse)));
+            i += 1;
         }
 
-        t.wrapping_neg()
+        inverse.wrapping_neg()
     }
 
     /// Performs the Montgomery reduction.
     ///
     /// Given T = a * b, computes T * R^(-1) mod n efficiently.
-    fn montgomery_reduction(&self, t: u128) -> u64 {
+    const fn montgomery_reduction(&self, t: u128) -> u64 {
+        Self::montgomery_reduce(t, self.modulus, self.n_prime)
+    }
+
+    /// Montgomery reduction with explicit constants, usable in `const` contexts.
+    ///
+    /// Requires T < n * R.
+    #[allow(clippy::cast_possible_truncation)] // m only needs T mod R, and the result is below n
+    pub(crate) const fn montgomery_reduce(t: u128, modulus: u64, n_prime: u64) -> u64 {
         // Compute m = (T mod R) * n' mod R
-        let m = ((t as u64).wrapping_mul(self.n_prime)) as u128;
+        let m = (t as u64).wrapping_mul(n_prime);
 
-        // Compute t = (T + m * n) / R
-        let t = (t + m * self.modulus as u128) >> 64;
+        // Compute t = (T + m * n) / R. For T < n * R the true sum is below 2nR < 2^129,
+        // so add the low halves separately and keep only the carry out of bit 64.
+        let mn = m as u128 * modulus as u128;
+        let (sum, overflow) = t.overflowing_add(mn);
+        let t = (sum >> 64) | ((overflow as u128) << 64);
 
         // If t >= n, return t - n; else return t
-        if t >= self.modulus as u128 {
-            (t - self.modulus as u128) as u64
+        if t >= modulus as u128 {
+            (t - modulus as u128) as u64
         } else {
             t as u64
         }
     }
 }
 
-// Implementation of MontgomeryArithmetic for u64
-impl MontgomeryArithmetic<u64> for ModularInt<u64> {
-    fn to_montgomery(&self, ctx: &MontgomeryContext<u64>) -> ModularInt<u64> {
+// Implementation for u128
+impl MontgomeryWord for u128 {
+    fn montgomery_context(modulus: Self) -> Result<MontgomeryContext<Self>, ModularError> {
+        if modulus == 0 {
+            return Err(ModularError::ZeroModulus);
+        }
+        if modulus.is_multiple_of(2) {
+            return Err(ModularError::EvenModulus);
+        }
+
+        Ok(MontgomeryContext {
+            modulus,
+            r_squared: MontgomeryContext::<Self>::compute_r_squared(modulus),
+            n_prime: MontgomeryContext::<Self>::compute_n_prime(modulus),
+        })
+    }
+
+    fn montgomery_product(ctx: &MontgomeryContext<Self>, a: &Self, b: &Self) -> Self {
+        let (high, low) = mul_wide_u128(*a, *b);
+        ctx.montgomery_reduction(high, low)
+    }
+
+    fn montgomery_redc(ctx: &MontgomeryContext<Self>, a: &Self) -> Self {
+        ctx.montgomery_reduction(0, *a)
+    }
+}
+
+impl MontgomeryContext<u128> {
+    /// Computes R^2 mod n where R = 2^128
+    fn compute_r_squared(modulus: u128) -> u128 {
+        // R mod n = (2^128 - 1) mod n + 1, reduced once more in case it equals n
+        let r = (u128::MAX % modulus + 1) % modulus;
+        u128::mul_mod(r, r, modulus)
+    }
+
+    /// Computes n' such that n * n' ≡ -1 (mod 2^128)
+    const fn compute_n_prime(modulus: u128) -> u128 {
+        // Newton's iteration as for u64; six steps take 3 correct bits to 192 >= 128
+        let mut inverse = modulus;
+        let mut i = 0;
+        while i < 6 {
+            inverse = inverse.wrapping_mul(2u128.wrapping_sub(modulus.wrapping_mul(inverse)));
+            i += 1;
+        }
+
+        inverse.wrapping_neg()
+    }
+
+    /// Performs the Montgomery reduction of the 256-bit value (high, low).
+    ///
+    /// Given T = a * b < n * R, computes T * R^(-1) mod n.
+    const fn montgomery_reduction(&self, high: u128, low: u128) -> u128 {
+        // Compute m = (T mod R) * n' mod R
+        let m = low.wrapping_mul(self.n_prime);
+
+        // Compute t = (T + m * n) / R; the low half of the sum is zero by construction
+        // and the sum is below 2nR, so the carry out of the top word is bit 128 of t
+        let ((t, _), overflow) = add_wide_u128((high, low), mul_wide_u128(m, self.modulus));
+
+        // If t >= n, return t - n; else return t
+        if overflow || t >= self.modulus {
+            t.wrapping_sub(self.modulus)
+        } else {
+            t
+        }
+    }
+}
+
+// Implementation for BigUint, with R = 2^(64 * limbs) for a modulus of `limbs` 64-bit words
+#[cfg(feature = "arbitrary-precision")]
+impl MontgomeryWord for BigUint {
+    fn montgomery_context(modulus: Self) -> Result<MontgomeryContext<Self>, ModularError> {
+        if modulus.is_zero() {
+            return Err(ModularError::ZeroModulus);
+        }
+        if !modulus.bit(0) {
+            return Err(ModularError::EvenModulus);
+        }
+
+        let limbs = modulus.iter_u64_digits().len();
+        let r_squared = (Self::one() << (128 * limbs)) % &modulus;
+        // Word-by-word reduction only needs -N⁻¹ mod 2^64, not mod R
+        let low_limb = modulus.iter_u64_digits().next().unwrap_or_default();
+        let n_prime = Self::from(MontgomeryContext::<u64>::compute_n_prime(low_limb));
+
+        Ok(MontgomeryContext {
+            modulus,
+            r_squared,
+            n_prime,
+        })
+    }
+
+    fn montgomery_product(ctx: &MontgomeryContext<Self>, a: &Self, b: &Self) -> Self {
+        let modulus = ctx.modulus.to_u64_digits();
+        let mut product = vec![0; modulus.len()];
+        montgomery_mul_limbs(
+            &to_limbs(a, modulus.len()),
+            &to_limbs(b, modulus.len()),
+            &modulus,
+            ctx.limb_n_prime(),
+            &mut product,
+        );
+        from_limbs(&product)
+    }
+
+    fn montgomery_redc(ctx: &MontgomeryContext<Self>, a: &Self) -> Self {
+        Self::montgomery_product(ctx, a, &Self::one())
+    }
+}
+
+#[cfg(feature = "arbitrary-precision")]
+impl MontgomeryContext<BigUint> {
+    /// Returns the modulus used in this Montgomery context.
+    #[must_use]
+    pub const fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    /// Returns R² mod N, which converts a residue into Montgomery form.
+    pub(crate) const fn r_squared(&self) -> &BigUint {
+        &self.r_squared
+    }
+
+    /// Returns -N⁻¹ mod 2^64, the per-word multiplier for CIOS reduction.
+    pub(crate) fn limb_n_prime(&self) -> u64 {
+        self.n_prime.iter_u64_digits().next().unwrap_or_default()
+    }
+}
+
+// Implementation for fixed-size multi-limb integers, with R = 2^(64 * LIMBS)
+impl<const LIMBS: usize> MontgomeryWord for Uint<LIMBS> {
+    fn montgomery_context(modulus: Self) -> Result<MontgomeryContext<Self>, ModularError> {
+        if modulus.is_zero() {
+            return Err(ModularError::ZeroModulus);
+        }
+        if !modulus.is_odd() {
+            return Err(ModularError::EvenModulus);
+        }
+
+        // R² mod N by doubling 1 modulo N, 2 * 64 * LIMBS times, without any division
+        let mut r_squared = if modulus == Self::ONE {
+            Self::ZERO
+        } else {
+            Self::ONE
+        };
+        for _ in 0..2 * Self::BITS {
+            r_squared = r_squared.add_mod(&r_squared, &modulus);
+        }
+        // Word-by-word reduction only needs -N⁻¹ mod 2^64, not mod R
+        let n_prime = Self::from_u64(MontgomeryContext::<u64>::compute_n_prime(
+            modulus.as_words()[0],
+        ));
+
+        Ok(MontgomeryContext {
+            modulus,
+            r_squared,
+            n_prime,
+        })
+    }
+
+    fn montgomery_product(ctx: &MontgomeryContext<Self>, a: &Self, b: &Self) -> Self {
+        let mut product = [0; LIMBS];
+        montgomery_mul_limbs(
+            a.as_words(),
+            b.as_words(),
+            ctx.modulus.as_words(),
+            ctx.limb_n_prime(),
+            &mut product,
+        );
+        Self::from_words(product)
+    }
+
+    fn montgomery_redc(ctx: &MontgomeryContext<Self>, a: &Self) -> Self {
+        Self::montgomery_product(ctx, a, &Self::ONE)
+    }
+}
+
+impl<const LIMBS: usize> MontgomeryContext<Uint<LIMBS>> {
+    /// Returns the modulus used in this Montgomery context.
+    #[must_use]
+    pub const fn modulus(&self) -> &Uint<LIMBS> {
+        &self.modulus
+    }
+
+    /// Returns R² mod N, which converts a residue into Montgomery form.
+    pub(crate) const fn r_squared(&self) -> &Uint<LIMBS> {
+        &self.r_squared
+    }
+
+    /// Returns -N⁻¹ mod 2^64, the per-word multiplier for CIOS reduction.
+    pub(crate) const fn limb_n_prime(&self) -> u64 {
+        self.n_prime.as_words()[0]
+    }
+
+    /// Reduces any value modulo N.
+    #[must_use]
+    pub fn reduce(&self, value: &Uint<LIMBS>) -> Uint<LIMBS> {
+        // value * R² * R⁻¹ = value * R mod N, then one more reduction drops the R
+        let value = Uint::montgomery_product(self, value, &self.r_squared);
+        Uint::montgomery_redc(self, &value)
+    }
+
+    /// Computes (a * b) mod N for any a and b, without leaving the stack.
+    #[must_use]
+    pub fn mul_mod(&self, a: &Uint<LIMBS>, b: &Uint<LIMBS>) -> Uint<LIMBS> {
+        // a * R² * R⁻¹ = aR < N, and aR * b * R⁻¹ = ab; CIOS only needs one operand below N
+        let a = Uint::montgomery_product(self, a, &self.r_squared);
+        Uint::montgomery_product(self, &a, b)
+    }
+
+    /// Computes base^exponent mod N with every step in Montgomery form.
+    #[must_use]
+    pub fn pow_mod<E: Exponent>(&self, base: &Uint<LIMBS>, exponent: E) -> Uint<LIMBS> {
+        let base = Uint::montgomery_product(self, base, &self.r_squared);
+        let result = pow_with(self, &base, &exponent, PowStrategy::Auto);
+        Uint::montgomery_redc(self, &result)
+    }
+}
+
+/// Computes a * b * R⁻¹ mod N with CIOS (coarsely integrated operand scanning).
+///
+/// All operands are little-endian limbs of the same length as the modulus, with
+/// a, b < N and R = 2^(64 * limbs); the result is written to `out`. Interleaves one
+/// row of the schoolbook product with one word of Montgomery reduction, so the
+/// accumulator is `out` plus two extra words and nothing is allocated.
+///
+/// With hardware acceleration on an x86_64 CPU with BMI2 and ADX, the whole loop
+/// runs in the MULX/ADX kernel instead; the choice is made once per product.
+pub fn montgomery_mul_limbs(a: &[u64], b: &[u64], modulus: &[u64], n_prime: u64, out: &mut [u64]) {
+    #[cfg(all(feature = "hardware-acceleration", target_arch = "x86_64"))]
+    if crate::intrinsics::arithmetic::montgomery_mul_limbs(a, b, modulus, n_prime, out) {
+        return;
+    }
+    montgomery_mul_limbs_portable(a, b, modulus, n_prime, out);
+}
+
+/// The portable CIOS loop behind `montgomery_mul_limbs`.
+#[allow(clippy::many_single_char_names)] // s, t, m, a, b as in the CIOS description
+pub fn montgomery_mul_limbs_portable(
+    a: &[u64],
+    b: &[u64],
+    modulus: &[u64],
+    n_prime: u64,
+    out: &mut [u64],
+) {
+    let s = modulus.len();
+    let t = &mut out[..s];
+    t.fill(0);
+    let mut t_s = 0u64;
+
+    for &b_i in b {
+        // t += a * b_i
+        let mut carry = 0;
+        for (t_j, &a_j) in t.iter_mut().zip(a) {
+            (carry, *t_j) = mul_add_carry(a_j, b_i, *t_j, carry);
+        }
+        let (sum, overflow) = t_s.overflowing_add(carry);
+        let t_s1 = u64::from(overflow);
+
+        // t = (t + m * N) / 2^64, where m makes the lowest word vanish
+        let t_s0 = sum;
+        let m = t[0].wrapping_mul(n_prime);
+        let (mut carry, _) = mul_add_carry(m, modulus[0], t[0], 0);
+        for j in 1..s {
+            (carry, t[j - 1]) = mul_add_carry(m, modulus[j], t[j], carry);
+        }
+        let (sum, overflow) = t_s0.overflowing_add(carry);
+        t[s - 1] = sum;
+        t_s = t_s1 + u64::from(overflow);
+    }
+
+    subtract_modulus_once(t, t_s, modulus);
+}
+
+/// Finishes a CIOS product held in `t` plus the extra word `t_s`.
+///
+/// The accumulator is below 2N, so at most one subtraction brings it into range.
+pub fn subtract_modulus_once(t: &mut [u64], t_s: u64, modulus: &[u64]) {
+    if t_s != 0 || limbs_less_than(modulus, t) || t == modulus {
+        let mut borrow = false;
+        for (t_j, &n_j) in t.iter_mut().zip(modulus) {
+            let (difference, borrow_1) = t_j.overflowing_sub(n_j);
+            let (difference, borrow_2) = difference.overflowing_sub(u64::from(borrow));
+            *t_j = difference;
+            borrow = borrow_1 | borrow_2;
+        }
+    }
+}
+
+/// Compares two little-endian limb slices of equal length.
+fn limbs_less_than(a: &[u64], b: &[u64]) -> bool {
+    a.iter().rev().lt(b.iter().rev())
+}
+
+/// Splits a value into exactly `len` little-endian 64-bit limbs.
+#[cfg(feature = "arbitrary-precision")]
+pub fn to_limbs(value: &BigUint, len: usize) -> Vec<u64> {
+    let mut limbs = value.to_u64_digits();
+    limbs.resize(len, 0);
+    limbs
+}
+
+/// Reassembles little-endian 64-bit limbs into a BigUint.
+#[cfg(feature = "arbitrary-precision")]
+#[allow(clippy::cast_possible_truncation)] // BigUint::new takes 32-bit digits
+pub fn from_limbs(limbs: &[u64]) -> BigUint {
+    BigUint::new(
+        limbs
+            .iter()
+            .flat_map(|&limb| [limb as u32, (limb >> 32) as u32])
+            .collect(),
+    )
+}
+
+// Implementation of MontgomeryArithmetic for every supported word type
+impl<T: MontgomeryWord + ModularStorage> MontgomeryArithmetic<T> for ModularInt<T> {
+    fn to_montgomery(&self, ctx: &MontgomeryContext<T>) -> Self {
         assert_eq!(self.modulus(), ctx.modulus(), "Modulus mismatch");
         // To convert to Montgomery form, multiply by R^2 mod n and then reduce
-        let mont_value = ctx.montgomery_reduction(self.value() as u128 * ctx.r_squared as u128);
-        ModularInt::<u64>::new(mont_value, self.modulus())
+        let mont_value = T::montgomery_product(ctx, &self.value(), &ctx.r_squared);
+        Self::new(mont_value, self.modulus())
     }
 
-    fn from_montgomery(&self, ctx: &MontgomeryContext<u64>) -> ModularInt<u64> {
+    fn from_montgomery(&self, ctx: &MontgomeryContext<T>) -> Self {
         assert_eq!(self.modulus(), ctx.modulus(), "Modulus mismatch");
         // To convert from Montgomery form, apply Montgomery reduction with 1
-        let regular_value = ctx.montgomery_reduction(self.value() as u128);
-        ModularInt::<u64>::new(regular_value, self.modulus())
+        let regular_value = T::montgomery_redc(ctx, &self.value());
+        Self::new(regular_value, self.modulus())
     }
 
-    fn montgomery_mul(
-        &self,
-        other: &ModularInt<u64>,
-        ctx: &MontgomeryContext<u64>,
-    ) -> ModularInt<u64> {
+    fn montgomery_mul(&self, other: &Self, ctx: &MontgomeryContext<T>) -> Self {
         assert_eq!(self.modulus(), ctx.modulus(), "Modulus mismatch for self");
         assert_eq!(other.modulus(), ctx.modulus(), "Modulus mismatch for other");
 
-        // Montgomery multiplication is just regular multiplication followed by Montgomery reduction
-        let result = ctx.montgomery_reduction(self.value() as u128 * other.value() as u128);
-        ModularInt::<u64>::new(result, self.modulus())
+        let result = T::montgomery_product(ctx, &self.value(), &other.value());
+        Self::new(result, self.modulus())
+    }
+}
+
+// Implementation of MontgomeryArithmetic for BigUint
+#[cfg(feature = "arbitrary-precision")]
+impl MontgomeryArithmetic<BigUint> for ModularInt<BigUint> {
+    fn to_montgomery(&self, ctx: &MontgomeryContext<BigUint>) -> Self {
+        assert_eq!(self.modulus(), ctx.modulus(), "Modulus mismatch");
+        let mont_value = BigUint::montgomery_product(ctx, self.value(), ctx.r_squared());
+        Self::new(mont_value, self.modulus().clone())
+    }
+
+    fn from_montgomery(&self, ctx: &MontgomeryContext<BigUint>) -> Self {
+        assert_eq!(self.modulus(), ctx.modulus(), "Modulus mismatch");
+        let regular_value = BigUint::montgomery_redc(ctx, self.value());
+        Self::new(regular_value, self.modulus().clone())
+    }
+
+    fn montgomery_mul(&self, other: &Self, ctx: &MontgomeryContext<BigUint>) -> Self {
+        assert_eq!(self.modulus(), ctx.modulus(), "Modulus mismatch for self");
+        assert_eq!(other.modulus(), ctx.modulus(), "Modulus mismatch for other");
+
+        let result = BigUint::montgomery_product(ctx, self.value(), other.value());
+        Self::new(result, self.modulus().clone())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_montgomery_multiplication_u64() {
@@ -187,11 +572,11 @@ mod tests {
 
     #[test]
     fn test_montgomery_large_modulus() {
-        let large_prime = 0xFFFFFFFFFFFFFFFBu64; // 2^64 - 5
+        let large_prime = 0xFFFF_FFFF_FFFF_FFFBu64; // 2^64 - 5
         let ctx = MontgomeryContext::new(large_prime);
 
-        let a = ModularInt::<u64>::new(0xABCDEF0123456789u64, large_prime);
-        let b = ModularInt::<u64>::new(0x123456789ABCDEFu64, large_prime);
+        let a = ModularInt::<u64>::new(0xABCD_EF01_2345_6789u64, large_prime);
+        let b = ModularInt::<u64>::new(0x0123_4567_89AB_CDEFu64, large_prime);
 
         let a_mont = a.to_montgomery(&ctx);
         let b_mont = b.to_montgomery(&ctx);
@@ -200,7 +585,73 @@ mod tests {
         let result = result_mont.from_montgomery(&ctx);
 
         // Verify using regular multiplication
-        let expected = (a.value() as u128 * b.value() as u128 % large_prime as u128) as u64;
+        #[allow(clippy::cast_possible_truncation)] // the remainder is below the prime
+        let expected =
+            (u128::from(a.value()) * u128::from(b.value()) % u128::from(large_prime)) as u64;
         assert_eq!(result.value(), expected);
     }
+
+    #[test]
+    fn test_montgomery_try_new() {
+        assert_eq!(
+            MontgomeryContext::<u64>::try_new(0).map(|ctx| ctx.modulus()),
+            Err(ModularError::ZeroModulus)
+        );
+        assert_eq!(
+            MontgomeryContext::<u64>::try_new(18).map(|ctx| ctx.modulus()),
+            Err(ModularError::EvenModulus)
+        );
+        assert_eq!(
+            MontgomeryContext::<u64>::try_new(17).map(|ctx| ctx.modulus()),
+            Ok(17)
+        );
+        // R² mod n against (R mod n)² reduced with a u128 product
+        for modulus in [1u64, 3, 17, (1 << 32) + 15, u64::MAX] {
+            let r = (1u128 << 64) % u128::from(modulus);
+            let expected = u64::try_from(r * r % u128::from(modulus)).unwrap();
+            assert_eq!(MontgomeryContext::<u64>::new(modulus).r_squared(), expected);
+        }
+    }
+
+    #[test]
+    fn test_montgomery_u128() {
+        let large_prime = u128::MAX - 158; // 2^128 - 159
+        let ctx = MontgomeryContext::<u128>::new(large_prime);
+
+        let a = ModularInt::<u128>::new(0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210, large_prime);
+        let b = ModularInt::<u128>::new(0xF0E1_D2C3_B4A5_9687_7869_5A4B_3C2D_1E0F, large_prime);
+
+        let result = a
+            .to_montgomery(&ctx)
+            .montgomery_mul(&b.to_montgomery(&ctx), &ctx)
+            .from_montgomery(&ctx);
+        assert_eq!(result, a * b);
+        assert_eq!(result.value(), 0x6DFD_0381_76E3_C818_A974_C8A5_09F7_6D9C);
+
+        let minus_one = ModularInt::<u128>::new(large_prime - 1, large_prime).to_montgomery(&ctx);
+        let one = minus_one
+            .montgomery_mul(&minus_one, &ctx)
+            .from_montgomery(&ctx);
+        assert_eq!(one.value(), 1);
+
+        assert_eq!(
+            MontgomeryContext::<u128>::try_new(1 << 100).map(|ctx| ctx.modulus()),
+            Err(ModularError::EvenModulus)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_montgomery_u128_matches_mul(m in 0u128..(u128::MAX / 2), a: u128, b: u128) {
+            let m = 2 * m + 1;
+            let ctx = MontgomeryContext::<u128>::new(m);
+            let a = ModularInt::<u128>::new(a, m);
+            let b = ModularInt::<u128>::new(b, m);
+            let result = a
+                .to_montgomery(&ctx)
+                .montgomery_mul(&b.to_montgomery(&ctx), &ctx)
+                .from_montgomery(&ctx);
+            prop_assert_eq!(result, a * b);
+        }
+    }
 }
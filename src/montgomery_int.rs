@@ -0,0 +1,277 @@
+// Residues that stay in the Montgomery domain
+
+use crate::{
+    pow_with, Exponent, ModularError, ModularInt, ModularStorage, MontgomeryContext,
+    MontgomeryWord, PowStrategy,
+};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// A residue held in Montgomery form and bound to the context that created it.
+///
+/// Unlike the `ModularInt` returned by `MontgomeryArithmetic::to_montgomery`, a
+/// `MontgomeryInt` cannot be mixed up with an ordinary residue: all arithmetic stays
+/// in Montgomery form, and the only way back to a `ModularInt` is `from_montgomery`.
+#[derive(Debug, Clone, Copy)]
+pub struct MontgomeryInt<'ctx, T = u64> {
+    value: T, // a * R mod N
+    ctx: &'ctx MontgomeryContext<T>,
+}
+
+// Entry points into the Montgomery domain
+impl<T: MontgomeryWord + ModularStorage> MontgomeryContext<T> {
+    /// Converts a residue into Montgomery form, reducing it first if necessary.
+    #[must_use]
+    pub fn to_montgomery(&self, value: T) -> MontgomeryInt<'_, T> {
+        let value = T::reduce(value, self.modulus());
+        MontgomeryInt {
+            value: T::montgomery_product(self, &value, &self.r_squared()),
+            ctx: self,
+        }
+    }
+
+    /// Returns 0 in Montgomery form.
+    #[must_use]
+    pub fn zero(&self) -> MontgomeryInt<'_, T> {
+        MontgomeryInt {
+            value: T::zero(),
+            ctx: self,
+        }
+    }
+
+    /// Returns 1 in Montgomery form, i.e. R mod N.
+    #[must_use]
+    pub fn one(&self) -> MontgomeryInt<'_, T> {
+        // R² * R⁻¹ = R, a single reduction
+        MontgomeryInt {
+            value: T::montgomery_redc(self, &self.r_squared()),
+            ctx: self,
+        }
+    }
+}
+
+impl<'ctx, T: MontgomeryWord + ModularStorage> MontgomeryInt<'ctx, T> {
+    /// Converts the value out of Montgomery form.
+    #[must_use]
+    #[allow(clippy::wrong_self_convention)] // converts the receiver out of Montgomery form
+    pub fn from_montgomery(self) -> ModularInt<T> {
+        ModularInt::new(
+            T::montgomery_redc(self.ctx, &self.value),
+            self.ctx.modulus(),
+        )
+    }
+
+    /// Returns the raw Montgomery representation a * R mod N.
+    #[must_use]
+    pub const fn montgomery_value(self) -> T {
+        self.value
+    }
+
+    /// Returns the context this value is bound to.
+    #[must_use]
+    pub const fn context(self) -> &'ctx MontgomeryContext<T> {
+        self.ctx
+    }
+
+    /// Returns the modulus of the underlying context.
+    #[must_use]
+    pub const fn modulus(self) -> T {
+        self.ctx.modulus()
+    }
+
+    /// Computes self^exponent with every step in Montgomery form.
+    ///
+    /// Accepts any `Exponent`, including `u64` limb slices and `BigUint`.
+    #[must_use]
+    pub fn pow<E: Exponent>(self, exponent: E) -> Self {
+        Self {
+            value: pow_with(self.ctx, &self.value, &exponent, PowStrategy::Auto),
+            ctx: self.ctx,
+        }
+    }
+
+    /// Computes the modular inverse, staying in Montgomery form.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the inverse does not exist (i.e., if gcd(value, modulus) != 1).
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        match self.checked_inverse() {
+            Ok(inverse) => inverse,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Computes the modular inverse, returning an error if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::NotInvertible` if gcd(value, modulus) != 1.
+    pub fn checked_inverse(self) -> Result<Self, ModularError> {
+        // Inverting aR directly gives a⁻¹R⁻¹; two products with R² restore the R² factor
+        let inverse = ModularInt::new(self.value, self.ctx.modulus()).checked_inverse()?;
+        let r_squared = self.ctx.r_squared();
+        let value = T::montgomery_product(self.ctx, &inverse.value(), &r_squared);
+        Ok(Self {
+            value: T::montgomery_product(self.ctx, &value, &r_squared),
+            ctx: self.ctx,
+        })
+    }
+}
+
+impl<T: MontgomeryWord + ModularStorage> PartialEq for MontgomeryInt<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.ctx.modulus() == other.ctx.modulus()
+    }
+}
+
+impl<T: MontgomeryWord + ModularStorage> Eq for MontgomeryInt<'_, T> {}
+
+// Implement Add trait for MontgomeryInt
+impl<T: MontgomeryWord + ModularStorage> Add for MontgomeryInt<'_, T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        assert_eq!(
+            self.ctx.modulus(),
+            other.ctx.modulus(),
+            "Modulus mismatch in addition"
+        );
+        // aR + bR = (a + b)R, so addition is unchanged in Montgomery form
+        Self {
+            value: T::add_mod(self.value, other.value, self.ctx.modulus()),
+            ctx: self.ctx,
+        }
+    }
+}
+
+// Implement AddAssign trait for MontgomeryInt
+impl<T: MontgomeryWord + ModularStorage> AddAssign for MontgomeryInt<'_, T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+// Implement Sub trait for MontgomeryInt
+impl<T: MontgomeryWord + ModularStorage> Sub for MontgomeryInt<'_, T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        assert_eq!(
+            self.ctx.modulus(),
+            other.ctx.modulus(),
+            "Modulus mismatch in subtraction"
+        );
+        Self {
+            value: T::sub_mod(self.value, other.value, self.ctx.modulus()),
+            ctx: self.ctx,
+        }
+    }
+}
+
+// Implement SubAssign trait for MontgomeryInt
+impl<T: MontgomeryWord + ModularStorage> SubAssign for MontgomeryInt<'_, T> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+// Implement Mul trait for MontgomeryInt
+impl<T: MontgomeryWord + ModularStorage> Mul for MontgomeryInt<'_, T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        assert_eq!(
+            self.ctx.modulus(),
+            other.ctx.modulus(),
+            "Modulus mismatch in multiplication"
+        );
+        // aR * bR * R⁻¹ = abR
+        Self {
+            value: T::montgomery_product(self.ctx, &self.value, &other.value),
+            ctx: self.ctx,
+        }
+    }
+}
+
+// Implement MulAssign trait for MontgomeryInt
+impl<T: MontgomeryWord + ModularStorage> MulAssign for MontgomeryInt<'_, T> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+// Implement Neg trait for MontgomeryInt
+impl<T: MontgomeryWord + ModularStorage> Neg for MontgomeryInt<'_, T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            value: T::neg_mod(self.value, self.ctx.modulus()),
+            ctx: self.ctx,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_montgomery_int_arithmetic() {
+        let ctx = MontgomeryContext::new(19u64);
+        let a = ctx.to_montgomery(5);
+        let b = ctx.to_montgomery(7);
+
+        assert_eq!((a + b).from_montgomery().value(), 12);
+        assert_eq!((a - b).from_montgomery().value(), 17);
+        assert_eq!((a * b).from_montgomery().value(), 16);
+        assert_eq!((-a).from_montgomery().value(), 14);
+        assert_eq!(a * ctx.one(), a);
+        assert_eq!(a + ctx.zero(), a);
+        assert_eq!(ctx.one().from_montgomery().value(), 1);
+
+        // The raw representation is 5 * 2^64 mod 19, not 5
+        assert_eq!(a.montgomery_value(), 9);
+    }
+
+    #[test]
+    fn test_montgomery_int_pow_and_inverse() {
+        let large_prime = 0xFFFF_FFFF_FFFF_FFFBu64; // 2^64 - 5
+        let ctx = MontgomeryContext::new(large_prime);
+        let x = ModularInt::<u64>::new(0xABCD_EF01_2345_6789, large_prime);
+        let a = ctx.to_montgomery(x.value());
+
+        assert_eq!(a.pow(12_345u64).from_montgomery(), x.pow_mod(12_345));
+        assert_eq!(a.pow(0u64), ctx.one());
+        assert_eq!(a.inverse().from_montgomery(), x.inverse_mod());
+        assert_eq!(a * a.inverse(), ctx.one());
+
+        let composite = MontgomeryContext::new(15u64);
+        assert_eq!(
+            composite.to_montgomery(6).checked_inverse(),
+            Err(ModularError::NotInvertible)
+        );
+    }
+
+    #[test]
+    fn test_montgomery_int_u128() {
+        let large_prime = u128::MAX - 158; // 2^128 - 159
+        let ctx = MontgomeryContext::new(large_prime);
+        let x = ModularInt::<u128>::new(0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210, large_prime);
+        let y = ModularInt::<u128>::new(0xF0E1_D2C3_B4A5_9687_7869_5A4B_3C2D_1E0F, large_prime);
+        let (a, b) = (ctx.to_montgomery(x.value()), ctx.to_montgomery(y.value()));
+
+        assert_eq!((a * b).from_montgomery(), x * y);
+        assert_eq!((a - b).from_montgomery(), x - y);
+        assert_eq!(a * a.inverse(), ctx.one());
+    }
+
+    #[test]
+    #[should_panic(expected = "Modulus mismatch in multiplication")]
+    fn test_montgomery_int_modulus_mismatch() {
+        let ctx17 = MontgomeryContext::new(17u64);
+        let ctx19 = MontgomeryContext::new(19u64);
+        let _ = ctx17.one() * ctx19.one();
+    }
+}
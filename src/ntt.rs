@@ -0,0 +1,516 @@
+// Number-theoretic transforms over NTT-friendly primes
+
+use crate::{
+    ModularError, ModularInt, ModularStorage, MontgomeryContext, MontgomeryWord, PowStrategy,
+};
+
+/// 998244353 = 119 * 2^23 + 1, which supports transforms of up to 2^23 points.
+pub const P998244353: u64 = 998_244_353;
+
+/// 2^64 - 2^32 + 1, which supports transforms of up to 2^32 points.
+pub const GOLDILOCKS: u64 = 0xFFFF_FFFF_0000_0001;
+
+/// Precomputed twiddle factors for transforms of one size over one prime.
+///
+/// A plan is built for one prime p and one power-of-two size n, where n divides
+/// p - 1. Data is kept as plain residues in `u64` slices and twiddles are stored in
+/// Montgomery form, so each butterfly costs a single Montgomery reduction and the
+/// input never needs converting. Both the usual primes such as 998244353 and the
+/// 64-bit prime 2^64 - 2^32 + 1 are supported.
+///
+/// The cyclic transforms multiply polynomials modulo x^n - 1; the negacyclic ones,
+/// available when 2n also divides p - 1, multiply modulo x^n + 1.
+///
+/// ```
+/// use modularity::ntt::NttPlan;
+///
+/// let plan = NttPlan::new(998_244_353, 4);
+/// // (1 + 2x)(3 + x) = 3 + 7x + 2x²
+/// let product = plan.cyclic_convolution(&[1, 2, 0, 0], &[3, 1, 0, 0]);
+/// assert_eq!(product, vec![3, 7, 2, 0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct NttPlan {
+    ctx: MontgomeryContext<u64>,
+    size: usize,
+    roots: Vec<u64>,                      // ω^j for j < n/2, in Montgomery form
+    inverse_roots: Vec<u64>,              // ω^-j for j < n/2, in Montgomery form
+    size_inverse: u64,                    // n⁻¹ in Montgomery form
+    twists: Option<Vec<u64>>,             // ψ^i for i < n, where ψ² = ω
+    inverse_twists: Option<Vec<u64>>,     // n⁻¹ψ^-i for i < n
+    convolution_scale: u64,               // n⁻¹R in Montgomery form
+    convolution_twists: Option<Vec<u64>>, // n⁻¹ψ^-i·R for i < n
+}
+
+impl NttPlan {
+    /// Creates a plan for transforms of `size` points modulo the prime `modulus`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is not a power of two, or if the modulus is even or has no
+    /// root of unity of order `size`.
+    #[must_use]
+    pub fn new(modulus: u64, size: usize) -> Self {
+        match Self::try_new(modulus, size) {
+            Ok(plan) => plan,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Creates a plan, returning an error instead of panicking.
+    ///
+    /// The modulus is usually prime. A composite odd modulus also works when the
+    /// generator search finds an element whose 2^(k-1)-th power is -1, for 2^k the
+    /// largest power of two dividing modulus - 1; otherwise it fails with
+    /// `NoRootOfUnity`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::InvalidTransformSize` if `size` is not a power of two,
+    /// `ModularError::ZeroModulus` or `ModularError::EvenModulus` for a modulus that
+    /// cannot host a Montgomery context, and `ModularError::NoRootOfUnity` if the
+    /// modulus is 1 or `size` does not divide modulus - 1.
+    pub fn try_new(modulus: u64, size: usize) -> Result<Self, ModularError> {
+        if !size.is_power_of_two() {
+            return Err(ModularError::InvalidTransformSize);
+        }
+        let ctx = MontgomeryContext::try_new(modulus)?;
+        // Z/1 has no field structure, and modulus - 1 = 0 has no 2-adic valuation
+        if modulus < 3 {
+            return Err(ModularError::NoRootOfUnity);
+        }
+        let two_adicity = (modulus - 1).trailing_zeros();
+        let log_size = size.trailing_zeros();
+        if log_size > two_adicity {
+            return Err(ModularError::NoRootOfUnity);
+        }
+
+        // A generator of the 2-power subgroup, whose order is 2^two_adicity
+        let generator = two_adic_generator(modulus, two_adicity)?;
+        let root_of_order =
+            |log_order: u32| pow_mod(generator, 1 << (two_adicity - log_order), modulus);
+
+        let omega = root_of_order(log_size);
+        let omega_inverse = pow_mod(omega, size as u64 - 1, modulus);
+        let to_montgomery = |x: u64| u64::montgomery_product(&ctx, &x, &ctx.r_squared());
+        let powers = |base: u64, count: usize, scale: u64| {
+            std::iter::successors(Some(scale), move |&x| Some(u64::mul_mod(x, base, modulus)))
+                .take(count)
+                .map(to_montgomery)
+                .collect::<Vec<_>>()
+        };
+
+        // Fermat's n^(p-2) is only the inverse for a prime modulus
+        let size_inverse = ModularInt::new(size as u64, modulus)
+            .checked_inverse()?
+            .value();
+        // Convolutions multiply the transforms with a Montgomery product, which leaves
+        // a factor R⁻¹ that their final scaling by n⁻¹R cancels
+        let size_inverse_r = u64::mul_mod(size_inverse, to_montgomery(1), modulus);
+        let (twists, inverse_twists, convolution_twists) = if log_size < two_adicity {
+            let psi = root_of_order(log_size + 1);
+            let psi_inverse = pow_mod(psi, 2 * size as u64 - 1, modulus);
+            (
+                Some(powers(psi, size, 1)),
+                Some(powers(psi_inverse, size, size_inverse)),
+                Some(powers(psi_inverse, size, size_inverse_r)),
+            )
+        } else {
+            (None, None, None)
+        };
+
+        Ok(Self {
+            size,
+            roots: powers(omega, size / 2, 1),
+            inverse_roots: powers(omega_inverse, size / 2, 1),
+            size_inverse: to_montgomery(size_inverse),
+            twists,
+            inverse_twists,
+            convolution_scale: to_montgomery(size_inverse_r),
+            convolution_twists,
+            ctx,
+        })
+    }
+
+    /// Returns the prime modulus.
+    #[must_use]
+    pub const fn modulus(&self) -> u64 {
+        self.ctx.modulus()
+    }
+
+    /// Returns the number of points n.
+    #[must_use]
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns true if the modulus has a 2n-th root of unity, which the negacyclic
+    /// transforms need.
+    #[must_use]
+    pub const fn supports_negacyclic(&self) -> bool {
+        self.twists.is_some()
+    }
+
+    /// Computes the cyclic transform in place: a[k] becomes Σ a[j]·ω^(jk).
+    ///
+    /// Input residues must be below the modulus; the output is in natural order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` does not have exactly n elements.
+    pub fn forward(&self, a: &mut [u64]) {
+        self.transform(a, &self.roots);
+    }
+
+    /// Inverts `forward` in place, including the division by n.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` does not have exactly n elements.
+    pub fn inverse(&self, a: &mut [u64]) {
+        self.transform(a, &self.inverse_roots);
+        self.scale(a, |_| self.size_inverse);
+    }
+
+    /// Computes the negacyclic transform in place, evaluating at the odd powers of a
+    /// 2n-th root of unity ψ, so that pointwise products correspond to products
+    /// modulo x^n + 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` does not have exactly n elements or if the plan does not support
+    /// negacyclic transforms.
+    pub fn forward_negacyclic(&self, a: &mut [u64]) {
+        let Some(twists) = &self.twists else {
+            panic!("{}", ModularError::NoRootOfUnity);
+        };
+        self.scale(a, |i| twists[i]);
+        self.transform(a, &self.roots);
+    }
+
+    /// Inverts `forward_negacyclic` in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` does not have exactly n elements or if the plan does not support
+    /// negacyclic transforms.
+    pub fn inverse_negacyclic(&self, a: &mut [u64]) {
+        let Some(inverse_twists) = &self.inverse_twists else {
+            panic!("{}", ModularError::NoRootOfUnity);
+        };
+        self.transform(a, &self.inverse_roots);
+        // The twists already include the factor n⁻¹
+        self.scale(a, |i| inverse_twists[i]);
+    }
+
+    /// Multiplies two polynomials of at most n coefficients modulo x^n - 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either operand has more than n coefficients.
+    #[must_use]
+    pub fn cyclic_convolution(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        self.convolution(a, b, Self::forward, |_| self.convolution_scale)
+    }
+
+    /// Multiplies two polynomials of at most n coefficients modulo x^n + 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either operand has more than n coefficients or if the plan does not
+    /// support negacyclic transforms.
+    #[must_use]
+    pub fn negacyclic_convolution(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let Some(convolution_twists) = &self.convolution_twists else {
+            panic!("{}", ModularError::NoRootOfUnity);
+        };
+        self.convolution(a, b, Self::forward_negacyclic, |i| convolution_twists[i])
+    }
+
+    /// Multiplies two transformed vectors point by point, writing into `a`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices do not both have exactly n elements.
+    pub fn pointwise_mul(&self, a: &mut [u64], b: &[u64]) {
+        assert!(
+            a.len() == self.size && b.len() == self.size,
+            "transform size mismatch"
+        );
+        let r_squared = self.ctx.r_squared();
+        for (x, &y) in a.iter_mut().zip(b) {
+            // xyR⁻¹, then R² lifts it back to xy
+            let product = u64::montgomery_product(&self.ctx, x, &y);
+            *x = u64::montgomery_product(&self.ctx, &product, &r_squared);
+        }
+    }
+
+    /// Transforms both operands, multiplies them point by point with a single
+    /// Montgomery product each, and inverts with `inverse_scale`, which must carry
+    /// the factor R that the products drop.
+    fn convolution(
+        &self,
+        a: &[u64],
+        b: &[u64],
+        forward: fn(&Self, &mut [u64]),
+        inverse_scale: impl Fn(usize) -> u64,
+    ) -> Vec<u64> {
+        assert!(
+            a.len() <= self.size && b.len() <= self.size,
+            "operand longer than the transform size"
+        );
+        let mut fa = vec![0; self.size];
+        let mut fb = vec![0; self.size];
+        fa[..a.len()].copy_from_slice(a);
+        fb[..b.len()].copy_from_slice(b);
+        forward(self, &mut fa);
+        forward(self, &mut fb);
+        for (x, y) in fa.iter_mut().zip(&fb) {
+            *x = u64::montgomery_product(&self.ctx, x, y);
+        }
+        self.transform(&mut fa, &self.inverse_roots);
+        self.scale(&mut fa, inverse_scale);
+        fa
+    }
+
+    /// Multiplies a[i] by the Montgomery-form factor for index i.
+    fn scale(&self, a: &mut [u64], factor: impl Fn(usize) -> u64) {
+        for (i, x) in a.iter_mut().enumerate() {
+            *x = u64::montgomery_product(&self.ctx, x, &factor(i));
+        }
+    }
+
+    /// Decimation-in-time transform: bit-reversal, then radix-4 stages with one
+    /// radix-2 stage first when log2(n) is odd.
+    fn transform(&self, a: &mut [u64], roots: &[u64]) {
+        assert_eq!(a.len(), self.size, "transform size mismatch");
+        let n = self.size;
+        let modulus = self.modulus();
+        bit_reverse_permute(a);
+
+        let mut half = if n.trailing_zeros() % 2 == 1 {
+            // Stage of length 2, whose only twiddle is 1
+            for pair in a.chunks_exact_mut(2) {
+                let (u, v) = (pair[0], pair[1]);
+                pair[0] = u64::add_mod(u, v, modulus);
+                pair[1] = u64::sub_mod(u, v, modulus);
+            }
+            2
+        } else {
+            1
+        };
+
+        // Each pass fuses the stages of length 2·half and 4·half
+        while half < n {
+            let stride = n / (4 * half); // ω_{4h} = ω^stride
+            for block in a.chunks_exact_mut(4 * half) {
+                for k in 0..half {
+                    let w1 = roots[2 * stride * k]; // ω_{2h}^k
+                    let w2 = roots[stride * k]; // ω_{4h}^k
+                    let w3 = roots[stride * (k + half)]; // ω_{4h}^(k+h)
+
+                    let x0 = block[k];
+                    let x1 = u64::montgomery_product(&self.ctx, &block[k + half], &w1);
+                    let x2 = block[k + 2 * half];
+                    let x3 = u64::montgomery_product(&self.ctx, &block[k + 3 * half], &w1);
+
+                    let (y0, y1) = (u64::add_mod(x0, x1, modulus), u64::sub_mod(x0, x1, modulus));
+                    let (y2, y3) = (u64::add_mod(x2, x3, modulus), u64::sub_mod(x2, x3, modulus));
+                    let y2 = u64::montgomery_product(&self.ctx, &y2, &w2);
+                    let y3 = u64::montgomery_product(&self.ctx, &y3, &w3);
+
+                    block[k] = u64::add_mod(y0, y2, modulus);
+                    block[k + 2 * half] = u64::sub_mod(y0, y2, modulus);
+                    block[k + half] = u64::add_mod(y1, y3, modulus);
+                    block[k + 3 * half] = u64::sub_mod(y1, y3, modulus);
+                }
+            }
+            half *= 4;
+        }
+    }
+}
+
+/// Permutes a power-of-two-length slice into bit-reversed index order, in place.
+///
+/// # Panics
+///
+/// Panics if the length is not a power of two.
+pub fn bit_reverse_permute<T>(a: &mut [T]) {
+    let n = a.len();
+    assert!(n.is_power_of_two(), "length must be a power of two");
+    if n < 2 {
+        return;
+    }
+    let shift = usize::BITS - n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> shift;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+}
+
+fn pow_mod(base: u64, exponent: u64, modulus: u64) -> u64 {
+    u64::pow_mod(base, &exponent, modulus, PowStrategy::Auto)
+}
+
+/// Finds an element of order exactly 2^two_adicity modulo the prime `modulus`.
+fn two_adic_generator(modulus: u64, two_adicity: u32) -> Result<u64, ModularError> {
+    let cofactor = (modulus - 1) >> two_adicity;
+    // c^cofactor has full 2-power order exactly when c is a quadratic non-residue,
+    // and half of all candidates are, so a short search suffices for a prime
+    (2..modulus)
+        .take(256)
+        .map(|c| pow_mod(c, cofactor, modulus))
+        .find(|&x| pow_mod(x, 1 << (two_adicity - 1), modulus) == modulus - 1)
+        .ok_or(ModularError::NoRootOfUnity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(clippy::cast_possible_truncation)] // j indexes a, so it fits a usize
+    fn naive_transform(a: &[u64], root: u64, modulus: u64) -> Vec<u64> {
+        let n = a.len() as u64;
+        (0..n)
+            .map(|k| {
+                (0..n).fold(0, |acc, j| {
+                    let w = pow_mod(root, j * k % n, modulus);
+                    u64::add_mod(acc, u64::mul_mod(a[j as usize], w, modulus), modulus)
+                })
+            })
+            .collect()
+    }
+
+    fn naive_negacyclic(a: &[u64], b: &[u64], modulus: u64) -> Vec<u64> {
+        let n = a.len();
+        let mut result = vec![0; n];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                let product = u64::mul_mod(x, y, modulus);
+                let k = (i + j) % n;
+                result[k] = if i + j < n {
+                    u64::add_mod(result[k], product, modulus)
+                } else {
+                    u64::sub_mod(result[k], product, modulus)
+                };
+            }
+        }
+        result
+    }
+
+    fn sample(n: usize, modulus: u64, seed: u64) -> Vec<u64> {
+        let mut state = seed;
+        (0..n)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(0x5851_F42D_4C95_7F2D)
+                    .wrapping_add(0x1405_7B7E_F767_814F);
+                state % modulus
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ntt_matches_naive_dft() {
+        for modulus in [P998244353, GOLDILOCKS] {
+            for log_n in 0..=6u32 {
+                let n = 1 << log_n;
+                let plan = NttPlan::new(modulus, n);
+                let a = sample(n, modulus, u64::from(log_n));
+
+                // An element of order exactly n
+                let two_adicity = (modulus - 1).trailing_zeros();
+                let generator = two_adic_generator(modulus, two_adicity).unwrap();
+                let omega = pow_mod(generator, 1 << (two_adicity - log_n), modulus);
+
+                let mut transformed = a.clone();
+                plan.forward(&mut transformed);
+                assert_eq!(transformed, naive_transform(&a, omega, modulus), "n = {n}");
+
+                plan.inverse(&mut transformed);
+                assert_eq!(transformed, a);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ntt_convolutions() {
+        for modulus in [P998244353, GOLDILOCKS] {
+            let plan = NttPlan::new(modulus, 32);
+            let a = sample(32, modulus, 1);
+            let b = sample(32, modulus, 2);
+            assert_eq!(
+                plan.negacyclic_convolution(&a, &b),
+                naive_negacyclic(&a, &b, modulus)
+            );
+
+            let mut transformed = a.clone();
+            plan.forward_negacyclic(&mut transformed);
+            plan.inverse_negacyclic(&mut transformed);
+            assert_eq!(transformed, a);
+
+            // The public pointwise product agrees with the folded convolution path
+            let (mut fa, mut fb) = (a.clone(), b.clone());
+            plan.forward(&mut fa);
+            plan.forward(&mut fb);
+            plan.pointwise_mul(&mut fa, &fb);
+            plan.inverse(&mut fa);
+            assert_eq!(fa, plan.cyclic_convolution(&a, &b));
+        }
+
+        // 257 · 7681 is composite, but -1 has a square root of order 256 modulo both
+        // primes, so the transforms still invert each other
+        let composite = 257 * 7681;
+        let plan = NttPlan::new(composite, 128);
+        let (a, b) = (sample(128, composite, 3), sample(128, composite, 4));
+        let mut transformed = a.clone();
+        plan.forward(&mut transformed);
+        plan.inverse(&mut transformed);
+        assert_eq!(transformed, a);
+        assert_eq!(
+            plan.negacyclic_convolution(&a, &b),
+            naive_negacyclic(&a, &b, composite)
+        );
+
+        // Products of degree below n do not wrap around
+        let plan = NttPlan::new(P998244353, 8);
+        let product = plan.cyclic_convolution(&[1, 1, 1, 1], &[1, P998244353 - 1]);
+        assert_eq!(product, vec![1, 0, 0, 0, P998244353 - 1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_ntt_plan_errors() {
+        assert_eq!(
+            NttPlan::try_new(P998244353, 12).err(),
+            Some(ModularError::InvalidTransformSize)
+        );
+        assert_eq!(
+            NttPlan::try_new(P998244353, 1 << 24).err(),
+            Some(ModularError::NoRootOfUnity)
+        );
+        assert_eq!(
+            NttPlan::try_new(1 << 20, 4).err(),
+            Some(ModularError::EvenModulus)
+        );
+        for size in [1, 2] {
+            assert_eq!(
+                NttPlan::try_new(1, size).err(),
+                Some(ModularError::NoRootOfUnity)
+            );
+        }
+        // 17 = 2^4 + 1 has roots of unity of order 16 but not 32
+        assert!(!NttPlan::new(17, 16).supports_negacyclic());
+        assert!(NttPlan::new(17, 8).supports_negacyclic());
+    }
+
+    #[test]
+    fn test_bit_reverse_permute() {
+        let mut a: Vec<usize> = (0..8).collect();
+        bit_reverse_permute(&mut a);
+        assert_eq!(a, vec![0, 4, 2, 6, 1, 5, 3, 7]);
+        bit_reverse_permute(&mut a);
+        assert_eq!(a, (0..8).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,621 @@
+// Polynomials with modular integer coefficients
+
+use crate::ntt::NttPlan;
+use crate::prime::is_prime;
+use crate::{pow_with, ConstModInt, Exponent, ModularError, PowDomain, PowStrategy};
+use std::collections::HashMap;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+
+/// Operand length below which multiplication uses the schoolbook method.
+const KARATSUBA_THRESHOLD: usize = 32;
+
+/// Operand length from which multiplication uses the NTT, when `M` is a prime that
+/// allows it.
+const NTT_THRESHOLD: usize = 64;
+
+/// A polynomial over Z_M, with coefficients stored lowest degree first.
+///
+/// The representation is normalized (no trailing zero coefficients), so the zero
+/// polynomial has no coefficients and equality is structural. Multiplication picks
+/// schoolbook, Karatsuba or an NTT by operand size; the NTT path needs a prime
+/// modulus for which an `NttPlan` exists, such as 998244353. Division, GCD and modular
+/// composition need the relevant leading coefficients to be invertible, which always
+/// holds when `M` is prime.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Poly<const M: u64> {
+    coeffs: Vec<ConstModInt<M>>,
+}
+
+impl<const M: u64> Poly<M> {
+    /// Creates a polynomial from coefficients, lowest degree first.
+    #[must_use]
+    pub fn new(coeffs: Vec<ConstModInt<M>>) -> Self {
+        let mut poly = Self { coeffs };
+        poly.normalize();
+        poly
+    }
+
+    /// Creates a polynomial from integer coefficients, reducing each modulo `M`.
+    #[must_use]
+    pub fn from_coeffs(coeffs: &[u64]) -> Self {
+        Self::new(coeffs.iter().map(|&c| ConstModInt::new(c)).collect())
+    }
+
+    /// Returns the zero polynomial.
+    #[must_use]
+    pub const fn zero() -> Self {
+        Self { coeffs: Vec::new() }
+    }
+
+    /// Returns the constant polynomial 1.
+    #[must_use]
+    pub fn one() -> Self {
+        Self::new(vec![ConstModInt::ONE])
+    }
+
+    /// Returns coefficient · x^degree.
+    #[must_use]
+    pub fn monomial(coefficient: ConstModInt<M>, degree: usize) -> Self {
+        let mut coeffs = vec![ConstModInt::ZERO; degree + 1];
+        coeffs[degree] = coefficient;
+        Self::new(coeffs)
+    }
+
+    /// Returns the coefficients, lowest degree first, without trailing zeros.
+    #[must_use]
+    pub fn coeffs(&self) -> &[ConstModInt<M>] {
+        &self.coeffs
+    }
+
+    /// Returns the coefficient of x^index, which is zero beyond the degree.
+    #[must_use]
+    pub fn coeff(&self, index: usize) -> ConstModInt<M> {
+        self.coeffs.get(index).copied().unwrap_or(ConstModInt::ZERO)
+    }
+
+    /// Returns the degree, or `None` for the zero polynomial.
+    #[must_use]
+    pub const fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    /// Returns true if this is the zero polynomial.
+    #[must_use]
+    pub const fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// Returns the coefficient of the highest power, or `None` for the zero polynomial.
+    #[must_use]
+    pub fn leading_coefficient(&self) -> Option<ConstModInt<M>> {
+        self.coeffs.last().copied()
+    }
+
+    /// Evaluates the polynomial at x with Horner's rule.
+    #[must_use]
+    pub fn evaluate(&self, x: ConstModInt<M>) -> ConstModInt<M> {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(ConstModInt::ZERO, |acc, &c| acc * x + c)
+    }
+
+    /// Returns the formal derivative.
+    #[must_use]
+    pub fn derivative(&self) -> Self {
+        Self::new(
+            self.coeffs
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, &c)| c * ConstModInt::new(i as u64))
+                .collect(),
+        )
+    }
+
+    /// Multiplies with the schoolbook method.
+    #[must_use]
+    pub fn mul_schoolbook(&self, other: &Self) -> Self {
+        Self::new(mul_schoolbook(&self.coeffs, &other.coeffs))
+    }
+
+    /// Multiplies with Karatsuba's method.
+    #[must_use]
+    pub fn mul_karatsuba(&self, other: &Self) -> Self {
+        Self::new(mul_karatsuba(&self.coeffs, &other.coeffs))
+    }
+
+    /// Multiplies through a number-theoretic transform.
+    ///
+    /// Plans are cached per modulus and size, so only the first product of a given
+    /// size pays for the twiddle tables.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `NttPlan` construction error if `M` has no root of unity of a
+    /// power-of-two order at least the product length.
+    pub fn checked_mul_ntt(&self, other: &Self) -> Result<Self, ModularError> {
+        if self.is_zero() || other.is_zero() {
+            return Ok(Self::zero());
+        }
+        let len = self.coeffs.len() + other.coeffs.len() - 1;
+        let plan = cached_plan(M, len.next_power_of_two())?;
+        Ok(Self::new(mul_ntt(&plan, &self.coeffs, &other.coeffs)))
+    }
+
+    /// Divides by a non-zero polynomial, returning (quotient, remainder) with
+    /// deg(remainder) < deg(divisor).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the divisor is zero or its leading coefficient is not invertible.
+    #[must_use]
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        match self.checked_div_rem(divisor) {
+            Ok(result) => result,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Divides by a polynomial, returning an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::DivisionByZero` if the divisor is zero and
+    /// `ModularError::NotInvertible` if its leading coefficient is not invertible.
+    pub fn checked_div_rem(&self, divisor: &Self) -> Result<(Self, Self), ModularError> {
+        let Some(leading) = divisor.leading_coefficient() else {
+            return Err(ModularError::DivisionByZero);
+        };
+        let leading_inverse = leading.checked_inverse()?;
+        let divisor_len = divisor.coeffs.len();
+        if self.coeffs.len() < divisor_len {
+            return Ok((Self::zero(), self.clone()));
+        }
+
+        let mut remainder = self.coeffs.clone();
+        let mut quotient = vec![ConstModInt::ZERO; remainder.len() - divisor_len + 1];
+        for i in (0..quotient.len()).rev() {
+            let q = remainder[i + divisor_len - 1] * leading_inverse;
+            quotient[i] = q;
+            for (r, &d) in remainder[i..].iter_mut().zip(&divisor.coeffs) {
+                *r -= q * d;
+            }
+        }
+        remainder.truncate(divisor_len - 1);
+        Ok((Self::new(quotient), Self::new(remainder)))
+    }
+
+    /// Returns the polynomial scaled so its leading coefficient is 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the leading coefficient is not invertible.
+    #[must_use]
+    pub fn monic(&self) -> Self {
+        self.leading_coefficient()
+            .map_or_else(Self::zero, |leading| {
+                let inverse = leading.inverse_mod();
+                Self::new(self.coeffs.iter().map(|&c| c * inverse).collect())
+            })
+    }
+
+    /// Returns the monic greatest common divisor, or zero if both are zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Euclidean algorithm meets a non-invertible leading coefficient,
+    /// which cannot happen when `M` is prime.
+    #[must_use]
+    pub fn gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let remainder = a.div_rem(&b).1;
+            a = b;
+            b = remainder;
+        }
+        a.monic()
+    }
+
+    /// Computes self(inner) mod modulus, the modular composition f(g) mod h.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the modulus is zero or its leading coefficient is not invertible.
+    #[must_use]
+    pub fn compose_mod(&self, inner: &Self, modulus: &Self) -> Self {
+        let inner = inner % modulus;
+        // Horner's rule with a reduction after every step
+        self.coeffs.iter().rev().fold(Self::zero(), |acc, &c| {
+            &(&(&acc * &inner) + &Self::new(vec![c])) % modulus
+        })
+    }
+
+    /// Computes self^exponent mod modulus.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the modulus is zero or its leading coefficient is not invertible.
+    #[must_use]
+    pub fn pow_mod<E: Exponent>(&self, exponent: E, modulus: &Self) -> Self {
+        let domain = PolyModulus { modulus };
+        pow_with(&domain, &(self % modulus), &exponent, PowStrategy::Auto)
+    }
+
+    /// Drops trailing zero coefficients.
+    fn normalize(&mut self) {
+        while self.coeffs.last() == Some(&ConstModInt::ZERO) {
+            self.coeffs.pop();
+        }
+    }
+}
+
+/// Polynomials reduced modulo a fixed polynomial, for the exponentiation engine.
+struct PolyModulus<'a, const M: u64> {
+    modulus: &'a Poly<M>,
+}
+
+impl<const M: u64> PowDomain for PolyModulus<'_, M> {
+    type Element = Poly<M>;
+
+    fn one(&self) -> Poly<M> {
+        &Poly::one() % self.modulus
+    }
+
+    fn mul(&self, a: &Poly<M>, b: &Poly<M>) -> Poly<M> {
+        &(a * b) % self.modulus
+    }
+}
+
+/// Multiplies coefficient slices, choosing the algorithm by size and modulus.
+fn mul_auto<const M: u64>(a: &[ConstModInt<M>], b: &[ConstModInt<M>]) -> Vec<ConstModInt<M>> {
+    let shorter = a.len().min(b.len());
+    if shorter < KARATSUBA_THRESHOLD {
+        return mul_schoolbook(a, b);
+    }
+    // Only plans over a prime modulus are trusted here; composite ones fall back
+    if shorter >= NTT_THRESHOLD && cached_is_prime(M) {
+        if let Ok(plan) = cached_plan(M, (a.len() + b.len() - 1).next_power_of_two()) {
+            return mul_ntt(&plan, a, b);
+        }
+    }
+    mul_karatsuba(a, b)
+}
+
+type PlanCache = HashMap<(u64, usize), Arc<OnceLock<Result<Arc<NttPlan>, ModularError>>>>;
+
+/// Returns the transform plan for `size` points, computed on first use and shared
+/// across threads.
+///
+/// The cache lock only covers the lookup of the key's cell; the twiddle tables are
+/// built outside it, so other threads are not held up by a plan they do not need.
+fn cached_plan(modulus: u64, size: usize) -> Result<Arc<NttPlan>, ModularError> {
+    static PLANS: OnceLock<Mutex<PlanCache>> = OnceLock::new();
+    let cell = PLANS
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .entry((modulus, size))
+        .or_default()
+        .clone();
+    cell.get_or_init(|| NttPlan::try_new(modulus, size).map(Arc::new))
+        .clone()
+}
+
+/// Returns whether the modulus is prime, tested once per modulus in the same way as
+/// `cached_plan`.
+fn cached_is_prime(modulus: u64) -> bool {
+    static PRIMES: OnceLock<Mutex<HashMap<u64, Arc<OnceLock<bool>>>>> = OnceLock::new();
+    let cell = PRIMES
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .entry(modulus)
+        .or_default()
+        .clone();
+    *cell.get_or_init(|| is_prime(modulus))
+}
+
+/// Multiplies non-empty coefficient slices with a plan of at least the product length.
+fn mul_ntt<const M: u64>(
+    plan: &NttPlan,
+    a: &[ConstModInt<M>],
+    b: &[ConstModInt<M>],
+) -> Vec<ConstModInt<M>> {
+    let len = a.len() + b.len() - 1;
+    let values = |p: &[ConstModInt<M>]| p.iter().map(|c| c.value()).collect::<Vec<_>>();
+    let product = plan.cyclic_convolution(&values(a), &values(b));
+    product[..len]
+        .iter()
+        .map(|&c| ConstModInt::new(c))
+        .collect()
+}
+
+fn mul_schoolbook<const M: u64>(a: &[ConstModInt<M>], b: &[ConstModInt<M>]) -> Vec<ConstModInt<M>> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut product = vec![ConstModInt::ZERO; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (p, &y) in product[i..].iter_mut().zip(b) {
+            *p += x * y;
+        }
+    }
+    product
+}
+
+/// Karatsuba on operands padded to a common length n: with a = a0 + x^h·a1,
+/// a·b = a0·b0 + x^h·((a0 + a1)(b0 + b1) - a0·b0 - a1·b1) + x^2h·a1·b1.
+fn mul_karatsuba<const M: u64>(a: &[ConstModInt<M>], b: &[ConstModInt<M>]) -> Vec<ConstModInt<M>> {
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        return mul_schoolbook(a, b);
+    }
+    let n = a.len().max(b.len());
+    let pad = |p: &[ConstModInt<M>]| {
+        let mut padded = p.to_vec();
+        padded.resize(n, ConstModInt::ZERO);
+        padded
+    };
+    let (a_padded, b_padded) = (pad(a), pad(b));
+    let half = n / 2;
+    let (a0, a1) = a_padded.split_at(half);
+    let (b0, b1) = b_padded.split_at(half);
+
+    let z0 = mul_karatsuba(a0, b0);
+    let z2 = mul_karatsuba(a1, b1);
+    let sum = |low: &[ConstModInt<M>], high: &[ConstModInt<M>]| {
+        let mut sum = high.to_vec();
+        for (s, &l) in sum.iter_mut().zip(low) {
+            *s += l;
+        }
+        sum
+    };
+    let mut z1 = mul_karatsuba(&sum(a0, a1), &sum(b0, b1));
+    for (m, &z) in z1.iter_mut().zip(&z0) {
+        *m -= z;
+    }
+    for (m, &z) in z1.iter_mut().zip(&z2) {
+        *m -= z;
+    }
+
+    let mut product = vec![ConstModInt::ZERO; a.len() + b.len() - 1];
+    for (offset, part) in [(0, &z0), (half, &z1), (2 * half, &z2)] {
+        // The padding contributes only zeros beyond the true product length
+        for (p, &z) in product[offset..].iter_mut().zip(part) {
+            *p += z;
+        }
+    }
+    product
+}
+
+impl<const M: u64> From<Vec<ConstModInt<M>>> for Poly<M> {
+    fn from(coeffs: Vec<ConstModInt<M>>) -> Self {
+        Self::new(coeffs)
+    }
+}
+
+// Implement Add trait for references to Poly
+impl<const M: u64> Add for &Poly<M> {
+    type Output = Poly<M>;
+
+    fn add(self, other: Self) -> Self::Output {
+        let (mut sum, shorter) = if self.coeffs.len() >= other.coeffs.len() {
+            (self.coeffs.clone(), &other.coeffs)
+        } else {
+            (other.coeffs.clone(), &self.coeffs)
+        };
+        for (s, &c) in sum.iter_mut().zip(shorter) {
+            *s += c;
+        }
+        Poly::new(sum)
+    }
+}
+
+// Implement Sub trait for references to Poly
+impl<const M: u64> Sub for &Poly<M> {
+    type Output = Poly<M>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self + &-other
+    }
+}
+
+// Implement Mul trait for references to Poly
+impl<const M: u64> Mul for &Poly<M> {
+    type Output = Poly<M>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        Poly::new(mul_auto(&self.coeffs, &other.coeffs))
+    }
+}
+
+// Implement Div trait for references to Poly, giving the quotient
+impl<const M: u64> Div for &Poly<M> {
+    type Output = Poly<M>;
+
+    fn div(self, other: Self) -> Self::Output {
+        self.div_rem(other).0
+    }
+}
+
+// Implement Rem trait for references to Poly
+impl<const M: u64> Rem for &Poly<M> {
+    type Output = Poly<M>;
+
+    fn rem(self, other: Self) -> Self::Output {
+        self.div_rem(other).1
+    }
+}
+
+// Implement Neg trait for references to Poly
+impl<const M: u64> Neg for &Poly<M> {
+    type Output = Poly<M>;
+
+    fn neg(self) -> Self::Output {
+        Poly {
+            coeffs: self.coeffs.iter().map(|&c| -c).collect(),
+        }
+    }
+}
+
+/// Implements the owned binary operators and their assigning forms by delegating to
+/// the reference implementations.
+macro_rules! impl_poly_owned_ops {
+    ($(($trait:ident, $method:ident $(, $assign_trait:ident, $assign_method:ident)?)),* $(,)?) => {
+        $(
+            impl<const M: u64> $trait for Poly<M> {
+                type Output = Self;
+
+                fn $method(self, other: Self) -> Self::Output {
+                    (&self).$method(&other)
+                }
+            }
+
+            $(
+                impl<const M: u64> $assign_trait for Poly<M> {
+                    fn $assign_method(&mut self, other: Self) {
+                        *self = (&*self).$method(&other);
+                    }
+                }
+            )?
+        )*
+    };
+}
+
+impl_poly_owned_ops!(
+    (Add, add, AddAssign, add_assign),
+    (Sub, sub, SubAssign, sub_assign),
+    (Mul, mul, MulAssign, mul_assign),
+    (Div, div),
+    (Rem, rem),
+);
+
+// Implement Neg trait for Poly
+impl<const M: u64> Neg for Poly<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        -&self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ntt::P998244353;
+
+    type P = Poly<P998244353>;
+
+    fn sample<const M: u64>(len: usize, seed: u64) -> Poly<M> {
+        let mut state = seed;
+        Poly::new(
+            (0..len)
+                .map(|_| {
+                    state = state
+                        .wrapping_mul(0x5851_F42D_4C95_7F2D)
+                        .wrapping_add(0x1405_7B7E_F767_814F);
+                    ConstModInt::new(state >> 1)
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_poly_basics() {
+        let f = Poly::<17>::from_coeffs(&[1, 2, 3, 0, 0]);
+        assert_eq!(f.degree(), Some(2));
+        assert_eq!(f.coeff(7), ConstModInt::ZERO);
+        assert_eq!(Poly::<17>::from_coeffs(&[0, 17]).degree(), None);
+
+        // f(2) = 1 + 4 + 12 = 17 = 0 mod 17, f' = 2 + 6x
+        assert_eq!(f.evaluate(ConstModInt::new(2)), ConstModInt::ZERO);
+        assert_eq!(f.derivative(), Poly::from_coeffs(&[2, 6]));
+
+        let g = Poly::<17>::from_coeffs(&[16, 1]); // x - 1
+        assert_eq!(&f + &g, Poly::from_coeffs(&[0, 3, 3]));
+        assert_eq!(&f - &f, Poly::zero());
+        assert_eq!(&f * &g, Poly::from_coeffs(&[16, 16, 16, 3]));
+        assert_eq!(-g.clone() + g, Poly::zero());
+    }
+
+    #[test]
+    fn test_poly_multiplication_algorithms_agree() {
+        for (len_a, len_b) in [(40, 40), (100, 37), (130, 200), (257, 3)] {
+            let a: P = sample(len_a, len_a as u64);
+            let b: P = sample(len_b, len_b as u64 + 1);
+            let expected = a.mul_schoolbook(&b);
+            assert_eq!(a.mul_karatsuba(&b), expected);
+            assert_eq!(a.checked_mul_ntt(&b), Ok(expected.clone()));
+            assert_eq!(&a * &b, expected);
+        }
+
+        // 2^61 - 1 has no large power-of-two roots of unity, so large products fall
+        // back to Karatsuba
+        let a: Poly<0x1FFF_FFFF_FFFF_FFFF> = sample(100, 3);
+        assert_eq!(&a * &a, a.mul_schoolbook(&a));
+        assert_eq!(a.checked_mul_ntt(&a), Err(ModularError::NoRootOfUnity));
+
+        // 257 · 7681 has roots of unity of order 256 but is composite, so the
+        // automatic path takes Karatsuba
+        let a: Poly<1_974_017> = sample(64, 5);
+        let b: Poly<1_974_017> = sample(64, 6);
+        assert_eq!(&a * &b, a.mul_schoolbook(&b));
+    }
+
+    #[test]
+    #[allow(clippy::many_single_char_names)] // a = q·b + r
+    fn test_poly_division() {
+        let a: P = sample(50, 1);
+        let b: P = sample(20, 2);
+        let (q, r) = a.div_rem(&b);
+        assert!(r.degree() < b.degree());
+        assert_eq!(&(&q * &b) + &r, a);
+        assert_eq!(&b / &a, P::zero());
+
+        assert_eq!(
+            a.checked_div_rem(&P::zero()),
+            Err(ModularError::DivisionByZero)
+        );
+        // 2 is not invertible modulo 4
+        let f = Poly::<4>::from_coeffs(&[1, 1]);
+        let g = Poly::<4>::from_coeffs(&[1, 2]);
+        assert_eq!(f.checked_div_rem(&g), Err(ModularError::NotInvertible));
+    }
+
+    #[test]
+    fn test_poly_gcd() {
+        let common: P = sample(10, 3).monic();
+        let a = &common * &sample(15, 4);
+        let b = &common * &sample(12, 5);
+        // The cofactors are coprime with overwhelming probability
+        assert_eq!(a.gcd(&b), common);
+        assert_eq!(a.gcd(&P::zero()), a.monic());
+        assert_eq!(P::zero().gcd(&P::zero()), P::zero());
+    }
+
+    #[test]
+    fn test_poly_compose_and_pow_mod() {
+        let f: P = sample(12, 6);
+        let g: P = sample(9, 7);
+        let h: P = sample(8, 8);
+        let x = ConstModInt::new(12_345);
+
+        // Composition agrees with evaluating through the composite
+        let composed = f.compose_mod(&g, &P::monomial(ConstModInt::ONE, 200));
+        assert_eq!(composed.evaluate(x), f.evaluate(g.evaluate(x)));
+
+        // Reducing modulo (x - c) is evaluation at c
+        let linear = P::from_coeffs(&[P998244353 - 12_345, 1]);
+        assert_eq!(
+            f.compose_mod(&g, &linear),
+            P::new(vec![f.evaluate(g.evaluate(x))])
+        );
+
+        let mut expected = P::one();
+        for _ in 0..37 {
+            expected = &(&expected * &g) % &h;
+        }
+        assert_eq!(g.pow_mod(37u64, &h), expected);
+    }
+}
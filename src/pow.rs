@@ -0,0 +1,494 @@
+// Modular exponentiation engine shared by every backend
+
+use crate::{BarrettContext, BarrettWord, ModularStorage, MontgomeryContext, MontgomeryWord, Uint};
+
+/// Exponents shorter than this skip the Montgomery conversion in `ModularStorage::pow_mod`,
+/// since computing R² mod N costs more than the few multiplications it would speed up.
+pub const MONTGOMERY_MIN_EXPONENT_BITS: usize = 16;
+
+/// An exponent that the engine scans bit by bit.
+///
+/// Implemented for the primitive unsigned integers, for little-endian `u64` limb
+/// slices (least significant limb first), and for `BigUint` with the
+/// `arbitrary-precision` feature.
+pub trait Exponent {
+    /// Returns the number of significant bits, 0 for a zero exponent.
+    fn bits(&self) -> usize;
+
+    /// Returns the bit at `index`, counting from the least significant bit.
+    ///
+    /// Indices at or above `bits()` return `false`.
+    fn bit(&self, index: usize) -> bool;
+}
+
+/// Implements `Exponent` for primitive unsigned integers.
+macro_rules! impl_exponent_primitive {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl Exponent for $t {
+                fn bits(&self) -> usize {
+                    (<$t>::BITS - self.leading_zeros()) as usize
+                }
+
+                fn bit(&self, index: usize) -> bool {
+                    index < <$t>::BITS as usize && (self >> index) & 1 == 1
+                }
+            }
+        )*
+    };
+}
+
+impl_exponent_primitive!(u8, u16, u32, u64, u128, usize);
+
+impl Exponent for [u64] {
+    fn bits(&self) -> usize {
+        self.iter()
+            .rposition(|&limb| limb != 0)
+            .map_or(0, |top| top * 64 + self[top].bits())
+    }
+
+    fn bit(&self, index: usize) -> bool {
+        self.get(index / 64)
+            .is_some_and(|limb| (limb >> (index % 64)) & 1 == 1)
+    }
+}
+
+impl<const N: usize> Exponent for [u64; N] {
+    fn bits(&self) -> usize {
+        self.as_slice().bits()
+    }
+
+    fn bit(&self, index: usize) -> bool {
+        self.as_slice().bit(index)
+    }
+}
+
+impl Exponent for Vec<u64> {
+    fn bits(&self) -> usize {
+        self.as_slice().bits()
+    }
+
+    fn bit(&self, index: usize) -> bool {
+        self.as_slice().bit(index)
+    }
+}
+
+#[cfg(feature = "arbitrary-precision")]
+impl Exponent for num_bigint::BigUint {
+    #[allow(clippy::cast_possible_truncation)] // the bits are held in memory, so they fit a usize
+    fn bits(&self) -> usize {
+        Self::bits(self) as usize
+    }
+
+    fn bit(&self, index: usize) -> bool {
+        Self::bit(self, index as u64)
+    }
+}
+
+impl<E: Exponent + ?Sized> Exponent for &E {
+    fn bits(&self) -> usize {
+        (**self).bits()
+    }
+
+    fn bit(&self, index: usize) -> bool {
+        (**self).bit(index)
+    }
+}
+
+/// How the engine walks the exponent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PowStrategy {
+    /// Picks binary or sliding-window exponentiation from the exponent length.
+    #[default]
+    Auto,
+    /// Left-to-right square-and-multiply.
+    Binary,
+    /// Fixed windows of the given width (1 to 8 bits).
+    ///
+    /// Multiplies once per window, even by a zero digit, so the sequence of
+    /// operations depends only on the exponent length.
+    FixedWindow(u8),
+    /// Sliding windows of at most the given width (1 to 8 bits) over the odd powers
+    /// of the base, which needs the fewest multiplications.
+    SlidingWindow(u8),
+}
+
+/// A multiplicative monoid that the engine can exponentiate in.
+///
+/// Elements are whatever representation the domain multiplies natively, e.g. raw
+/// Montgomery-form words for `MontgomeryContext`.
+pub trait PowDomain {
+    /// The representation of an element.
+    type Element: Clone;
+
+    /// Returns the multiplicative identity.
+    fn one(&self) -> Self::Element;
+
+    /// Returns a * b.
+    fn mul(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+
+    /// Returns a * a.
+    fn square(&self, a: &Self::Element) -> Self::Element {
+        self.mul(a, a)
+    }
+}
+
+// Montgomery-form words, so the inner loop never leaves the Montgomery domain
+impl<T: MontgomeryWord + ModularStorage> PowDomain for MontgomeryContext<T> {
+    type Element = T;
+
+    fn one(&self) -> T {
+        T::montgomery_redc(self, &self.r_squared())
+    }
+
+    fn mul(&self, a: &T, b: &T) -> T {
+        T::montgomery_product(self, a, b)
+    }
+}
+
+// Fixed-size multi-limb residues in Montgomery form
+impl<const LIMBS: usize> PowDomain for MontgomeryContext<Uint<LIMBS>> {
+    type Element = Uint<LIMBS>;
+
+    fn one(&self) -> Uint<LIMBS> {
+        Uint::montgomery_redc(self, self.r_squared())
+    }
+
+    fn mul(&self, a: &Uint<LIMBS>, b: &Uint<LIMBS>) -> Uint<LIMBS> {
+        Uint::montgomery_product(self, a, b)
+    }
+}
+
+// Plain residues, multiplied with Barrett reduction
+impl<T: BarrettWord + ModularStorage> PowDomain for BarrettContext<T> {
+    type Element = T;
+
+    fn one(&self) -> T {
+        T::reduce(T::one(), self.modulus())
+    }
+
+    fn mul(&self, a: &T, b: &T) -> T {
+        T::barrett_product(self, a, b)
+    }
+}
+
+// Bignum residues, multiplied with Barrett reduction
+#[cfg(feature = "arbitrary-precision")]
+impl PowDomain for BarrettContext<num_bigint::BigUint> {
+    type Element = num_bigint::BigUint;
+
+    fn one(&self) -> Self::Element {
+        self.reduce(&num_traits::One::one())
+    }
+
+    fn mul(&self, a: &Self::Element, b: &Self::Element) -> Self::Element {
+        self.mul_mod(a, b)
+    }
+}
+
+/// Plain residues multiplied with `ModularStorage::mul_mod`.
+struct PlainDomain<T> {
+    modulus: T,
+}
+
+impl<T: ModularStorage> PowDomain for PlainDomain<T> {
+    type Element = T;
+
+    fn one(&self) -> T {
+        T::reduce(T::one(), self.modulus)
+    }
+
+    fn mul(&self, a: &T, b: &T) -> T {
+        T::mul_mod(*a, *b, self.modulus)
+    }
+}
+
+/// Computes base^exponent in the given domain.
+///
+/// # Panics
+///
+/// Panics if a window strategy is given a width outside 1 to 8.
+pub fn pow_with<D, E>(
+    domain: &D,
+    base: &D::Element,
+    exponent: &E,
+    strategy: PowStrategy,
+) -> D::Element
+where
+    D: PowDomain + ?Sized,
+    E: Exponent + ?Sized,
+{
+    match strategy {
+        PowStrategy::Auto => match window_width(exponent.bits()) {
+            1 => pow_binary(domain, base, exponent),
+            width => pow_sliding_window(domain, base, exponent, width),
+        },
+        PowStrategy::Binary => pow_binary(domain, base, exponent),
+        PowStrategy::FixedWindow(width) => {
+            pow_fixed_window(domain, base, exponent, checked_width(width))
+        }
+        PowStrategy::SlidingWindow(width) => {
+            pow_sliding_window(domain, base, exponent, checked_width(width))
+        }
+    }
+}
+
+/// Computes base^exponent mod modulus on plain residues.
+pub fn pow_plain<T, E>(base: T, exponent: &E, modulus: T, strategy: PowStrategy) -> T
+where
+    T: ModularStorage,
+    E: Exponent + ?Sized,
+{
+    let domain = PlainDomain { modulus };
+    pow_with(&domain, &T::reduce(base, modulus), exponent, strategy)
+}
+
+/// Computes base^exponent mod modulus, running the inner loop in Montgomery form.
+///
+/// Falls back to `pow_plain` for even moduli and short exponents.
+pub fn pow_montgomery<T, E>(base: T, exponent: &E, modulus: T, strategy: PowStrategy) -> T
+where
+    T: MontgomeryWord + ModularStorage,
+    E: Exponent + ?Sized,
+{
+    if exponent.bits() < MONTGOMERY_MIN_EXPONENT_BITS {
+        return pow_plain(base, exponent, modulus, strategy);
+    }
+    MontgomeryContext::try_new(modulus).map_or_else(
+        |_| pow_plain(base, exponent, modulus, strategy),
+        |ctx| {
+            let base = ctx.to_montgomery(base).montgomery_value();
+            let result = pow_with(&ctx, &base, exponent, strategy);
+            T::montgomery_redc(&ctx, &result)
+        },
+    )
+}
+
+/// Window width for sliding-window exponentiation, by exponent length.
+///
+/// Balances the 2^(w-1) precomputed odd powers against the multiplications saved.
+const fn window_width(bits: usize) -> u32 {
+    match bits {
+        0..=23 => 1,
+        24..=79 => 3,
+        80..=239 => 4,
+        240..=671 => 5,
+        _ => 6,
+    }
+}
+
+fn checked_width(width: u8) -> u32 {
+    assert!(
+        (1..=8).contains(&width),
+        "window width must be between 1 and 8"
+    );
+    u32::from(width)
+}
+
+/// Reads the `len` exponent bits starting at `low` as an integer.
+fn window_digit<E: Exponent + ?Sized>(exponent: &E, low: usize, len: usize) -> usize {
+    (low..low + len).rev().fold(0, |digit, index| {
+        (digit << 1) | usize::from(exponent.bit(index))
+    })
+}
+
+fn pow_binary<D, E>(domain: &D, base: &D::Element, exponent: &E) -> D::Element
+where
+    D: PowDomain + ?Sized,
+    E: Exponent + ?Sized,
+{
+    let bits = exponent.bits();
+    if bits == 0 {
+        return domain.one();
+    }
+
+    // The top bit is always set, so start from the base instead of squaring one
+    let mut result = base.clone();
+    for index in (0..bits - 1).rev() {
+        result = domain.square(&result);
+        if exponent.bit(index) {
+            result = domain.mul(&result, base);
+        }
+    }
+    result
+}
+
+fn pow_fixed_window<D, E>(domain: &D, base: &D::Element, exponent: &E, width: u32) -> D::Element
+where
+    D: PowDomain + ?Sized,
+    E: Exponent + ?Sized,
+{
+    let width = width as usize;
+
+    // table[i] = base^i for every digit
+    let mut table = Vec::with_capacity(1 << width);
+    table.push(domain.one());
+    for i in 1..1 << width {
+        table.push(domain.mul(&table[i - 1], base));
+    }
+
+    let windows = exponent.bits().div_ceil(width);
+    let mut result = domain.one();
+    for window in (0..windows).rev() {
+        for _ in 0..width {
+            result = domain.square(&result);
+        }
+        result = domain.mul(
+            &result,
+            &table[window_digit(exponent, window * width, width)],
+        );
+    }
+    result
+}
+
+fn pow_sliding_window<D, E>(domain: &D, base: &D::Element, exponent: &E, width: u32) -> D::Element
+where
+    D: PowDomain + ?Sized,
+    E: Exponent + ?Sized,
+{
+    let width = width as usize;
+
+    // table[i] = base^(2i + 1), the odd powers a window can end on
+    let base_squared = domain.square(base);
+    let mut table = Vec::with_capacity(1 << (width - 1));
+    table.push(base.clone());
+    for i in 1..1 << (width - 1) {
+        table.push(domain.mul(&table[i - 1], &base_squared));
+    }
+
+    // Bits [0, remaining) are still to be processed
+    let mut remaining = exponent.bits();
+    let mut result: Option<D::Element> = None;
+    while remaining > 0 {
+        if !exponent.bit(remaining - 1) {
+            result = result.map(|value| domain.square(&value));
+            remaining -= 1;
+            continue;
+        }
+
+        // Longest window of at most `width` bits that starts and ends on a set bit
+        let mut low = remaining.saturating_sub(width);
+        while !exponent.bit(low) {
+            low += 1;
+        }
+        let len = remaining - low;
+        let power = &table[window_digit(exponent, low, len) >> 1];
+
+        result = Some(result.map_or_else(
+            || power.clone(),
+            |mut value| {
+                for _ in 0..len {
+                    value = domain.square(&value);
+                }
+                domain.mul(&value, power)
+            },
+        ));
+        remaining = low;
+    }
+
+    result.unwrap_or_else(|| domain.one())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STRATEGIES: [PowStrategy; 7] = [
+        PowStrategy::Auto,
+        PowStrategy::Binary,
+        PowStrategy::FixedWindow(1),
+        PowStrategy::FixedWindow(4),
+        PowStrategy::SlidingWindow(1),
+        PowStrategy::SlidingWindow(5),
+        PowStrategy::SlidingWindow(8),
+    ];
+
+    /// Reference square-and-multiply with a u128 product.
+    #[allow(clippy::cast_possible_truncation)] // the result is below the modulus
+    fn naive_pow(base: u64, mut exponent: u64, modulus: u64) -> u64 {
+        let m = u128::from(modulus);
+        let (mut base, mut result) = (u128::from(base) % m, 1 % m);
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result * base % m;
+            }
+            base = base * base % m;
+            exponent >>= 1;
+        }
+        result as u64
+    }
+
+    #[test]
+    fn test_exponent_bits() {
+        assert_eq!(0u64.bits(), 0);
+        assert_eq!(5u8.bits(), 3);
+        assert!(!5u8.bit(200));
+        assert_eq!(u128::MAX.bits(), 128);
+
+        let limbs = [0, 1u64 << 3, 0];
+        assert_eq!(limbs.bits(), 68);
+        assert!(limbs.bit(67));
+        assert!(!limbs.bit(66));
+        assert!(!limbs.bit(1_000));
+        assert_eq!([0u64; 4].bits(), 0);
+    }
+
+    #[test]
+    fn test_strategies_agree() {
+        let modulus = 0xFFFF_FFFF_FFFF_FFFBu64; // 2^64 - 5
+        let base = 0xABCD_EF01_2345_6789u64;
+        for exponent in [0, 1, 2, 3, 0xFF, 0x8000_0000_0000_0001, u64::MAX] {
+            let expected = naive_pow(base, exponent, modulus);
+            for strategy in STRATEGIES {
+                assert_eq!(
+                    pow_plain(base, &exponent, modulus, strategy),
+                    expected,
+                    "{strategy:?}, exponent {exponent}"
+                );
+                assert_eq!(pow_montgomery(base, &exponent, modulus, strategy), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_limb_exponent() {
+        // Fermat: a^(p - 1) = 1, and a^(k(p - 1) + 1) = a for a multi-limb multiple
+        let p = 0xFFFF_FFFF_FFFF_FFC5u64; // 2^64 - 59
+        let base = 0x1234_5678_9ABC_DEF0u64;
+        // (p - 1) * 2^64 + 1, split into limbs
+        let exponent = [1, p - 1];
+        for strategy in STRATEGIES {
+            assert_eq!(pow_montgomery(base, &exponent[..], p, strategy), base);
+            assert_eq!(pow_plain(base, &exponent, p, strategy), base);
+        }
+    }
+
+    #[test]
+    fn test_even_and_unit_moduli() {
+        assert_eq!(
+            pow_montgomery(3u64, &100_000u32, 1 << 40, PowStrategy::Auto),
+            naive_pow(3, 100_000, 1 << 40)
+        );
+        assert_eq!(pow_plain(3u32, &0u8, 1, PowStrategy::Auto), 0);
+        assert_eq!(pow_montgomery(3u128, &u128::MAX, 1, PowStrategy::Auto), 0);
+    }
+
+    #[cfg(feature = "arbitrary-precision")]
+    #[test]
+    fn test_biguint_exponent() {
+        // p = 2^64 - 59 and the 192-bit exponent (p - 1)^3 + 1
+        let p = 0xFFFF_FFFF_FFFF_FFC5u64;
+        let exponent = num_bigint::BigUint::from(p - 1).pow(3) + 1u32;
+        assert_eq!(exponent.bits(), 192);
+        assert_eq!(
+            pow_montgomery(12_345u64, &exponent, p, PowStrategy::Auto),
+            12_345
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "window width must be between 1 and 8")]
+    fn test_invalid_window_width() {
+        let _ = pow_plain(3u64, &5u64, 7, PowStrategy::SlidingWindow(0));
+    }
+}
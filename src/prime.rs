@@ -0,0 +1,532 @@
+// Deterministic primality testing, prime search and sieving
+
+use crate::pow::{pow_with, Exponent, PowDomain, PowStrategy};
+#[cfg(feature = "arbitrary-precision")]
+use crate::BarrettContext;
+use crate::{ModularStorage, MontgomeryContext, MontgomeryWord};
+#[cfg(feature = "arbitrary-precision")]
+use num_bigint::BigUint;
+use num_integer::{Integer, Roots};
+
+/// The primes used for trial division before any modular exponentiation.
+const TRIAL_PRIMES: [u64; 15] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+
+/// Below 53², a number without a factor in `TRIAL_PRIMES` is prime.
+const TRIAL_LIMIT: u64 = 53 * 53;
+
+/// Miller–Rabin bases that are deterministic for every n < 2^32 (Jaeschke).
+const WITNESSES_32: [u64; 3] = [2, 7, 61];
+
+/// Miller–Rabin bases that are deterministic for every n < 2^64 (Sinclair).
+const WITNESSES_64: [u64; 7] = [2, 325, 9375, 28178, 450_775, 9_780_504, 1_795_265_022];
+
+/// Sieving primes stop here; larger ranges test the survivors with `is_prime`.
+const SIEVE_BASE_LIMIT: u64 = 1 << 16;
+
+/// Numbers sieved at once by `primes_in_range`.
+const SEGMENT_SIZE: u64 = 1 << 15;
+
+/// Arithmetic modulo an odd n > 2, in whatever representation the domain multiplies
+/// natively.
+trait OddDomain: PowDomain<Element: PartialEq> {
+    /// Embeds a small non-negative integer, reducing it modulo n.
+    fn embed(&self, value: u64) -> Self::Element;
+
+    fn add(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+
+    fn sub(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+
+    /// Returns a / 2, which exists because n is odd.
+    fn half(&self, a: &Self::Element) -> Self::Element;
+
+    /// Embeds a small signed integer.
+    fn embed_signed(&self, value: i64) -> Self::Element {
+        let magnitude = self.embed(value.unsigned_abs());
+        if value < 0 {
+            self.sub(&self.embed(0), &magnitude)
+        } else {
+            magnitude
+        }
+    }
+}
+
+// Montgomery-form words; halving commutes with the factor R
+impl<T> OddDomain for MontgomeryContext<T>
+where
+    T: MontgomeryWord + ModularStorage + From<u64>,
+{
+    fn embed(&self, value: u64) -> T {
+        self.to_montgomery(<T as From<u64>>::from(value))
+            .montgomery_value()
+    }
+
+    fn add(&self, a: &T, b: &T) -> T {
+        T::add_mod(*a, *b, self.modulus())
+    }
+
+    fn sub(&self, a: &T, b: &T) -> T {
+        T::sub_mod(*a, *b, self.modulus())
+    }
+
+    fn half(&self, a: &T) -> T {
+        // (a + n) / 2 for odd a, without forming a + n
+        if a.is_even() {
+            *a >> 1
+        } else {
+            (*a >> 1) + (self.modulus() >> 1) + T::one()
+        }
+    }
+}
+
+// Plain bignum residues with Barrett multiplication
+#[cfg(feature = "arbitrary-precision")]
+impl OddDomain for BarrettContext<BigUint> {
+    fn embed(&self, value: u64) -> BigUint {
+        self.reduce(&BigUint::from(value))
+    }
+
+    fn add(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        let sum = a + b;
+        if &sum >= self.modulus() {
+            sum - self.modulus()
+        } else {
+            sum
+        }
+    }
+
+    fn sub(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        if a >= b {
+            a - b
+        } else {
+            self.modulus() - b + a
+        }
+    }
+
+    fn half(&self, a: &BigUint) -> BigUint {
+        if a.is_even() {
+            a >> 1u8
+        } else {
+            (a + self.modulus()) >> 1u8
+        }
+    }
+}
+
+/// Returns true if n is prime.
+///
+/// Uses trial division by the primes below 53, then Miller–Rabin with a witness set
+/// that is proven deterministic below 2^64: three bases below 2^32 (which covers
+/// `u32` inputs) and seven above. Exponentiation runs in Montgomery form.
+///
+/// ```
+/// use modularity::prime::is_prime;
+///
+/// assert!(is_prime(998_244_353));
+/// assert!(!is_prime(3_215_031_751)); // a strong pseudoprime to bases 2, 3, 5 and 7
+/// ```
+#[must_use]
+pub fn is_prime(n: u64) -> bool {
+    if let Some(prime) = trial_division(n) {
+        return prime;
+    }
+    let ctx = MontgomeryContext::new(n);
+    let (d, s) = split_power_of_two(n - 1);
+    let witnesses: &[u64] = if n < 1 << 32 {
+        &WITNESSES_32
+    } else {
+        &WITNESSES_64
+    };
+    witnesses.iter().all(|&a| {
+        // A base divisible by n says nothing about n
+        a.is_multiple_of(n) || strong_probable_prime(&ctx, &ctx.embed(a), &d, s)
+    })
+}
+
+/// Returns true if n is prime.
+///
+/// Values below 2^64 go through `is_prime`. Larger values use the Baillie–PSW test:
+/// a base-2 strong probable prime test followed by a strong Lucas test with
+/// Selfridge's parameters. No composite passing both is known, and none exists
+/// below 2^64.
+#[must_use]
+pub fn is_prime_u128(n: u128) -> bool {
+    if let Ok(n) = u64::try_from(n) {
+        return is_prime(n);
+    }
+    if TRIAL_PRIMES
+        .iter()
+        .any(|&p| n.is_multiple_of(u128::from(p)))
+    {
+        return false;
+    }
+    baillie_psw(&MontgomeryContext::new(n), &n)
+}
+
+/// Returns true if n is prime, using the Baillie–PSW test above 2^128.
+///
+/// See `is_prime_u128`.
+#[cfg(feature = "arbitrary-precision")]
+#[must_use]
+pub fn is_prime_biguint(n: &BigUint) -> bool {
+    if let Ok(n) = u128::try_from(n) {
+        return is_prime_u128(n);
+    }
+    if TRIAL_PRIMES
+        .iter()
+        .any(|&p| n.is_multiple_of(&BigUint::from(p)))
+    {
+        return false;
+    }
+    baillie_psw(&BarrettContext::new(n.clone()), n)
+}
+
+/// Returns the smallest prime greater than n, or `None` if it exceeds `u64::MAX`.
+#[must_use]
+pub fn next_prime(n: u64) -> Option<u64> {
+    if n < 2 {
+        return Some(2);
+    }
+    let mut candidate = n.checked_add(1)? | 1;
+    while !is_prime(candidate) {
+        candidate = candidate.checked_add(2)?;
+    }
+    Some(candidate)
+}
+
+/// Returns the largest prime less than n, or `None` if n is at most 2.
+#[must_use]
+pub fn prev_prime(n: u64) -> Option<u64> {
+    if n <= 3 {
+        return (n == 3).then_some(2);
+    }
+    // The largest odd number below n; 3 is prime, so the search stops
+    let mut candidate = (n - 2) | 1;
+    while !is_prime(candidate) {
+        candidate -= 2;
+    }
+    Some(candidate)
+}
+
+/// Returns the primes in [low, high) in increasing order.
+///
+/// Sieves the range in segments with the primes below 2^16, which needs no further
+/// testing when high ≤ 2^32. Above that, the numbers that survive the sieve are
+/// checked with `is_prime`, so the cost grows with the length of the range rather
+/// than with √high.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)] // offsets stay below SEGMENT_SIZE, primes below SIEVE_BASE_LIMIT
+pub fn primes_in_range(low: u64, high: u64) -> Vec<u64> {
+    let mut primes = Vec::new();
+    let low = low.max(2);
+    if high <= low {
+        return primes;
+    }
+    let largest = high - 1;
+    let complete = largest.isqrt() < SIEVE_BASE_LIMIT;
+    let base = small_primes(largest.isqrt().min(SIEVE_BASE_LIMIT));
+
+    let mut start = low;
+    while start < high {
+        let end = start.saturating_add(SEGMENT_SIZE).min(high);
+        let mut composite = vec![false; (end - start) as usize];
+        for &p in &base {
+            // Multiples below p² have a smaller prime factor, and p itself is prime
+            let Some(first) = start.div_ceil(p).checked_mul(p) else {
+                continue;
+            };
+            for multiple in (first.max(p * p)..end).step_by(p as usize) {
+                composite[(multiple - start) as usize] = true;
+            }
+        }
+        primes.extend(
+            (start..end)
+                .zip(&composite)
+                .filter(|&(n, &composite)| !composite && (complete || is_prime(n)))
+                .map(|(n, _)| n),
+        );
+        start = end;
+    }
+    primes
+}
+
+/// The primes up to and including limit, by the sieve of Eratosthenes.
+#[allow(clippy::cast_possible_truncation)] // callers sieve only up to SIEVE_BASE_LIMIT
+pub(crate) fn small_primes(limit: u64) -> Vec<u64> {
+    let limit = limit as usize;
+    let mut composite = vec![false; limit + 1];
+    let mut primes = Vec::new();
+    for n in 2..=limit {
+        if !composite[n] {
+            primes.push(n as u64);
+            for multiple in (n * n..=limit).step_by(n) {
+                composite[multiple] = true;
+            }
+        }
+    }
+    primes
+}
+
+/// Settles small n and n with a small factor, or returns `None` to continue.
+fn trial_division(n: u64) -> Option<bool> {
+    if n < 2 {
+        return Some(false);
+    }
+    for &p in &TRIAL_PRIMES {
+        if n.is_multiple_of(p) {
+            return Some(n == p);
+        }
+    }
+    (n < TRIAL_LIMIT).then_some(true)
+}
+
+/// Writes an even m as d·2^s with d odd.
+fn split_power_of_two<T: Integer + Clone + From<u64>>(m: T) -> (T, u32) {
+    let two = T::from(2);
+    let (mut d, mut s) = (m, 0);
+    while d.is_even() && !d.is_zero() {
+        d = d / two.clone();
+        s += 1;
+    }
+    (d, s)
+}
+
+/// The Miller–Rabin test for one base, given n - 1 = d·2^s.
+fn strong_probable_prime<D, E>(domain: &D, base: &D::Element, d: &E, s: u32) -> bool
+where
+    D: OddDomain,
+    E: Exponent + ?Sized,
+{
+    let one = domain.one();
+    let minus_one = domain.sub(&domain.embed(0), &one);
+    let mut x = pow_with(domain, base, d, PowStrategy::Auto);
+    if x == one || x == minus_one {
+        return true;
+    }
+    for _ in 1..s {
+        x = domain.square(&x);
+        if x == minus_one {
+            return true;
+        }
+        if x == one {
+            return false;
+        }
+    }
+    false
+}
+
+/// The Baillie–PSW test for odd n without small factors.
+fn baillie_psw<D, T>(domain: &D, n: &T) -> bool
+where
+    D: OddDomain,
+    T: Integer + Clone + From<u64> + Roots + Exponent,
+{
+    let (d, s) = split_power_of_two(n.clone() - T::one());
+    if !strong_probable_prime(domain, &domain.embed(2), &d, s) {
+        return false;
+    }
+    // Selfridge's search never ends for a square, which has no D with (D/n) = -1
+    let root = n.sqrt();
+    if root.clone() * root == *n {
+        return false;
+    }
+    let Some(discriminant) = selfridge_discriminant(n) else {
+        return false;
+    };
+    let (d, s) = split_power_of_two(n.clone() + T::one());
+    strong_lucas_probable_prime(domain, discriminant, &d, s)
+}
+
+/// The first D in 5, -7, 9, -11, ... with Jacobi symbol (D/n) = -1, or `None` if
+/// some D shares a factor with n, which is then composite.
+fn selfridge_discriminant<T: Integer + Clone + From<u64>>(n: &T) -> Option<i64> {
+    let mut discriminant: i64 = 5;
+    loop {
+        let magnitude = T::from(discriminant.unsigned_abs());
+        let residue = if discriminant < 0 {
+            n.clone() - magnitude.mod_floor(n)
+        } else {
+            magnitude
+        };
+        match jacobi(&residue, n) {
+            -1 => return Some(discriminant),
+            0 => return None,
+            _ => discriminant = -(discriminant + 2 * discriminant.signum()),
+        }
+    }
+}
+
+/// The strong Lucas test with P = 1 and Q = (1 - D) / 4, given n + 1 = d·2^s.
+#[allow(clippy::many_single_char_names)] // the Lucas sequence names u, v, q, d, s
+fn strong_lucas_probable_prime<D, E>(domain: &D, discriminant: i64, d: &E, s: u32) -> bool
+where
+    D: OddDomain,
+    E: Exponent + ?Sized,
+{
+    let zero = domain.embed(0);
+    let big_d = domain.embed_signed(discriminant);
+    let q = domain.embed_signed((1 - discriminant) / 4);
+
+    // U_k, V_k and Q^k, starting from k = 0 and walking the bits of d
+    let (mut u, mut v, mut qk) = (zero.clone(), domain.embed(2), domain.one());
+    for i in (0..d.bits()).rev() {
+        // k -> 2k
+        u = domain.mul(&u, &v);
+        v = domain.sub(&domain.square(&v), &domain.add(&qk, &qk));
+        qk = domain.square(&qk);
+        if d.bit(i) {
+            // k -> k + 1, with P = 1
+            let next_u = domain.half(&domain.add(&u, &v));
+            v = domain.half(&domain.add(&domain.mul(&big_d, &u), &v));
+            u = next_u;
+            qk = domain.mul(&qk, &q);
+        }
+    }
+
+    if u == zero {
+        return true;
+    }
+    for _ in 0..s {
+        if v == zero {
+            return true;
+        }
+        v = domain.sub(&domain.square(&v), &domain.add(&qk, &qk));
+        qk = domain.square(&qk);
+    }
+    false
+}
+
+/// The Jacobi symbol (a/n) for odd n.
+pub(crate) fn jacobi<T: Integer + Clone + From<u64>>(a: &T, n: &T) -> i8 {
+    let (two, three, four, five, eight) =
+        (T::from(2), T::from(3), T::from(4), T::from(5), T::from(8));
+    let (mut a, mut n) = (a.mod_floor(n), n.clone());
+    let mut result = 1;
+    while !a.is_zero() {
+        while a.is_even() {
+            a = a / two.clone();
+            let r = n.mod_floor(&eight);
+            if r == three || r == five {
+                result = -result;
+            }
+        }
+        std::mem::swap(&mut a, &mut n);
+        if a.mod_floor(&four) == three && n.mod_floor(&four) == three {
+            result = -result;
+        }
+        a = a.mod_floor(&n);
+    }
+    if n.is_one() {
+        result
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_is_prime(n: u64) -> bool {
+        n >= 2 && (2..=n.isqrt()).all(|d| !n.is_multiple_of(d))
+    }
+
+    #[test]
+    fn test_small_values_match_trial_division() {
+        for n in 0..20_000 {
+            assert_eq!(is_prime(n), naive_is_prime(n), "n = {n}");
+        }
+        let sieved = primes_in_range(0, 20_000);
+        let expected: Vec<u64> = (0..20_000).filter(|&n| naive_is_prime(n)).collect();
+        assert_eq!(sieved, expected);
+    }
+
+    #[test]
+    fn test_u64_primes_and_pseudoprimes() {
+        let primes = [
+            998_244_353,
+            1_000_000_007,
+            (1 << 61) - 1,
+            u64::MAX - 58, // 2^64 - 59
+            0xFFFF_FFFF_0000_0001,
+            4_294_967_291, // the largest prime below 2^32
+        ];
+        assert!(primes.iter().all(|&p| is_prime(p)));
+
+        let composites = [
+            561,                           // a Carmichael number
+            3_215_031_751,                 // strong pseudoprime to bases 2, 3, 5, 7
+            3_825_123_056_546_413_051,     // strong pseudoprime to the first nine primes
+            u64::MAX - 4,                  // 2^64 - 5
+            4_294_967_291 * 4_294_967_279, // two primes just below 2^32
+            u64::MAX,
+        ];
+        assert!(composites.iter().all(|&n| !is_prime(n)));
+    }
+
+    #[test]
+    fn test_u128_baillie_psw() {
+        let primes = [
+            (1u128 << 127) - 1,
+            (1u128 << 89) - 1,
+            u128::MAX - 158, // 2^128 - 159
+            u128::from(u64::MAX - 58),
+        ];
+        assert!(primes.iter().all(|&p| is_prime_u128(p)));
+
+        let p = u128::from(u64::MAX - 58);
+        let composites = [
+            p * p,
+            u128::from(u64::MAX - 82) * u128::from((1u64 << 61) - 1),
+            ((1u128 << 89) - 1) * 3_215_031_751,
+            u128::MAX,
+        ];
+        assert!(composites.iter().all(|&n| !is_prime_u128(n)));
+    }
+
+    #[test]
+    fn test_jacobi_symbol() {
+        // (2/n) depends on n mod 8, and (a/n) is 0 when they share a factor
+        assert_eq!(jacobi(&2u64, &7), 1);
+        assert_eq!(jacobi(&2u64, &11), -1);
+        assert_eq!(jacobi(&6u64, &9), 0);
+        assert_eq!(jacobi(&1001u64, &9907), -1);
+        assert_eq!(jacobi(&19u64, &45), 1);
+    }
+
+    #[test]
+    fn test_next_and_prev_prime() {
+        assert_eq!(next_prime(0), Some(2));
+        assert_eq!(next_prime(2), Some(3));
+        assert_eq!(next_prime(13), Some(17));
+        assert_eq!(next_prime(u64::MAX - 60), Some(u64::MAX - 58));
+        assert_eq!(next_prime(u64::MAX - 58), None);
+
+        assert_eq!(prev_prime(2), None);
+        assert_eq!(prev_prime(3), Some(2));
+        assert_eq!(prev_prime(4), Some(3));
+        assert_eq!(prev_prime(1 << 32), Some(4_294_967_291));
+        assert_eq!(prev_prime(u64::MAX), Some(u64::MAX - 58));
+    }
+
+    #[test]
+    fn test_segmented_sieve_large_ranges() {
+        for low in [1_000_000_000_000u64, (1 << 32) - 500, u64::MAX - 1000] {
+            let high = low.saturating_add(70_000);
+            let expected: Vec<u64> = (low..high).filter(|&n| is_prime(n)).collect();
+            assert_eq!(primes_in_range(low, high), expected);
+        }
+        assert!(primes_in_range(10, 10).is_empty());
+        assert_eq!(primes_in_range(0, 3), vec![2]);
+    }
+
+    #[cfg(feature = "arbitrary-precision")]
+    #[test]
+    fn test_biguint_baillie_psw() {
+        let mersenne = |e: u32| (BigUint::from(1u8) << e) - 1u8;
+        assert!(is_prime_biguint(&mersenne(521)));
+        assert!(is_prime_biguint(&mersenne(607)));
+        assert!(!is_prime_biguint(&mersenne(523)));
+        assert!(!is_prime_biguint(&(mersenne(127) * mersenne(89))));
+        assert!(!is_prime_biguint(&(mersenne(521) * mersenne(521))));
+        assert!(is_prime_biguint(&BigUint::from(998_244_353u32)));
+    }
+}
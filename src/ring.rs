@@ -0,0 +1,653 @@
+// Elements of the quotient ring Z_q[x]/(x^n + 1)
+
+use crate::ntt::NttPlan;
+use crate::{BarrettContext, ModularError, ModularStorage, MontgomeryContext, MontgomeryWord};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Precomputed data for arithmetic in Z_q[x]/(x^N + 1).
+///
+/// When q ≡ 1 (mod 2N), as for Dilithium's q = 8380417, the NTT splits x^N + 1
+/// into linear factors and multiplication is pointwise. When only q ≡ 1 (mod N), as
+/// for Kyber's q = 3329, the NTT stops one level early: it splits x^N + 1 into the
+/// quadratics x² - ζ_k and multiplies slot by slot in Z_q[x]/(x² - ζ_k). Reductions
+/// go through the crate's `MontgomeryContext` and `BarrettContext`.
+#[derive(Debug, Clone)]
+pub struct RingContext<const N: usize> {
+    plan: NttPlan,
+    montgomery: MontgomeryContext<u64>,
+    barrett: BarrettContext<u64>,
+    points: Option<Vec<u64>>, // ζ_k in Montgomery form for the degree-2 slots
+}
+
+/// Which representation a `RingElement` currently holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Domain {
+    /// Coefficients of the polynomial, lowest degree first.
+    Coefficient,
+    /// NTT slots in Montgomery form, where multiplication is slot-wise.
+    Ntt,
+}
+
+/// An element of Z_q[x]/(x^N + 1), bound to the context that created it.
+///
+/// Elements remember whether they hold coefficients or NTT slots. Multiplication
+/// moves both operands into the NTT domain and returns an NTT-domain result;
+/// addition and subtraction work in either domain, converting the right-hand side to
+/// the domain of the left. Use `to_coefficients` to get the polynomial back.
+#[derive(Debug, Clone, Copy)]
+pub struct RingElement<'ctx, const N: usize> {
+    values: [u64; N],
+    domain: Domain,
+    ctx: &'ctx RingContext<N>,
+}
+
+impl<const N: usize> RingContext<N> {
+    /// Creates a context for the prime modulus q.
+    ///
+    /// # Panics
+    ///
+    /// Panics if N is not a power of two of at least 2, or if q is even or has no
+    /// N-th root of unity.
+    #[must_use]
+    pub fn new(modulus: u64) -> Self {
+        match Self::try_new(modulus) {
+            Ok(ctx) => ctx,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Creates a context, returning an error instead of panicking.
+    ///
+    /// The modulus must be prime; this is not checked.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::InvalidTransformSize` if N is not a power of two of at
+    /// least 2, `ModularError::ZeroModulus` or `ModularError::EvenModulus` for an
+    /// unusable modulus, and `ModularError::NoRootOfUnity` if q is 1 or N does not
+    /// divide q - 1.
+    pub fn try_new(modulus: u64) -> Result<Self, ModularError> {
+        if N < 2 || !N.is_power_of_two() {
+            return Err(ModularError::InvalidTransformSize);
+        }
+        if modulus == 1 {
+            return Err(ModularError::NoRootOfUnity);
+        }
+        let montgomery = MontgomeryContext::try_new(modulus)?;
+        let barrett = BarrettContext::try_new(modulus)?;
+
+        let full = NttPlan::try_new(modulus, N)?;
+        if full.supports_negacyclic() {
+            return Ok(Self {
+                plan: full,
+                montgomery,
+                barrett,
+                points: None,
+            });
+        }
+
+        // x^N + 1 = y^(N/2) + 1 with y = x²; the negacyclic transform of the monomial y
+        // yields the evaluation points ζ_k of its slots
+        let plan = NttPlan::try_new(modulus, N / 2)?;
+        if !plan.supports_negacyclic() {
+            return Err(ModularError::NoRootOfUnity);
+        }
+        let mut points = vec![0; N / 2];
+        // y reduced modulo y^(N/2) + 1, which is -1 when N/2 = 1
+        if N > 2 {
+            points[1] = 1;
+        } else {
+            points[0] = modulus - 1;
+        }
+        plan.forward_negacyclic(&mut points);
+        let points = points
+            .iter()
+            .map(|p| u64::montgomery_product(&montgomery, p, &montgomery.r_squared()))
+            .collect();
+
+        Ok(Self {
+            plan,
+            montgomery,
+            barrett,
+            points: Some(points),
+        })
+    }
+
+    /// Returns the modulus q.
+    #[must_use]
+    pub const fn modulus(&self) -> u64 {
+        self.montgomery.modulus()
+    }
+
+    /// Returns true if the NTT splits x^N + 1 completely into linear factors.
+    #[must_use]
+    pub const fn is_fully_split(&self) -> bool {
+        self.points.is_none()
+    }
+
+    /// Returns the zero element, in the coefficient domain.
+    #[must_use]
+    pub const fn zero(&self) -> RingElement<'_, N> {
+        RingElement {
+            values: [0; N],
+            domain: Domain::Coefficient,
+            ctx: self,
+        }
+    }
+
+    /// Creates an element from coefficients, reducing each modulo q.
+    #[must_use]
+    pub fn from_coefficients(&self, coefficients: &[u64; N]) -> RingElement<'_, N> {
+        RingElement {
+            values: coefficients.map(|c| self.barrett.reduce_u64(c)),
+            domain: Domain::Coefficient,
+            ctx: self,
+        }
+    }
+
+    /// Creates an element from signed coefficients, e.g. small noise.
+    #[must_use]
+    pub fn from_signed(&self, coefficients: &[i64; N]) -> RingElement<'_, N> {
+        let q = self.modulus();
+        RingElement {
+            values: coefficients.map(|c| {
+                let magnitude = self.barrett.reduce_u64(c.unsigned_abs());
+                if c < 0 {
+                    u64::neg_mod(magnitude, q)
+                } else {
+                    magnitude
+                }
+            }),
+            domain: Domain::Coefficient,
+            ctx: self,
+        }
+    }
+
+    /// Reverses `RingElement::compress`, mapping each d-bit value y to ⌈(q / 2^d)·y⌋.
+    ///
+    /// # Panics
+    ///
+    /// Panics if d is 0 or above 32.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)] // the rounded value is reduced below q
+    pub fn decompress(&self, compressed: &[u64; N], d: u32) -> RingElement<'_, N> {
+        assert!(
+            (1..=32).contains(&d),
+            "compression width must be between 1 and 32"
+        );
+        let q = u128::from(self.modulus());
+        let mask = (1u64 << d) - 1;
+        RingElement {
+            values: compressed.map(|y| {
+                // A d-bit value times q/2^d rounds to at most q, which is 0 in Z_q
+                let value = (u128::from(y & mask) * q + (1 << (d - 1))) >> d;
+                if value == q {
+                    0
+                } else {
+                    value as u64
+                }
+            }),
+            domain: Domain::Coefficient,
+            ctx: self,
+        }
+    }
+
+    /// Samples coefficients uniformly from [0, q) by rejection, reading each candidate
+    /// from ⌈log2(q) / 8⌉ little-endian bytes masked to the bit length of q.
+    ///
+    /// Returns `None` if the byte stream ends before N candidates are accepted.
+    pub fn sample_uniform(
+        &self,
+        bytes: &mut impl Iterator<Item = u8>,
+    ) -> Option<RingElement<'_, N>> {
+        let q = self.modulus();
+        let bits = u64::BITS - (q - 1).leading_zeros();
+        let mask = u64::MAX >> (u64::BITS - bits);
+        let width = bits.div_ceil(8) as usize;
+
+        let mut values = [0; N];
+        let mut filled = 0;
+        while filled < N {
+            let mut candidate = 0;
+            for i in 0..width {
+                candidate |= u64::from(bytes.next()?) << (8 * i);
+            }
+            candidate &= mask;
+            if candidate < q {
+                values[filled] = candidate;
+                filled += 1;
+            }
+        }
+        Some(RingElement {
+            values,
+            domain: Domain::Coefficient,
+            ctx: self,
+        })
+    }
+
+    /// Samples from the centered binomial distribution with parameter η: each
+    /// coefficient is the popcount of η bits minus the popcount of the next η bits,
+    /// with bits read least significant first.
+    ///
+    /// Returns `None` if the byte stream ends before 2ηN bits are read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if η is 0 or above 16.
+    pub fn sample_cbd(
+        &self,
+        eta: u32,
+        bytes: &mut impl Iterator<Item = u8>,
+    ) -> Option<RingElement<'_, N>> {
+        assert!((1..=16).contains(&eta), "eta must be between 1 and 16");
+        let mut buffer = 0u64;
+        let mut available = 0;
+        let mut coefficients = [0i64; N];
+        for c in &mut coefficients {
+            while available < 2 * eta {
+                buffer |= u64::from(bytes.next()?) << available;
+                available += 8;
+            }
+            let ones = |word: u64| i64::from((word & ((1 << eta) - 1)).count_ones());
+            *c = ones(buffer) - ones(buffer >> eta);
+            buffer >>= 2 * eta;
+            available -= 2 * eta;
+        }
+        Some(self.from_signed(&coefficients))
+    }
+
+    /// Converts values < q into Montgomery form.
+    fn montgomery_encode(&self, values: &mut [u64]) {
+        let r_squared = self.montgomery.r_squared();
+        for v in values {
+            *v = u64::montgomery_product(&self.montgomery, v, &r_squared);
+        }
+    }
+
+    /// Converts values out of Montgomery form.
+    fn montgomery_decode(&self, values: &mut [u64]) {
+        for v in values {
+            *v = u64::montgomery_redc(&self.montgomery, v);
+        }
+    }
+
+    fn forward(&self, values: &mut [u64; N]) {
+        if self.points.is_some() {
+            // Transform even and odd coefficients separately, then interleave the
+            // slots so that [2k, 2k + 1] holds the residue modulo x² - ζ_k
+            let mut halves = [0; N];
+            let (even, odd) = halves.split_at_mut(N / 2);
+            for (i, pair) in values.chunks_exact(2).enumerate() {
+                (even[i], odd[i]) = (pair[0], pair[1]);
+            }
+            self.plan.forward_negacyclic(even);
+            self.plan.forward_negacyclic(odd);
+            for (i, pair) in values.chunks_exact_mut(2).enumerate() {
+                (pair[0], pair[1]) = (even[i], odd[i]);
+            }
+        } else {
+            self.plan.forward_negacyclic(values);
+        }
+        self.montgomery_encode(values);
+    }
+
+    fn inverse(&self, values: &mut [u64; N]) {
+        self.montgomery_decode(values);
+        if self.points.is_some() {
+            let mut halves = [0; N];
+            let (even, odd) = halves.split_at_mut(N / 2);
+            for (i, pair) in values.chunks_exact(2).enumerate() {
+                (even[i], odd[i]) = (pair[0], pair[1]);
+            }
+            self.plan.inverse_negacyclic(even);
+            self.plan.inverse_negacyclic(odd);
+            for (i, pair) in values.chunks_exact_mut(2).enumerate() {
+                (pair[0], pair[1]) = (even[i], odd[i]);
+            }
+        } else {
+            self.plan.inverse_negacyclic(values);
+        }
+    }
+
+    /// Multiplies NTT-domain values slot by slot, all in Montgomery form.
+    fn slot_mul(&self, a: &[u64; N], b: &[u64; N]) -> [u64; N] {
+        let ctx = &self.montgomery;
+        let q = self.modulus();
+        let mul = |x: &u64, y: &u64| u64::montgomery_product(ctx, x, y);
+        let mut product = [0; N];
+        match &self.points {
+            None => {
+                for ((p, x), y) in product.iter_mut().zip(a).zip(b) {
+                    *p = mul(x, y);
+                }
+            }
+            Some(points) => {
+                // (a0 + a1·x)(b0 + b1·x) = (a0·b0 + ζ·a1·b1) + (a0·b1 + a1·b0)·x mod x² - ζ
+                for (k, zeta) in points.iter().enumerate() {
+                    let (a0, a1, b0, b1) = (&a[2 * k], &a[2 * k + 1], &b[2 * k], &b[2 * k + 1]);
+                    product[2 * k] = u64::add_mod(mul(a0, b0), mul(&mul(a1, b1), zeta), q);
+                    product[2 * k + 1] = u64::add_mod(mul(a0, b1), mul(a1, b0), q);
+                }
+            }
+        }
+        product
+    }
+}
+
+impl<'ctx, const N: usize> RingElement<'ctx, N> {
+    /// Returns the context this element is bound to.
+    #[must_use]
+    pub const fn context(&self) -> &'ctx RingContext<N> {
+        self.ctx
+    }
+
+    /// Returns the representation currently held.
+    #[must_use]
+    pub const fn domain(&self) -> Domain {
+        self.domain
+    }
+
+    /// Returns the raw values of the current representation: coefficients, or NTT
+    /// slots in Montgomery form.
+    #[must_use]
+    pub const fn values(&self) -> &[u64; N] {
+        &self.values
+    }
+
+    /// Returns the element in the NTT domain.
+    #[must_use]
+    pub fn to_ntt(mut self) -> Self {
+        if self.domain == Domain::Coefficient {
+            self.ctx.forward(&mut self.values);
+            self.domain = Domain::Ntt;
+        }
+        self
+    }
+
+    /// Returns the element in the coefficient domain.
+    #[must_use]
+    pub fn to_coefficient_domain(mut self) -> Self {
+        if self.domain == Domain::Ntt {
+            self.ctx.inverse(&mut self.values);
+            self.domain = Domain::Coefficient;
+        }
+        self
+    }
+
+    /// Returns the coefficients in [0, q), converting out of the NTT domain if needed.
+    #[must_use]
+    pub fn to_coefficients(self) -> [u64; N] {
+        self.to_coefficient_domain().values
+    }
+
+    /// Returns the coefficients as centered representatives in (-q/2, q/2].
+    #[must_use]
+    pub fn to_centered(self) -> [i64; N] {
+        let q = self.ctx.modulus();
+        // Both representatives are below q/2 < 2^63, so the casts are exact
+        self.to_coefficients().map(|c| {
+            if c > q / 2 {
+                -(q - c).cast_signed()
+            } else {
+                c.cast_signed()
+            }
+        })
+    }
+
+    /// Returns the largest absolute value among the centered coefficients.
+    #[must_use]
+    pub fn infinity_norm(self) -> u64 {
+        self.to_centered()
+            .iter()
+            .map(|c| c.unsigned_abs())
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Compresses each coefficient x to the d-bit value ⌈(2^d / q)·x⌋ mod 2^d.
+    ///
+    /// # Panics
+    ///
+    /// Panics if d is 0 or above 32.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)] // the quotient is at most 2^d before the mask
+    pub fn compress(self, d: u32) -> [u64; N] {
+        assert!(
+            (1..=32).contains(&d),
+            "compression width must be between 1 and 32"
+        );
+        let q = u128::from(self.ctx.modulus());
+        let mask = (1u64 << d) - 1;
+        self.to_coefficients()
+            .map(|x| (((u128::from(x) << d) + q / 2) / q) as u64 & mask)
+    }
+
+    fn in_domain(self, domain: Domain) -> Self {
+        match domain {
+            Domain::Coefficient => self.to_coefficient_domain(),
+            Domain::Ntt => self.to_ntt(),
+        }
+    }
+
+    fn check_context(&self, other: &Self, operation: &str) {
+        assert_eq!(
+            self.ctx.modulus(),
+            other.ctx.modulus(),
+            "Modulus mismatch in {operation}"
+        );
+    }
+}
+
+impl<const N: usize> PartialEq for RingElement<'_, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ctx.modulus() == other.ctx.modulus()
+            && self.to_coefficients() == other.to_coefficients()
+    }
+}
+
+impl<const N: usize> Eq for RingElement<'_, N> {}
+
+// Implement Add trait for RingElement
+impl<const N: usize> Add for RingElement<'_, N> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self::Output {
+        self.check_context(&other, "addition");
+        let q = self.ctx.modulus();
+        let other = other.in_domain(self.domain);
+        for (x, &y) in self.values.iter_mut().zip(&other.values) {
+            *x = u64::add_mod(*x, y, q);
+        }
+        self
+    }
+}
+
+// Implement AddAssign trait for RingElement
+impl<const N: usize> AddAssign for RingElement<'_, N> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+// Implement Sub trait for RingElement
+impl<const N: usize> Sub for RingElement<'_, N> {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self::Output {
+        self.check_context(&other, "subtraction");
+        let q = self.ctx.modulus();
+        let other = other.in_domain(self.domain);
+        for (x, &y) in self.values.iter_mut().zip(&other.values) {
+            *x = u64::sub_mod(*x, y, q);
+        }
+        self
+    }
+}
+
+// Implement SubAssign trait for RingElement
+impl<const N: usize> SubAssign for RingElement<'_, N> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+// Implement Mul trait for RingElement, producing an NTT-domain result
+impl<const N: usize> Mul for RingElement<'_, N> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        self.check_context(&other, "multiplication");
+        let (a, b) = (self.to_ntt(), other.to_ntt());
+        Self {
+            values: self.ctx.slot_mul(&a.values, &b.values),
+            domain: Domain::Ntt,
+            ctx: self.ctx,
+        }
+    }
+}
+
+// Implement MulAssign trait for RingElement
+impl<const N: usize> MulAssign for RingElement<'_, N> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+// Implement Neg trait for RingElement
+impl<const N: usize> Neg for RingElement<'_, N> {
+    type Output = Self;
+
+    fn neg(mut self) -> Self::Output {
+        let q = self.ctx.modulus();
+        for x in &mut self.values {
+            *x = u64::neg_mod(*x, q);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KYBER_Q: u64 = 3329;
+    const DILITHIUM_Q: u64 = 8_380_417;
+
+    fn stream(seed: u64) -> impl Iterator<Item = u8> {
+        let mut state = seed;
+        std::iter::repeat_with(move || {
+            state = state
+                .wrapping_mul(0x5851_F42D_4C95_7F2D)
+                .wrapping_add(0x1405_7B7E_F767_814F);
+            (state >> 56) as u8
+        })
+    }
+
+    fn schoolbook<const N: usize>(a: &[u64; N], b: &[u64; N], q: u64) -> [u64; N] {
+        let mut product = [0; N];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                let term = u64::mul_mod(x, y, q);
+                let k = (i + j) % N;
+                // x^N = -1
+                product[k] = if i + j < N {
+                    u64::add_mod(product[k], term, q)
+                } else {
+                    u64::sub_mod(product[k], term, q)
+                };
+            }
+        }
+        product
+    }
+
+    fn check_multiplication<const N: usize>(q: u64) {
+        let ctx = RingContext::<N>::new(q);
+        let a = ctx.sample_uniform(&mut stream(1)).unwrap();
+        let b = ctx.sample_uniform(&mut stream(2)).unwrap();
+        let expected = schoolbook(a.values(), b.values(), q);
+        assert_eq!((a * b).to_coefficients(), expected);
+        assert_eq!((a.to_ntt() * b).to_coefficients(), expected);
+        assert_eq!(a.to_ntt().to_coefficient_domain(), a);
+        assert_eq!(
+            (a + b.to_ntt()).to_coefficients(),
+            (b + a).to_coefficients()
+        );
+        assert_eq!((a * b - b * a).infinity_norm(), 0);
+    }
+
+    #[test]
+    fn test_ring_multiplication() {
+        check_multiplication::<256>(DILITHIUM_Q);
+        check_multiplication::<256>(KYBER_Q);
+        check_multiplication::<2>(KYBER_Q);
+        check_multiplication::<16>(17);
+        assert!(RingContext::<256>::new(DILITHIUM_Q).is_fully_split());
+        assert!(!RingContext::<256>::new(KYBER_Q).is_fully_split());
+
+        // x^(N-1) · x = x^N = -1
+        let ctx = RingContext::<8>::new(17);
+        let x = ctx.from_coefficients(&[0, 1, 0, 0, 0, 0, 0, 0]);
+        let top = ctx.from_coefficients(&[0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!((x * top).to_centered(), [-1, 0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_ring_context_errors() {
+        assert_eq!(
+            RingContext::<512>::try_new(KYBER_Q).err(),
+            Some(ModularError::NoRootOfUnity)
+        );
+        assert_eq!(
+            RingContext::<6>::try_new(KYBER_Q).err(),
+            Some(ModularError::InvalidTransformSize)
+        );
+        assert_eq!(
+            RingContext::<2>::try_new(1).err(),
+            Some(ModularError::NoRootOfUnity)
+        );
+        assert_eq!(
+            RingContext::<2>::try_new(0).err(),
+            Some(ModularError::ZeroModulus)
+        );
+        assert_eq!(
+            RingContext::<2>::try_new(2).err(),
+            Some(ModularError::EvenModulus)
+        );
+    }
+
+    #[test]
+    fn test_ring_sampling() {
+        let ctx = RingContext::<256>::new(KYBER_Q);
+        let noise = ctx.sample_cbd(2, &mut stream(3)).unwrap();
+        assert!(noise.infinity_norm() <= 2);
+        assert!(noise.to_centered().iter().any(|&c| c != 0));
+        // Exactly 2ηN / 8 bytes are needed
+        assert!(ctx.sample_cbd(3, &mut stream(4).take(3 * 64)).is_some());
+        assert!(ctx.sample_cbd(3, &mut stream(4).take(3 * 64 - 1)).is_none());
+
+        let uniform = ctx.sample_uniform(&mut stream(5)).unwrap();
+        assert!(uniform.values().iter().all(|&c| c < KYBER_Q));
+        assert!(ctx.sample_uniform(&mut stream(5).take(100)).is_none());
+    }
+
+    #[test]
+    fn test_ring_compression() {
+        let ctx = RingContext::<256>::new(KYBER_Q);
+        let a = ctx.sample_uniform(&mut stream(6)).unwrap();
+        for d in [1, 4, 10, 11] {
+            let restored = ctx.decompress(&a.compress(d), d);
+            // Rounding error is at most ⌈q / 2^(d+1)⌋
+            let bound = (KYBER_Q + (1 << (d + 1)) - 1) >> (d + 1);
+            assert!((a - restored).infinity_norm() <= bound, "d = {d}");
+        }
+        // 1-bit compression rounds to the nearer of 0 and q/2
+        let bits = ctx.from_signed(&[0; 256]).compress(1);
+        assert!(bits.iter().all(|&b| b == 0));
+        assert_eq!(ctx.decompress(&[1; 256], 1).values()[0], 1665);
+        // Widths above the bit length of q round the top value up to q, which wraps to 0
+        for d in [13, 20, 32] {
+            let top = ctx.decompress(&[(1 << d) - 1; 256], d);
+            assert!(top.values().iter().all(|&c| c == 0), "d = {d}");
+        }
+    }
+}
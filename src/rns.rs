@@ -0,0 +1,731 @@
+// Residue number system arithmetic over word-sized primes
+
+use crate::{
+    CrtBasis, ModularError, ModularInt, ModularStorage, MontgomeryContext, MontgomeryWord,
+};
+#[cfg(feature = "arbitrary-precision")]
+use num_bigint::BigUint;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// A set of pairwise coprime odd moduli q_i, typically primes just below 2^60, that
+/// represents integers modulo their product Q by their residues.
+///
+/// Each modulus keeps its own `MontgomeryContext`, so arithmetic on an `RnsInt` is a
+/// word-sized Montgomery operation per modulus with no carries between them.
+/// Conversion back to a single integer goes through a `CrtBasis`.
+#[derive(Debug, Clone)]
+pub struct RnsBasis {
+    contexts: Vec<MontgomeryContext<u64>>,
+    crt: CrtBasis,
+    punctured_inverses: Vec<u64>, // (Q/q_i)⁻¹ mod q_i
+    last_inverses: Vec<u64>,      // q_(k-1)⁻¹ mod q_i in Montgomery form, for i < k - 1
+}
+
+/// An integer modulo the product of an `RnsBasis`, held as one residue per modulus in
+/// Montgomery form and bound to the basis that created it.
+#[derive(Debug, Clone)]
+pub struct RnsInt<'b> {
+    residues: Vec<u64>,
+    basis: &'b RnsBasis,
+}
+
+/// Precomputed tables for moving values from one `RnsBasis` to another.
+///
+/// With y_i = x_i·(Q/q_i)⁻¹ mod q_i, the value Σ y_i·(Q/q_i) equals x + α·Q for
+/// some 0 ≤ α < k, where k is the number of source moduli. The fast conversion of
+/// Bajard et al. reduces that sum modulo each target modulus and accepts the error;
+/// the Shenoy–Kumaresan conversion recovers α from one redundant residue of x.
+#[derive(Debug, Clone)]
+pub struct BaseConverter<'a, 'b> {
+    from: &'a RnsBasis,
+    to: &'b RnsBasis,
+    punctured: Vec<Vec<u64>>, // (Q/q_i mod p_j)·R² mod p_j, indexed [j][i]
+    products: Vec<u64>,       // (Q mod p_j)·R² mod p_j
+}
+
+/// Converts a value below 2^64 into Montgomery form.
+fn encode(ctx: &MontgomeryContext<u64>, value: u64) -> u64 {
+    u64::montgomery_product(ctx, &value, &ctx.r_squared())
+}
+
+/// The product of `moduli` reduced modulo m, skipping the modulus at `skip`.
+fn product_mod(moduli: &[u64], skip: Option<usize>, m: u64) -> u64 {
+    moduli
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| Some(i) != skip)
+        .fold(1 % m, |acc, (_, &q)| u64::mul_mod(acc, q % m, m))
+}
+
+impl RnsBasis {
+    /// Creates a basis from pairwise coprime odd moduli.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a modulus is zero or even, or two moduli share a factor.
+    #[must_use]
+    pub fn new(moduli: &[u64]) -> Self {
+        match Self::try_new(moduli) {
+            Ok(basis) => basis,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Creates a basis, returning an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::ZeroModulus` or `ModularError::EvenModulus` for a
+    /// modulus without a Montgomery context, and `ModularError::NotInvertible` if two
+    /// moduli share a factor.
+    pub fn try_new(moduli: &[u64]) -> Result<Self, ModularError> {
+        let contexts = moduli
+            .iter()
+            .map(|&q| MontgomeryContext::try_new(q))
+            .collect::<Result<Vec<_>, _>>()?;
+        let crt = CrtBasis::try_new(moduli)?;
+
+        let punctured_inverses = moduli
+            .iter()
+            .enumerate()
+            .map(|(i, &q)| {
+                let punctured = ModularInt::new(product_mod(moduli, Some(i), q), q);
+                punctured.checked_inverse().map(|inverse| inverse.value())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let last_inverses = match moduli.split_last() {
+            Some((&last, rest)) => rest
+                .iter()
+                .zip(&contexts)
+                .map(|(&q, ctx)| {
+                    let inverse = ModularInt::new(last % q, q).checked_inverse()?;
+                    Ok(encode(ctx, inverse.value()))
+                })
+                .collect::<Result<Vec<_>, ModularError>>()?,
+            None => Vec::new(),
+        };
+
+        Ok(Self {
+            contexts,
+            crt,
+            punctured_inverses,
+            last_inverses,
+        })
+    }
+
+    /// Returns the moduli.
+    #[must_use]
+    pub fn moduli(&self) -> &[u64] {
+        self.crt.moduli()
+    }
+
+    /// Returns the number of moduli.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.contexts.len()
+    }
+
+    /// Returns true if the basis has no moduli, so that it represents only 0.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.contexts.is_empty()
+    }
+
+    /// Returns the product Q of the moduli, or `None` if it does not fit in a `u128`.
+    #[must_use]
+    pub fn modulus_u128(&self) -> Option<u128> {
+        self.crt.modulus_u128()
+    }
+
+    /// Returns 0.
+    #[must_use]
+    pub fn zero(&self) -> RnsInt<'_> {
+        RnsInt {
+            residues: vec![0; self.len()],
+            basis: self,
+        }
+    }
+
+    /// Returns 1.
+    #[must_use]
+    pub fn one(&self) -> RnsInt<'_> {
+        self.from_residues(&vec![1; self.len()])
+    }
+
+    /// Creates a value from its residues modulo each modulus, reducing them first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of residues differs from the number of moduli.
+    #[must_use]
+    pub fn from_residues(&self, residues: &[u64]) -> RnsInt<'_> {
+        assert_eq!(
+            residues.len(),
+            self.len(),
+            "residue count does not match the basis"
+        );
+        RnsInt {
+            residues: residues
+                .iter()
+                .zip(&self.contexts)
+                .map(|(&r, ctx)| encode(ctx, r))
+                .collect(),
+            basis: self,
+        }
+    }
+
+    /// Creates the residue of x modulo Q.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)] // each residue is below its 64-bit modulus
+    pub fn from_u128(&self, x: u128) -> RnsInt<'_> {
+        let residues: Vec<u64> = self
+            .moduli()
+            .iter()
+            .map(|&q| (x % u128::from(q)) as u64)
+            .collect();
+        self.from_residues(&residues)
+    }
+
+    /// Creates the residue of x modulo Q.
+    #[cfg(feature = "arbitrary-precision")]
+    #[must_use]
+    pub fn from_biguint(&self, x: &BigUint) -> RnsInt<'_> {
+        let residues: Vec<u64> = self
+            .moduli()
+            .iter()
+            .map(|&q| (x % q).try_into().unwrap_or_default())
+            .collect();
+        self.from_residues(&residues)
+    }
+}
+
+impl<'b> RnsInt<'b> {
+    /// Returns the basis this value is bound to.
+    #[must_use]
+    pub const fn basis(&self) -> &'b RnsBasis {
+        self.basis
+    }
+
+    /// Returns the residues modulo each modulus, out of Montgomery form.
+    #[must_use]
+    pub fn residues(&self) -> Vec<u64> {
+        self.residues
+            .iter()
+            .zip(&self.basis.contexts)
+            .map(|(r, ctx)| u64::montgomery_redc(ctx, r))
+            .collect()
+    }
+
+    /// Reconstructs the value in [0, Q), or `None` if Q does not fit in a `u128`.
+    #[must_use]
+    pub fn to_u128(&self) -> Option<u128> {
+        self.basis.crt.reconstruct_u128(&self.residues())
+    }
+
+    /// Reconstructs the value in [0, Q).
+    #[cfg(feature = "arbitrary-precision")]
+    #[must_use]
+    pub fn to_biguint(&self) -> BigUint {
+        self.basis.crt.reconstruct_biguint(&self.residues())
+    }
+
+    /// Divides by d, assuming d divides the value as an integer in [0, Q).
+    ///
+    /// Multiplying each residue by d⁻¹ gives the exact quotient when the division is
+    /// exact and an unrelated residue otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if d shares a factor with a modulus.
+    #[must_use]
+    pub fn exact_div(&self, d: u64) -> Self {
+        match self.checked_exact_div(d) {
+            Ok(quotient) => quotient,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Divides by d, returning an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::NotInvertible` if d shares a factor with a modulus.
+    pub fn checked_exact_div(&self, d: u64) -> Result<Self, ModularError> {
+        let residues = self
+            .residues
+            .iter()
+            .zip(&self.basis.contexts)
+            .map(|(r, ctx)| {
+                let q = ctx.modulus();
+                let inverse = ModularInt::new(d % q, q).checked_inverse()?;
+                Ok(u64::montgomery_product(
+                    ctx,
+                    r,
+                    &encode(ctx, inverse.value()),
+                ))
+            })
+            .collect::<Result<Vec<_>, ModularError>>()?;
+        Ok(Self {
+            residues,
+            basis: self.basis,
+        })
+    }
+
+    /// Computes ⌊x / q_(k-1)⌋ for the value x in [0, Q), dropping the last modulus.
+    ///
+    /// `target` must hold the first k - 1 moduli of this value's basis.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the basis is empty or `target` does not match its first k - 1 moduli.
+    #[must_use]
+    pub fn divide_by_last<'c>(&self, target: &'c RnsBasis) -> RnsInt<'c> {
+        self.scale_down(target, false)
+    }
+
+    /// Computes x / q_(k-1) rounded to the nearest integer, with ties rounded up, for
+    /// the value x in [0, Q), dropping the last modulus. The quotient is reduced
+    /// modulo the target basis, so values that round up to Q / q_(k-1) give 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the basis is empty or `target` does not match its first k - 1 moduli.
+    #[must_use]
+    pub fn divide_and_round_by_last<'c>(&self, target: &'c RnsBasis) -> RnsInt<'c> {
+        self.scale_down(target, true)
+    }
+
+    fn scale_down<'c>(&self, target: &'c RnsBasis, round: bool) -> RnsInt<'c> {
+        let moduli = self.basis.moduli();
+        let Some((&last, rest)) = moduli.split_last() else {
+            panic!("cannot drop a modulus from an empty basis");
+        };
+        assert_eq!(
+            target.moduli(),
+            rest,
+            "target basis must be the source basis without its last modulus"
+        );
+
+        // (x - (x mod q_last)) / q_last is exact, and the remainder decides rounding
+        let remainder = self.residues[rest.len()];
+        let remainder = u64::montgomery_redc(&self.basis.contexts[rest.len()], &remainder);
+        let carry = round && remainder >= last - remainder;
+
+        let residues = self
+            .residues
+            .iter()
+            .zip(&self.basis.contexts)
+            .zip(&self.basis.last_inverses)
+            .map(|((r, ctx), inverse)| {
+                let q = ctx.modulus();
+                let difference = u64::sub_mod(*r, encode(ctx, remainder), q);
+                let quotient = u64::montgomery_product(ctx, &difference, inverse);
+                if carry {
+                    u64::add_mod(quotient, encode(ctx, 1), q)
+                } else {
+                    quotient
+                }
+            })
+            .collect();
+        RnsInt {
+            residues,
+            basis: target,
+        }
+    }
+
+    fn check_basis(&self, other: &Self, operation: &str) {
+        assert_eq!(
+            self.basis.moduli(),
+            other.basis.moduli(),
+            "Basis mismatch in {operation}"
+        );
+    }
+
+    fn zip_with(
+        &self,
+        other: &Self,
+        op: impl Fn(&MontgomeryContext<u64>, u64, u64) -> u64,
+    ) -> Self {
+        Self {
+            residues: self
+                .residues
+                .iter()
+                .zip(&other.residues)
+                .zip(&self.basis.contexts)
+                .map(|((&a, &b), ctx)| op(ctx, a, b))
+                .collect(),
+            basis: self.basis,
+        }
+    }
+}
+
+impl<'a, 'b> BaseConverter<'a, 'b> {
+    /// Precomputes the conversion from `from` to `to`.
+    #[must_use]
+    pub fn new(from: &'a RnsBasis, to: &'b RnsBasis) -> Self {
+        let source = from.moduli();
+        let punctured = to
+            .contexts
+            .iter()
+            .map(|ctx| {
+                (0..source.len())
+                    .map(|i| {
+                        let value = product_mod(source, Some(i), ctx.modulus());
+                        encode(ctx, encode(ctx, value))
+                    })
+                    .collect()
+            })
+            .collect();
+        let products = to
+            .contexts
+            .iter()
+            .map(|ctx| encode(ctx, encode(ctx, product_mod(source, None, ctx.modulus()))))
+            .collect();
+        Self {
+            from,
+            to,
+            punctured,
+            products,
+        }
+    }
+
+    /// Returns the source basis.
+    #[must_use]
+    pub const fn source(&self) -> &'a RnsBasis {
+        self.from
+    }
+
+    /// Returns the target basis.
+    #[must_use]
+    pub const fn target(&self) -> &'b RnsBasis {
+        self.to
+    }
+
+    /// Converts x to the target basis up to a multiple of the source modulus Q: the
+    /// result represents x + α·Q for some 0 ≤ α < k.
+    ///
+    /// This is the approximate conversion of Bajard et al., which costs k·l word
+    /// multiplications for k source and l target moduli.
+    ///
+    /// # Panics
+    ///
+    /// Panics if x does not belong to the source basis.
+    #[must_use]
+    pub fn fast_convert(&self, x: &RnsInt<'_>) -> RnsInt<'b> {
+        let y = self.scaled_residues(x);
+        let residues = self
+            .to
+            .contexts
+            .iter()
+            .zip(&self.punctured)
+            .map(|(ctx, row)| Self::dot(ctx, &y, row))
+            .collect();
+        RnsInt {
+            residues,
+            basis: self.to,
+        }
+    }
+
+    /// Converts x to the target basis exactly, given its residue modulo a redundant
+    /// modulus m coprime to Q.
+    ///
+    /// This is the Shenoy–Kumaresan conversion: the fast conversion's sum is also
+    /// reduced modulo m, and comparing it with the known residue yields α.
+    ///
+    /// # Panics
+    ///
+    /// Panics if x does not belong to the source basis, if m is smaller than the
+    /// number of source moduli, or if m shares a factor with Q.
+    #[must_use]
+    pub fn exact_convert(&self, x: &RnsInt<'_>, redundant: ModularInt<u64>) -> RnsInt<'b> {
+        let source = self.from.moduli();
+        let m = redundant.modulus();
+        assert!(
+            m >= source.len() as u64,
+            "the redundant modulus must be at least the number of source moduli"
+        );
+
+        let y = self.scaled_residues(x);
+        let sum = y.iter().enumerate().fold(0, |acc, (i, &y)| {
+            let punctured = product_mod(source, Some(i), m);
+            u64::add_mod(acc, u64::mul_mod(y % m, punctured, m), m)
+        });
+        let Ok(inverse) = ModularInt::new(product_mod(source, None, m), m).checked_inverse() else {
+            panic!("{}", ModularError::NotInvertible);
+        };
+        // Σ y_i·(Q/q_i) = x + α·Q, so α ≡ (sum - x)·Q⁻¹ (mod m), and α < k ≤ m
+        let alpha = (ModularInt::new(sum, m) - redundant) * inverse;
+        let alpha = alpha.value();
+
+        let residues = self
+            .to
+            .contexts
+            .iter()
+            .zip(&self.punctured)
+            .zip(&self.products)
+            .map(|((ctx, row), product)| {
+                let correction = u64::montgomery_product(ctx, &(alpha % ctx.modulus()), product);
+                u64::sub_mod(Self::dot(ctx, &y, row), correction, ctx.modulus())
+            })
+            .collect();
+        RnsInt {
+            residues,
+            basis: self.to,
+        }
+    }
+
+    /// The values y_i = x_i·(Q/q_i)⁻¹ mod q_i, out of Montgomery form.
+    fn scaled_residues(&self, x: &RnsInt<'_>) -> Vec<u64> {
+        assert_eq!(
+            x.basis.moduli(),
+            self.from.moduli(),
+            "value does not belong to the source basis"
+        );
+        // x_i·R times a plain inverse leaves a plain product
+        x.residues
+            .iter()
+            .zip(&self.from.contexts)
+            .zip(&self.from.punctured_inverses)
+            .map(|((r, ctx), inverse)| u64::montgomery_product(ctx, r, inverse))
+            .collect()
+    }
+
+    /// Σ y_i·c_i modulo the context's modulus, in Montgomery form given c_i·R².
+    fn dot(ctx: &MontgomeryContext<u64>, y: &[u64], row: &[u64]) -> u64 {
+        let p = ctx.modulus();
+        y.iter().zip(row).fold(0, |acc, (y, c)| {
+            u64::add_mod(acc, u64::montgomery_product(ctx, y, c), p)
+        })
+    }
+}
+
+impl PartialEq for RnsInt<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.basis.moduli() == other.basis.moduli() && self.residues == other.residues
+    }
+}
+
+impl Eq for RnsInt<'_> {}
+
+// Implement Add trait for RnsInt
+impl<'b> Add for &RnsInt<'b> {
+    type Output = RnsInt<'b>;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.check_basis(other, "addition");
+        self.zip_with(other, |ctx, a, b| u64::add_mod(a, b, ctx.modulus()))
+    }
+}
+
+impl Add for RnsInt<'_> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        &self + &other
+    }
+}
+
+// Implement AddAssign trait for RnsInt
+impl AddAssign<&Self> for RnsInt<'_> {
+    fn add_assign(&mut self, other: &Self) {
+        *self = &*self + other;
+    }
+}
+
+// Implement Sub trait for RnsInt
+impl<'b> Sub for &RnsInt<'b> {
+    type Output = RnsInt<'b>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self.check_basis(other, "subtraction");
+        self.zip_with(other, |ctx, a, b| u64::sub_mod(a, b, ctx.modulus()))
+    }
+}
+
+impl Sub for RnsInt<'_> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        &self - &other
+    }
+}
+
+// Implement SubAssign trait for RnsInt
+impl SubAssign<&Self> for RnsInt<'_> {
+    fn sub_assign(&mut self, other: &Self) {
+        *self = &*self - other;
+    }
+}
+
+// Implement Mul trait for RnsInt
+impl<'b> Mul for &RnsInt<'b> {
+    type Output = RnsInt<'b>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        self.check_basis(other, "multiplication");
+        self.zip_with(other, |ctx, a, b| u64::montgomery_product(ctx, &a, &b))
+    }
+}
+
+impl Mul for RnsInt<'_> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        &self * &other
+    }
+}
+
+// Implement MulAssign trait for RnsInt
+impl MulAssign<&Self> for RnsInt<'_> {
+    fn mul_assign(&mut self, other: &Self) {
+        *self = &*self * other;
+    }
+}
+
+// Implement Neg trait for RnsInt
+impl Neg for RnsInt<'_> {
+    type Output = Self;
+
+    fn neg(mut self) -> Self::Output {
+        for (r, ctx) in self.residues.iter_mut().zip(&self.basis.contexts) {
+            *r = u64::neg_mod(*r, ctx.modulus());
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Primes 2^60 - 93, 2^60 - 107, 2^60 - 173 and 2^60 - 179
+    const Q0: u64 = (1 << 60) - 93;
+    const Q1: u64 = (1 << 60) - 107;
+    const P0: u64 = (1 << 60) - 173;
+    const P1: u64 = (1 << 60) - 179;
+
+    fn samples(modulus: u128) -> impl Iterator<Item = u128> {
+        let mut state = 0x0123_4567_89AB_CDEFu128;
+        let edges = [0, 1, modulus - 1, modulus / 2];
+        edges.into_iter().chain(std::iter::repeat_with(move || {
+            state = state
+                .wrapping_mul(0x2360_ED05_1FC6_5DA4_4385_DF64_9FCC_F645)
+                .wrapping_add(0x5851_F42D_4C95_7F2D_1405_7B7E_F767_814F);
+            state % modulus
+        }))
+    }
+
+    #[test]
+    fn test_arithmetic_matches_u128() {
+        let basis = RnsBasis::new(&[Q0, Q1]);
+        let q = basis.modulus_u128().unwrap();
+        assert_eq!(q, u128::from(Q0) * u128::from(Q1));
+
+        let values: Vec<u128> = samples(q).take(12).collect();
+        for pair in values.windows(2) {
+            let (x, y) = (basis.from_u128(pair[0]), basis.from_u128(pair[1]));
+            let (mx, my) = (ModularInt::new(pair[0], q), ModularInt::new(pair[1], q));
+            assert_eq!(x.to_u128(), Some(pair[0]));
+            assert_eq!((&x + &y).to_u128(), Some((mx + my).value()));
+            assert_eq!((&x - &y).to_u128(), Some((mx - my).value()));
+            assert_eq!((&x * &y).to_u128(), Some((mx * my).value()));
+            assert_eq!((-x.clone()).to_u128(), Some((-mx).value()));
+
+            let mut acc = x.clone();
+            acc *= &y;
+            acc += &basis.one();
+            acc -= &x;
+            assert_eq!(
+                acc.to_u128(),
+                Some((mx * my + ModularInt::new(1, q) - mx).value())
+            );
+        }
+        assert_eq!(basis.zero().residues(), vec![0, 0]);
+        assert_eq!(basis.from_u128(q + 5), basis.from_u128(5));
+    }
+
+    #[test]
+    fn test_invalid_bases() {
+        assert_eq!(
+            RnsBasis::try_new(&[Q0, 0]).unwrap_err(),
+            ModularError::ZeroModulus
+        );
+        assert_eq!(
+            RnsBasis::try_new(&[Q0, 6]).unwrap_err(),
+            ModularError::EvenModulus
+        );
+        assert_eq!(
+            RnsBasis::try_new(&[15, 21]).unwrap_err(),
+            ModularError::NotInvertible
+        );
+        assert!(RnsBasis::new(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_base_conversion() {
+        let from = RnsBasis::new(&[Q0, Q1]);
+        let to = RnsBasis::new(&[P0, P1]);
+        let converter = BaseConverter::new(&from, &to);
+        let (q, p) = (from.modulus_u128().unwrap(), to.modulus_u128().unwrap());
+        let redundant_modulus = 65_537;
+
+        for x in samples(q).take(20) {
+            let value = from.from_u128(x);
+
+            // Bajard: x + α·Q with α in {0, 1}
+            let fast = converter.fast_convert(&value).to_u128().unwrap();
+            let (mx, mq) = (ModularInt::new(x % p, p), ModularInt::new(q % p, p));
+            assert!(fast == mx.value() || fast == (mx + mq).value());
+
+            // Shenoy–Kumaresan: exact
+            #[allow(clippy::cast_possible_truncation)] // the remainder is below 65537
+            let redundant = ModularInt::new((x % redundant_modulus) as u64, 65_537);
+            let exact = converter.exact_convert(&value, redundant);
+            assert_eq!(exact.to_u128(), Some(x % p));
+            assert_eq!(exact.basis().moduli(), &[P0, P1]);
+        }
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation)] // the divisor d is a 29-bit constant
+    fn test_scaling_and_exact_division() {
+        let basis = RnsBasis::new(&[Q0, Q1]);
+        let smaller = RnsBasis::new(&[Q0]);
+        let q = basis.modulus_u128().unwrap();
+        let last = u128::from(Q1);
+
+        for x in samples(q).take(20) {
+            let value = basis.from_u128(x);
+            let floor = value.divide_by_last(&smaller).to_u128();
+            assert_eq!(floor, Some(x / last));
+            let rounded = value.divide_and_round_by_last(&smaller).to_u128();
+            let nearest = x / last + u128::from(2 * (x % last) >= last);
+            assert_eq!(rounded, Some(nearest % u128::from(Q0)));
+
+            let d = 0x1234_5677;
+            let multiple = basis.from_u128((x / d) * d);
+            assert_eq!(multiple.exact_div(d as u64).to_u128(), Some(x / d));
+        }
+        assert_eq!(
+            basis.from_u128(Q0.into()).checked_exact_div(Q0 * 3),
+            Err(ModularError::NotInvertible)
+        );
+    }
+
+    #[cfg(feature = "arbitrary-precision")]
+    #[test]
+    #[allow(clippy::many_single_char_names)] // x, y, their residues a, b and the basis product q
+    fn test_biguint_round_trip() {
+        let primes = [Q0, Q1, P0, P1];
+        let basis = RnsBasis::new(&primes);
+        let q = primes.iter().fold(BigUint::from(1u8), |acc, &p| acc * p);
+        let x = (BigUint::from(0xFEDC_BA98_7654_3210u64) << 170u32) + 12_345u32;
+        let y = (BigUint::from(0x0F1E_2D3C_4B5A_6978u64) << 100u32) + 99u32;
+
+        let (a, b) = (basis.from_biguint(&x), basis.from_biguint(&y));
+        assert_eq!(a.to_biguint(), x);
+        assert_eq!((&a * &b).to_biguint(), (&x * &y) % &q);
+        assert_eq!(a.to_u128(), None);
+    }
+}
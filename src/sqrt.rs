@@ -0,0 +1,443 @@
+// Quadratic residues and modular square roots
+
+use crate::pow::{pow_with, PowDomain};
+use crate::prime::is_prime;
+use crate::{crt, ModularInt, ModularStorage, PowStrategy};
+
+/// Above this 2-adicity of p - 1, Cipolla's O(log p) method beats the O(s²)
+/// inner loop of Tonelli–Shanks.
+const CIPOLLA_MIN_TWO_ADICITY: u32 = 20;
+
+/// Returns the Legendre symbol (a/p) for an odd prime p: 1 if a is a nonzero square
+/// modulo p, -1 if it is not a square, and 0 if p divides a.
+///
+/// Primality of p is not checked; for composite p this is the Jacobi symbol.
+///
+/// # Panics
+///
+/// Panics if p is even.
+#[must_use]
+pub fn legendre(a: u64, p: u64) -> i8 {
+    jacobi(a, p)
+}
+
+/// Returns the Jacobi symbol (a/n) for odd n, the product of the Legendre symbols
+/// over the prime factors of n.
+///
+/// A symbol of -1 proves that a is not a square modulo n; 1 does not prove that it
+/// is one unless n is prime.
+///
+/// # Panics
+///
+/// Panics if n is even.
+#[must_use]
+pub fn jacobi(a: u64, n: u64) -> i8 {
+    assert!(n % 2 == 1, "the Jacobi symbol needs an odd modulus");
+    crate::prime::jacobi(&a, &n)
+}
+
+/// Returns both square roots of a modulo the prime p as (r, p - r) with r ≤ p - r,
+/// or `None` if a is not a square.
+///
+/// Uses a single exponentiation when p ≡ 3 (mod 4) and Atkin's formula when
+/// p ≡ 5 (mod 8); otherwise Tonelli–Shanks, or Cipolla's method when p - 1 is
+/// divisible by a large power of two.
+///
+/// Primality of p is only checked before the non-residue search of Tonelli–Shanks
+/// and Cipolla, which need not end for composite p, so composite p ≡ 1 (mod 8) give
+/// `None`. For other composite p a returned pair is still a pair of square roots, but
+/// `None` does not prove that none exist; even p > 2 always gives `None` for
+/// nonzero a.
+///
+/// ```
+/// use modularity::sqrt::sqrt_mod;
+///
+/// assert_eq!(sqrt_mod(2, 7), Some((3, 4)));
+/// assert_eq!(sqrt_mod(3, 7), None);
+/// ```
+///
+/// # Panics
+///
+/// Panics if p is zero.
+#[must_use]
+pub fn sqrt_mod(a: u64, p: u64) -> Option<(u64, u64)> {
+    let a = a % p;
+    if a == 0 || p == 2 {
+        return Some((a, a));
+    }
+    if p.is_multiple_of(2) {
+        return None;
+    }
+    if legendre(a, p) != 1 {
+        return None;
+    }
+
+    let root = if p % 4 == 3 {
+        pow(a, (p + 1) / 4, p)
+    } else if p % 8 == 5 {
+        atkin(a, p)
+    } else if !is_prime(p) {
+        return None;
+    } else if (p - 1).trailing_zeros() >= CIPOLLA_MIN_TWO_ADICITY {
+        cipolla(a, p)
+    } else {
+        tonelli_shanks(a, p)
+    };
+    if u64::mul_mod(root, root, p) != a {
+        return None;
+    }
+    let other = p - root;
+    Some((root.min(other), root.max(other)))
+}
+
+/// Returns every square root of a modulo p^k in increasing order, which is empty if
+/// a is not a square.
+///
+/// For a unit, odd prime powers have 0 or 2 roots, found by Hensel-lifting a root
+/// modulo p with Newton's iteration, and powers of two have up to 4: ±r and
+/// ±r + 2^(k-1). A non-unit a = p^(2j)·u has the roots p^j·y + t·p^(k-j) for every
+/// root y of u modulo p^(k-2j) and t < p^j, and none if p divides a an odd number of
+/// times. Zero has the p^⌊k/2⌋ multiples of p^⌈k/2⌉ as roots.
+///
+/// # Panics
+///
+/// Panics if k is zero or p^k does not fit in a `u64`.
+#[must_use]
+pub fn sqrt_mod_prime_power(a: u64, p: u64, k: u32) -> Vec<u64> {
+    assert!(k > 0, "the exponent must be positive");
+    let Some(modulus) = p.checked_pow(k) else {
+        panic!("p^k does not fit in a u64");
+    };
+    let a = a % modulus;
+    if a == 0 {
+        // x² ≡ 0 exactly when p^⌈k/2⌉ divides x
+        let step = p.pow(k.div_ceil(2));
+        return (0..modulus / step).map(|t| t * step).collect();
+    }
+
+    // a = p^valuation·unit with p not dividing unit
+    let (mut valuation, mut unit) = (0, a);
+    while unit.is_multiple_of(p) {
+        (valuation, unit) = (valuation + 1, unit / p);
+    }
+    if valuation % 2 == 1 {
+        return Vec::new();
+    }
+    // With valuation = 2j, x = p^j·y solves x² ≡ a exactly when y² ≡ unit
+    // (mod p^(k-2j)), which fixes x modulo p^(k-j)
+    let shift = p.pow(valuation / 2);
+    let period = modulus / shift;
+    let mut roots: Vec<u64> = sqrt_mod_unit(unit, p, k - valuation)
+        .into_iter()
+        .flat_map(|y| (0..shift).map(move |t| shift * y + t * period))
+        .collect();
+    roots.sort_unstable();
+    roots.dedup();
+    roots
+}
+
+/// Square roots of a unit a modulo p^k, in no particular order.
+fn sqrt_mod_unit(a: u64, p: u64, k: u32) -> Vec<u64> {
+    if p == 2 {
+        sqrt_mod_power_of_two(a, k)
+    } else {
+        let modulus = p.pow(k);
+        sqrt_mod(a, p).map_or_else(Vec::new, |(root, _)| {
+            let root = hensel_lift(a, root, modulus);
+            vec![root, modulus - root]
+        })
+    }
+}
+
+/// Returns every square root of a modulo the product of the given prime powers, in
+/// increasing order, combining the roots for each prime power with the Chinese
+/// Remainder Theorem.
+///
+/// # Panics
+///
+/// Panics if the modulus does not fit in a `u64` or a prime appears twice.
+#[must_use]
+pub fn sqrt_mod_composite(a: u64, factors: &[(u64, u32)]) -> Vec<u64> {
+    let mut roots = vec![ModularInt::new(0u64, 1)];
+    for &(p, k) in factors {
+        let Some(modulus) = p.checked_pow(k) else {
+            panic!("p^k does not fit in a u64");
+        };
+        let partial = sqrt_mod_prime_power(a, p, k);
+        roots = roots
+            .iter()
+            .flat_map(|&r| {
+                partial.iter().map(move |&s| {
+                    let Some(combined) = crt(&[r, ModularInt::new(s, modulus)]) else {
+                        panic!("prime powers must be distinct primes");
+                    };
+                    let Ok(value) = u64::try_from(combined.value()) else {
+                        panic!("the modulus does not fit in a u64");
+                    };
+                    let Ok(modulus) = u64::try_from(combined.modulus()) else {
+                        panic!("the modulus does not fit in a u64");
+                    };
+                    ModularInt::new(value, modulus)
+                })
+            })
+            .collect();
+    }
+    let mut roots: Vec<u64> = roots.iter().map(ModularInt::<u64>::value).collect();
+    roots.sort_unstable();
+    roots
+}
+
+// Square roots of a single residue
+impl ModularInt<u64> {
+    /// Returns the smaller square root of the value, or `None` if it is not a square.
+    ///
+    /// The modulus must be prime; this is not checked. Use `sqrt_mod_composite` for
+    /// composite moduli.
+    #[must_use]
+    pub fn sqrt(&self) -> Option<Self> {
+        sqrt_mod(self.value(), self.modulus()).map(|(root, _)| Self::new(root, self.modulus()))
+    }
+}
+
+/// Atkin's square root for p ≡ 5 (mod 8): with v = (2a)^((p-5)/8) and i = 2av², which
+/// is a square root of -1, the root is a·v·(i - 1).
+fn atkin(a: u64, p: u64) -> u64 {
+    let double = u64::add_mod(a, a, p);
+    let v = pow(double, (p - 5) / 8, p);
+    let i = u64::mul_mod(double, u64::mul_mod(v, v, p), p);
+    u64::mul_mod(u64::mul_mod(a, v, p), u64::sub_mod(i, 1, p), p)
+}
+
+/// Tonelli–Shanks for a quadratic residue a modulo an odd prime p.
+#[allow(clippy::many_single_char_names)] // s, q, c, r, t, m, i, b as in the usual statement
+fn tonelli_shanks(a: u64, p: u64) -> u64 {
+    let s = (p - 1).trailing_zeros();
+    let q = (p - 1) >> s;
+    let non_residue = (2..p).find(|&z| legendre(z, p) == -1).unwrap_or(2);
+
+    let mut c = pow(non_residue, q, p);
+    let mut r = pow(a, q.div_ceil(2), p);
+    let mut t = pow(a, q, p);
+    let mut m = s;
+    while t != 1 {
+        // The least i with t^(2^i) = 1, which is below m when p is prime
+        let mut i = 0;
+        let mut square = t;
+        while square != 1 {
+            square = u64::mul_mod(square, square, p);
+            i += 1;
+            if i == m {
+                return r;
+            }
+        }
+        let b = pow(c, 1 << (m - i - 1), p);
+        r = u64::mul_mod(r, b, p);
+        c = u64::mul_mod(b, b, p);
+        t = u64::mul_mod(t, c, p);
+        m = i;
+    }
+    r
+}
+
+/// Cipolla's method: with t chosen so that w = t² - a is not a square, the root is
+/// (t + √w)^((p+1)/2) computed in F_p(√w).
+fn cipolla(a: u64, p: u64) -> u64 {
+    let (t, w) = (1..p)
+        .map(|t| (t, u64::sub_mod(u64::mul_mod(t, t, p), a, p)))
+        .find(|&(_, w)| legendre(w, p) == -1)
+        .unwrap_or((0, 0));
+    let field = QuadraticExtension { p, w };
+    pow_with(&field, &(t, 1), &p.div_ceil(2), PowStrategy::Auto).0
+}
+
+/// F_p(√w) for a non-square w, with x + y√w stored as (x, y).
+struct QuadraticExtension {
+    p: u64,
+    w: u64,
+}
+
+impl PowDomain for QuadraticExtension {
+    type Element = (u64, u64);
+
+    fn one(&self) -> (u64, u64) {
+        (1, 0)
+    }
+
+    fn mul(&self, &(x1, y1): &(u64, u64), &(x2, y2): &(u64, u64)) -> (u64, u64) {
+        let p = self.p;
+        let yy = u64::mul_mod(u64::mul_mod(y1, y2, p), self.w, p);
+        (
+            u64::add_mod(u64::mul_mod(x1, x2, p), yy, p),
+            u64::add_mod(u64::mul_mod(x1, y2, p), u64::mul_mod(x2, y1, p), p),
+        )
+    }
+}
+
+/// Lifts a root of a modulo p to the odd prime power `modulus` with Newton's
+/// iteration r ← r - (r² - a)/(2r), which doubles the p-adic precision each step.
+fn hensel_lift(a: u64, root: u64, modulus: u64) -> u64 {
+    let mut r = root;
+    loop {
+        let error = u64::sub_mod(u64::mul_mod(r, r, modulus), a, modulus);
+        if error == 0 {
+            return r;
+        }
+        // 2r is a unit because p is odd and p does not divide r
+        let inverse = ModularInt::new(u64::add_mod(r, r, modulus), modulus).inverse_mod();
+        r = u64::sub_mod(r, u64::mul_mod(error, inverse.value(), modulus), modulus);
+    }
+}
+
+/// Square roots of an odd a modulo 2^k.
+fn sqrt_mod_power_of_two(a: u64, k: u32) -> Vec<u64> {
+    match k {
+        1 => vec![1],
+        2 => {
+            if a % 4 == 1 {
+                vec![1, 3]
+            } else {
+                vec![]
+            }
+        }
+        _ => {
+            if a % 8 != 1 {
+                return vec![];
+            }
+            // Keep r² ≡ a (mod 2^j), fixing bit j - 1 of r when the next bit of r² is
+            // wrong; arithmetic wraps modulo 2^64, which 2^(j+1) divides
+            let mut r = 1u64;
+            for j in 3..k {
+                let mask = (1u64 << (j + 1)) - 1;
+                if r.wrapping_mul(r).wrapping_sub(a) & mask != 0 {
+                    r += 1 << (j - 1);
+                }
+            }
+            let modulus = 1u64 << k;
+            let half = modulus >> 1;
+            let negated = modulus - r;
+            vec![r, negated, (r + half) % modulus, (negated + half) % modulus]
+        }
+    }
+}
+
+fn pow(base: u64, exponent: u64, modulus: u64) -> u64 {
+    u64::pow_mod(base, &exponent, modulus, PowStrategy::Auto)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ntt::{GOLDILOCKS, P998244353};
+
+    fn naive_roots(a: u64, n: u64) -> Vec<u64> {
+        (0..n).filter(|&r| u64::mul_mod(r, r, n) == a % n).collect()
+    }
+
+    #[test]
+    fn test_symbols_match_euler_criterion() {
+        for p in [3, 5, 7, 11, 13, 17, 41, 97] {
+            for a in 0..2 * p {
+                let euler = pow(a, (p - 1) / 2, p);
+                let expected = match euler {
+                    0 => 0,
+                    1 => 1,
+                    _ => -1,
+                };
+                assert_eq!(legendre(a, p), expected, "({a}/{p})");
+            }
+        }
+        // (2/15) = (2/3)(2/5) = 1 although 2 is not a square modulo 15
+        assert_eq!(jacobi(2, 15), 1);
+        assert!(naive_roots(2, 15).is_empty());
+        assert_eq!(jacobi(7, 15), -1);
+        assert_eq!(jacobi(5, 15), 0);
+    }
+
+    #[test]
+    fn test_sqrt_mod_small_primes() {
+        // Each residue class of p mod 8, and p - 1 = 7·2^4 for Tonelli–Shanks
+        for p in [2, 3, 5, 7, 13, 17, 41, 97, 113] {
+            for a in 0..p {
+                let roots = naive_roots(a, p);
+                match sqrt_mod(a, p) {
+                    Some((r, s)) => {
+                        assert!(r <= s);
+                        assert!(roots.contains(&r) && roots.contains(&s));
+                    }
+                    None => assert!(roots.is_empty(), "{a} mod {p}"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sqrt_mod_even_modulus() {
+        assert_eq!(sqrt_mod(3, 4), None);
+        assert_eq!(sqrt_mod(1, 8), None);
+        assert_eq!(sqrt_mod(8, 4), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_sqrt_mod_composite_returns_none() {
+        // The square of a prime is 1 mod 8, and 2 has Jacobi symbol 1 modulo it, so
+        // only the primality check stops an unbounded non-residue search
+        let q = 4_294_967_291u64;
+        assert_eq!(sqrt_mod(2, q * q), None);
+    }
+
+    #[test]
+    fn test_sqrt_mod_large_primes() {
+        let mersenne_61 = (1 << 61) - 1; // 3 mod 4
+        let p64 = u64::MAX - 58; // 2^64 - 59, 5 mod 8
+        for p in [mersenne_61, p64, P998244353, GOLDILOCKS] {
+            let mut x = 0x1234_5678_9ABC_DEF0 % p;
+            for _ in 0..20 {
+                x = u64::add_mod(u64::mul_mod(x, x, p), 12_345, p);
+                let square = u64::mul_mod(x, x, p);
+                let (r, s) = sqrt_mod(square, p).unwrap();
+                assert!(r == x || s == x);
+                assert_eq!(u64::add_mod(r, s, p), 0);
+            }
+        }
+        // Both general-case methods agree
+        for p in [P998244353, GOLDILOCKS] {
+            for a in [4, 9, 123_456_789, 1 << 40] {
+                let square = u64::mul_mod(a, a, p);
+                let t = tonelli_shanks(square, p);
+                let c = cipolla(square, p);
+                assert!(c == t || c == p - t);
+            }
+        }
+        assert_eq!(ModularInt::new(4, 7).sqrt(), Some(ModularInt::new(2, 7)));
+        assert_eq!(ModularInt::new(3, 7).sqrt(), None);
+    }
+
+    #[test]
+    fn test_prime_powers_and_composites() {
+        for (p, k) in [(3, 5), (5, 3), (7, 2), (2, 1), (2, 2), (2, 3), (2, 6)] {
+            let n = u64::pow(p, k);
+            for a in 0..n {
+                assert_eq!(
+                    sqrt_mod_prime_power(a, p, k),
+                    naive_roots(a, n),
+                    "{a} mod {p}^{k}"
+                );
+            }
+        }
+
+        let factors = [(2, 3), (3, 2), (5, 1)];
+        for a in 0..360 {
+            assert_eq!(sqrt_mod_composite(a, &factors), naive_roots(a, 360), "{a}");
+        }
+        assert_eq!(sqrt_mod_composite(0, &[(3, 2)]), vec![0, 3, 6]);
+
+        let big = [(u64::MAX - 58, 1)];
+        assert_eq!(sqrt_mod_composite(4, &big), vec![2, u64::MAX - 60]);
+        let p = 1_000_003u64;
+        let roots = sqrt_mod_prime_power(2 * 2 + p, p, 3);
+        assert_eq!(roots.len(), 2);
+        assert!(roots
+            .iter()
+            .all(|&r| u64::mul_mod(r, r, p * p * p) == 4 + p));
+    }
+}
@@ -0,0 +1,166 @@
+// Word-level modular arithmetic for the primitive unsigned integer types
+
+use crate::pow::{pow_montgomery, pow_plain, Exponent, PowStrategy};
+use crate::wide::{mul_wide_u128, rem_wide_u128};
+use num_integer::Integer;
+use num_traits::{PrimInt, Unsigned};
+use std::fmt::Debug;
+
+/// Storage types that `ModularInt<T>` can reduce without overflowing.
+///
+/// All methods take residues that are already reduced modulo `modulus` (except
+/// `reduce`, which produces one) and a non-zero `modulus`. The default
+/// implementations for addition, subtraction and negation never form a value
+/// larger than the modulus, so they are overflow-safe for every width; only
+/// multiplication needs a type-specific widening strategy.
+pub trait ModularStorage: PrimInt + Unsigned + Integer + Debug {
+    /// Reduces an arbitrary value into the range [0, modulus).
+    #[must_use]
+    fn reduce(value: Self, modulus: Self) -> Self {
+        if value >= modulus {
+            value % modulus
+        } else {
+            value
+        }
+    }
+
+    /// Computes (a + b) mod modulus.
+    #[must_use]
+    fn add_mod(a: Self, b: Self, modulus: Self) -> Self {
+        // a + b >= modulus  <=>  a >= modulus - b, and the right-hand side cannot overflow
+        let gap = modulus - b;
+        if a >= gap {
+            a - gap
+        } else {
+            a + b
+        }
+    }
+
+    /// Computes (a - b) mod modulus.
+    #[must_use]
+    fn sub_mod(a: Self, b: Self, modulus: Self) -> Self {
+        if a >= b {
+            a - b
+        } else {
+            modulus - (b - a)
+        }
+    }
+
+    /// Computes (-a) mod modulus.
+    #[must_use]
+    fn neg_mod(a: Self, modulus: Self) -> Self {
+        if a.is_zero() {
+            a
+        } else {
+            modulus - a
+        }
+    }
+
+    /// Computes (a * b) mod modulus.
+    #[must_use]
+    fn mul_mod(a: Self, b: Self, modulus: Self) -> Self;
+
+    /// Computes base^exponent mod modulus with the given strategy.
+    ///
+    /// The default multiplies with `mul_mod`; types with a Montgomery context
+    /// override it to run the loop in Montgomery form for odd moduli.
+    #[must_use]
+    fn pow_mod<E: Exponent + ?Sized>(
+        base: Self,
+        exponent: &E,
+        modulus: Self,
+        strategy: PowStrategy,
+    ) -> Self {
+        pow_plain(base, exponent, modulus, strategy)
+    }
+}
+
+/// Implements `ModularStorage` for a type by widening products into a type twice as wide.
+macro_rules! impl_modular_storage_widening {
+    ($($t:ty => $wide:ty),* $(,)?) => {
+        $(
+            impl ModularStorage for $t {
+                #[allow(clippy::cast_possible_truncation)] // the remainder is below the modulus
+                fn mul_mod(a: Self, b: Self, modulus: Self) -> Self {
+                    (<$wide>::from(a) * <$wide>::from(b) % <$wide>::from(modulus)) as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_modular_storage_widening!(u8 => u16, u16 => u32, u32 => u64);
+
+impl ModularStorage for u64 {
+    #[allow(clippy::cast_possible_truncation)] // the remainder is below the modulus
+    fn mul_mod(a: Self, b: Self, modulus: Self) -> Self {
+        (u128::from(a) * u128::from(b) % u128::from(modulus)) as Self
+    }
+
+    fn pow_mod<E: Exponent + ?Sized>(
+        base: Self,
+        exponent: &E,
+        modulus: Self,
+        strategy: PowStrategy,
+    ) -> Self {
+        pow_montgomery(base, exponent, modulus, strategy)
+    }
+}
+
+impl ModularStorage for usize {
+    fn mul_mod(a: Self, b: Self, modulus: Self) -> Self {
+        (a as u128 * b as u128 % modulus as u128) as Self
+    }
+}
+
+impl ModularStorage for u128 {
+    fn mul_mod(a: Self, b: Self, modulus: Self) -> Self {
+        // Products of two 64-bit values still fit in a u128
+        if (a | b) >> 64 == 0 {
+            return a * b % modulus;
+        }
+
+        // Otherwise form the full 256-bit product and reduce it
+        let (high, low) = mul_wide_u128(a, b);
+        rem_wide_u128(high, low, modulus)
+    }
+
+    fn pow_mod<E: Exponent + ?Sized>(
+        base: Self,
+        exponent: &E,
+        modulus: Self,
+        strategy: PowStrategy,
+    ) -> Self {
+        pow_montgomery(base, exponent, modulus, strategy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_sub_near_type_max() {
+        let m = u64::MAX;
+        assert_eq!(u64::add_mod(m - 1, m - 1, m), m - 2);
+        assert_eq!(u64::sub_mod(0, m - 1, m), 1);
+        assert_eq!(u64::neg_mod(0, m), 0);
+        assert_eq!(u64::neg_mod(1, m), m - 1);
+
+        let m = u8::MAX;
+        assert_eq!(u8::add_mod(200, 100, m), 45);
+        assert_eq!(u8::mul_mod(200, 100, m), 110);
+    }
+
+    #[test]
+    fn test_u128_mul_mod() {
+        let m = u128::MAX - 158; // 2^128 - 159 is prime
+        let a = m - 1;
+        // (-1) * (-1) = 1
+        assert_eq!(u128::mul_mod(a, a, m), 1);
+        assert_eq!(
+            u128::mul_mod(u128::from(u64::MAX), 1 << 64, m),
+            u128::from(u64::MAX) << 64
+        );
+    }
+}
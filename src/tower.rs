@@ -0,0 +1,747 @@
+// Extension towers GF(p²) ⊂ GF(p⁶) ⊂ GF(p¹²) for pairing arithmetic
+
+use crate::field::{FieldDomain, Fp};
+use crate::{pow_with, Exponent, ModularError, PowStrategy};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// An element c0 + c1·u of GF(P²) = GF(P)[u]/(u² - BETA).
+///
+/// BETA must be a quadratic non-residue modulo P, which is checked at compile time;
+/// for P ≡ 3 (mod 4), BETA = P - 1 gives u² = -1. Products use Karatsuba (three base
+/// field products) and squares the complex method (two). The Frobenius map is
+/// conjugation, so the norm, inverse and square root all reduce to GF(P).
+///
+/// ```
+/// use modularity::field::Fp;
+/// use modularity::tower::Fp2;
+///
+/// type F = Fp2<19, 18>; // u² = -1
+/// let a = F::new(Fp::new(3), Fp::new(4));
+/// assert_eq!(a.norm(), Fp::new(25));
+/// assert_eq!(a * a.inverse(), F::ONE);
+/// assert_eq!(a.frobenius(), a.pow(19u64));
+/// ```
+///
+/// ```compile_fail
+/// use modularity::tower::Fp2;
+///
+/// let _ = Fp2::<19, 4>::ONE; // 4 is a square modulo 19
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fp2<const P: u64, const BETA: u64> {
+    c0: Fp<P>,
+    c1: Fp<P>,
+}
+
+/// An element c0 + c1·v + c2·v² of GF(P⁶) = GF(P²)[v]/(v³ - ξ) with ξ = XI0 + XI1·u.
+///
+/// ξ must be a cubic non-residue in GF(P²) and P ≡ 1 (mod 3), both checked at
+/// compile time, so that the Frobenius map multiplies v by the constant ξ^((P-1)/3).
+/// Products use the Karatsuba formulas of Devegili et al. (six `Fp2` products) and
+/// squares Chung–Hasan SQR2 (two products and three squares).
+///
+/// ```compile_fail
+/// use modularity::tower::Fp6;
+///
+/// let _ = Fp6::<19, 18, 0, 0>::default(); // ξ = 0 is not a cubic non-residue
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fp6<const P: u64, const BETA: u64, const XI0: u64, const XI1: u64> {
+    c0: Fp2<P, BETA>,
+    c1: Fp2<P, BETA>,
+    c2: Fp2<P, BETA>,
+}
+
+/// An element c0 + c1·w of GF(P¹²) = GF(P⁶)[w]/(w² - v).
+///
+/// Needs ξ to be a quadratic non-residue in GF(P²) as well, so that v is not a
+/// square in GF(P⁶); this is checked at compile time. This is the tower used by BN
+/// and BLS12 pairings, whose final exponentiation relies on `conjugate` (the
+/// Frobenius map to the sixth power) and `frobenius`.
+///
+/// ```
+/// use modularity::tower::Fp12;
+///
+/// // p = 2^63 - 25 ≡ 7 (mod 12), with u² = -1 and ξ = 3 + u
+/// const P: u64 = 9_223_372_036_854_775_783;
+/// type F = Fp12<P, { P - 1 }, 3, 1>;
+///
+/// let a = F::ONE.frobenius() + F::ONE + F::ONE;
+/// assert_eq!(a * a.inverse(), F::ONE);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fp12<const P: u64, const BETA: u64, const XI0: u64, const XI1: u64> {
+    c0: Fp6<P, BETA, XI0, XI1>,
+    c1: Fp6<P, BETA, XI0, XI1>,
+}
+
+impl<const P: u64, const BETA: u64> Fp2<P, BETA> {
+    /// Rejects a BETA for which u² - BETA is reducible when the type is instantiated.
+    const VALID_FIELD: () = assert!(
+        Fp::<P>::new(BETA).pow_const(((P - 1) / 2) as u128).value() == P - 1,
+        "BETA must be a quadratic non-residue modulo P"
+    );
+
+    /// u² as a base field element.
+    const NON_RESIDUE: Fp<P> = Fp::new(BETA);
+
+    /// 1/2 in the base field.
+    const HALF: Fp<P> = Fp::new(P.div_ceil(2));
+
+    /// The additive identity.
+    pub const ZERO: Self = Self::new(Fp::ZERO, Fp::ZERO);
+
+    /// The multiplicative identity.
+    pub const ONE: Self = Self::new(Fp::ONE, Fp::ZERO);
+
+    /// Creates the element c0 + c1·u.
+    #[must_use]
+    pub const fn new(c0: Fp<P>, c1: Fp<P>) -> Self {
+        let () = Self::VALID_FIELD;
+        Self { c0, c1 }
+    }
+
+    /// Returns the constant coefficient.
+    #[must_use]
+    pub const fn c0(self) -> Fp<P> {
+        self.c0
+    }
+
+    /// Returns the coefficient of u.
+    #[must_use]
+    pub const fn c1(self) -> Fp<P> {
+        self.c1
+    }
+
+    /// Returns true if this is the zero element.
+    #[must_use]
+    pub const fn is_zero(self) -> bool {
+        self.c0.is_zero() && self.c1.is_zero()
+    }
+
+    /// Computes self² by the complex method.
+    #[must_use]
+    pub const fn square(self) -> Self {
+        // (c0 + c1·u)² = (c0 + c1)(c0 + β·c1) - (1 + β)·c0·c1 + 2·c0·c1·u
+        let v = self.c0.mul_const(self.c1);
+        let c0 = self
+            .c0
+            .add_const(self.c1)
+            .mul_const(self.c0.add_const(Self::NON_RESIDUE.mul_const(self.c1)))
+            .sub_const(v)
+            .sub_const(Self::NON_RESIDUE.mul_const(v));
+        Self::new(c0, v.add_const(v))
+    }
+
+    /// Returns c0 - c1·u, the image under the Frobenius map.
+    #[must_use]
+    pub const fn conjugate(self) -> Self {
+        Self::new(self.c0, self.c1.neg_const())
+    }
+
+    /// Applies the Frobenius automorphism a ↦ a^P, which is conjugation.
+    #[must_use]
+    pub const fn frobenius(self) -> Self {
+        self.conjugate()
+    }
+
+    /// Returns the norm to GF(P), c0² - β·c1².
+    #[must_use]
+    pub const fn norm(self) -> Fp<P> {
+        self.c0
+            .square()
+            .sub_const(Self::NON_RESIDUE.mul_const(self.c1.square()))
+    }
+
+    /// Returns the trace to GF(P), 2·c0.
+    #[must_use]
+    pub const fn trace(self) -> Fp<P> {
+        self.c0.add_const(self.c0)
+    }
+
+    /// Computes self^exponent.
+    ///
+    /// Accepts any `Exponent`, including `u64` limb slices and `BigUint`.
+    #[must_use]
+    pub fn pow<E: Exponent>(self, exponent: E) -> Self {
+        pow_with(&FieldDomain(Self::ONE), &self, &exponent, PowStrategy::Auto)
+    }
+
+    /// Computes the multiplicative inverse.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element is zero.
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        match self.checked_inverse() {
+            Ok(inverse) => inverse,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Computes the multiplicative inverse, conjugate / norm, returning an error for
+    /// zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::NotInvertible` if the element is zero.
+    pub fn checked_inverse(self) -> Result<Self, ModularError> {
+        let norm_inverse = self.norm().checked_inverse()?;
+        Ok(self.conjugate().scale(norm_inverse))
+    }
+
+    /// Returns true if the element is a square, including zero.
+    #[must_use]
+    pub fn is_square(self) -> bool {
+        self.norm().legendre() != -1
+    }
+
+    /// Returns a square root, or `None` if the element is not a square.
+    ///
+    /// Takes λ = √N(a) in GF(P); then one of δ = (c0 ± λ)/2 is a square and the root
+    /// is √δ + c1/(2√δ)·u. The other root is its negation.
+    #[must_use]
+    pub fn sqrt(self) -> Option<Self> {
+        if self.c1.is_zero() {
+            // Every base field element is a square in GF(P²): c0 = r² or c0 = β·r²
+            return self.c0.sqrt().map_or_else(
+                || {
+                    (self.c0 * Self::NON_RESIDUE.inverse())
+                        .sqrt()
+                        .map(|r| Self::new(Fp::ZERO, r))
+                },
+                |r| Some(Self::new(r, Fp::ZERO)),
+            );
+        }
+        let lambda = self.norm().sqrt()?;
+        let mut delta = (self.c0 + lambda) * Self::HALF;
+        if delta.legendre() == -1 {
+            delta = (self.c0 - lambda) * Self::HALF;
+        }
+        let x0 = delta.sqrt()?;
+        let x1 = self.c1 * Self::HALF * x0.inverse();
+        Some(Self::new(x0, x1))
+    }
+
+    /// Multiplies both coefficients by a base field element.
+    #[must_use]
+    pub const fn scale(self, k: Fp<P>) -> Self {
+        Self::new(self.c0.mul_const(k), self.c1.mul_const(k))
+    }
+
+    const fn mul_const(self, other: Self) -> Self {
+        let v0 = self.c0.mul_const(other.c0);
+        let v1 = self.c1.mul_const(other.c1);
+        let c1 = self
+            .c0
+            .add_const(self.c1)
+            .mul_const(other.c0.add_const(other.c1))
+            .sub_const(v0)
+            .sub_const(v1);
+        Self::new(v0.add_const(Self::NON_RESIDUE.mul_const(v1)), c1)
+    }
+
+    /// Square-and-multiply for compile-time constants.
+    const fn pow_const(self, mut exponent: u128) -> Self {
+        let (mut base, mut result) = (self, Self::ONE);
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.mul_const(base);
+            }
+            base = base.square();
+            exponent >>= 1;
+        }
+        result
+    }
+
+    const fn is_one(self) -> bool {
+        self.c0.value() == 1 && self.c1.is_zero()
+    }
+}
+
+impl<const P: u64, const BETA: u64> From<Fp<P>> for Fp2<P, BETA> {
+    fn from(value: Fp<P>) -> Self {
+        Self::new(value, Fp::ZERO)
+    }
+}
+
+impl<const P: u64, const BETA: u64, const XI0: u64, const XI1: u64> Fp6<P, BETA, XI0, XI1> {
+    /// Rejects a ξ for which v³ - ξ is reducible when the type is instantiated.
+    const VALID_FIELD: () = assert!(
+        P % 3 == 1
+            && !Self::XI.is_zero()
+            && !Self::XI.pow_const((P as u128 * P as u128 - 1) / 3).is_one(),
+        "XI0 + XI1·u must be a cubic non-residue in GF(P²), with P ≡ 1 (mod 3)"
+    );
+
+    /// v³ = ξ.
+    const XI: Fp2<P, BETA> = Fp2::new(Fp::new(XI0), Fp::new(XI1));
+
+    /// ξ^((P-1)/3), so that v^P = FROBENIUS_V·v.
+    const FROBENIUS_V: Fp2<P, BETA> = Self::XI.pow_const(((P - 1) / 3) as u128);
+
+    /// ξ^(2(P-1)/3), so that (v²)^P = FROBENIUS_V2·v².
+    const FROBENIUS_V2: Fp2<P, BETA> = Self::FROBENIUS_V.square();
+
+    /// The additive identity.
+    pub const ZERO: Self = Self::new(Fp2::ZERO, Fp2::ZERO, Fp2::ZERO);
+
+    /// The multiplicative identity.
+    pub const ONE: Self = Self::new(Fp2::ONE, Fp2::ZERO, Fp2::ZERO);
+
+    /// Creates the element c0 + c1·v + c2·v².
+    #[must_use]
+    pub const fn new(c0: Fp2<P, BETA>, c1: Fp2<P, BETA>, c2: Fp2<P, BETA>) -> Self {
+        let () = Self::VALID_FIELD;
+        Self { c0, c1, c2 }
+    }
+
+    /// Returns the constant coefficient.
+    #[must_use]
+    pub const fn c0(self) -> Fp2<P, BETA> {
+        self.c0
+    }
+
+    /// Returns the coefficient of v.
+    #[must_use]
+    pub const fn c1(self) -> Fp2<P, BETA> {
+        self.c1
+    }
+
+    /// Returns the coefficient of v².
+    #[must_use]
+    pub const fn c2(self) -> Fp2<P, BETA> {
+        self.c2
+    }
+
+    /// Returns true if this is the zero element.
+    #[must_use]
+    pub const fn is_zero(self) -> bool {
+        self.c0.is_zero() && self.c1.is_zero() && self.c2.is_zero()
+    }
+
+    /// Computes self² by Chung–Hasan SQR2.
+    #[must_use]
+    pub fn square(self) -> Self {
+        let s0 = self.c0.square();
+        let ab = self.c0 * self.c1;
+        let s1 = ab + ab;
+        let s2 = (self.c0 - self.c1 + self.c2).square();
+        let bc = self.c1 * self.c2;
+        let s3 = bc + bc;
+        let s4 = self.c2.square();
+        Self::new(
+            s0 + Self::XI * s3,
+            s1 + Self::XI * s4,
+            s1 + s2 + s3 - s0 - s4,
+        )
+    }
+
+    /// Multiplies by v, the generator of the extension: (c0, c1, c2) ↦ (ξ·c2, c0, c1).
+    #[must_use]
+    pub fn mul_by_v(self) -> Self {
+        Self::new(Self::XI * self.c2, self.c0, self.c1)
+    }
+
+    /// Multiplies every coefficient by an element of GF(P²).
+    #[must_use]
+    pub fn scale(self, k: Fp2<P, BETA>) -> Self {
+        Self::new(self.c0 * k, self.c1 * k, self.c2 * k)
+    }
+
+    /// Applies the Frobenius automorphism a ↦ a^P.
+    #[must_use]
+    pub fn frobenius(self) -> Self {
+        Self::new(
+            self.c0.frobenius(),
+            self.c1.frobenius() * Self::FROBENIUS_V,
+            self.c2.frobenius() * Self::FROBENIUS_V2,
+        )
+    }
+
+    /// Computes self^exponent.
+    ///
+    /// Accepts any `Exponent`, including `u64` limb slices and `BigUint`.
+    #[must_use]
+    pub fn pow<E: Exponent>(self, exponent: E) -> Self {
+        pow_with(&FieldDomain(Self::ONE), &self, &exponent, PowStrategy::Auto)
+    }
+
+    /// Computes the multiplicative inverse.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element is zero.
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        match self.checked_inverse() {
+            Ok(inverse) => inverse,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Computes the multiplicative inverse, returning an error for zero.
+    ///
+    /// Uses the adjugate of the multiplication matrix, so the only inversion is in
+    /// GF(P²).
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::NotInvertible` if the element is zero.
+    pub fn checked_inverse(self) -> Result<Self, ModularError> {
+        let t0 = self.c0.square() - Self::XI * (self.c1 * self.c2);
+        let t1 = Self::XI * self.c2.square() - self.c0 * self.c1;
+        let t2 = self.c1.square() - self.c0 * self.c2;
+        let determinant = self.c0 * t0 + Self::XI * (self.c2 * t1 + self.c1 * t2);
+        Ok(Self::new(t0, t1, t2).scale(determinant.checked_inverse()?))
+    }
+}
+
+impl<const P: u64, const BETA: u64, const XI0: u64, const XI1: u64> Fp12<P, BETA, XI0, XI1> {
+    /// Rejects a ξ for which w² - v is reducible when the type is instantiated.
+    const VALID_FIELD: () = assert!(
+        !Fp6::<P, BETA, XI0, XI1>::XI
+            .pow_const((P as u128 * P as u128 - 1) / 2)
+            .is_one(),
+        "XI0 + XI1·u must be a quadratic non-residue in GF(P²)"
+    );
+
+    /// ξ^((P-1)/6), so that w^P = FROBENIUS_W·w.
+    const FROBENIUS_W: Fp2<P, BETA> = Fp6::<P, BETA, XI0, XI1>::XI.pow_const(((P - 1) / 6) as u128);
+
+    /// The additive identity.
+    pub const ZERO: Self = Self::new(Fp6::ZERO, Fp6::ZERO);
+
+    /// The multiplicative identity.
+    pub const ONE: Self = Self::new(Fp6::ONE, Fp6::ZERO);
+
+    /// Creates the element c0 + c1·w.
+    #[must_use]
+    pub const fn new(c0: Fp6<P, BETA, XI0, XI1>, c1: Fp6<P, BETA, XI0, XI1>) -> Self {
+        let () = Self::VALID_FIELD;
+        Self { c0, c1 }
+    }
+
+    /// Returns the constant coefficient.
+    #[must_use]
+    pub const fn c0(self) -> Fp6<P, BETA, XI0, XI1> {
+        self.c0
+    }
+
+    /// Returns the coefficient of w.
+    #[must_use]
+    pub const fn c1(self) -> Fp6<P, BETA, XI0, XI1> {
+        self.c1
+    }
+
+    /// Returns true if this is the zero element.
+    #[must_use]
+    pub const fn is_zero(self) -> bool {
+        self.c0.is_zero() && self.c1.is_zero()
+    }
+
+    /// Computes self² by the complex method.
+    #[must_use]
+    pub fn square(self) -> Self {
+        // (c0 + c1·w)² = (c0 + c1)(c0 + v·c1) - (1 + v)·c0·c1 + 2·c0·c1·w
+        let product = self.c0 * self.c1;
+        let c0 =
+            (self.c0 + self.c1) * (self.c0 + self.c1.mul_by_v()) - product - product.mul_by_v();
+        Self::new(c0, product + product)
+    }
+
+    /// Returns c0 - c1·w, which is a^(P⁶); for elements of norm 1, such as pairing
+    /// values after the easy part of the final exponentiation, it is the inverse.
+    #[must_use]
+    pub fn conjugate(self) -> Self {
+        Self::new(self.c0, -self.c1)
+    }
+
+    /// Applies the Frobenius automorphism a ↦ a^P.
+    #[must_use]
+    pub fn frobenius(self) -> Self {
+        Self::new(
+            self.c0.frobenius(),
+            self.c1.frobenius().scale(Self::FROBENIUS_W),
+        )
+    }
+
+    /// Computes self^exponent.
+    ///
+    /// Accepts any `Exponent`, including `u64` limb slices and `BigUint`.
+    #[must_use]
+    pub fn pow<E: Exponent>(self, exponent: E) -> Self {
+        pow_with(&FieldDomain(Self::ONE), &self, &exponent, PowStrategy::Auto)
+    }
+
+    /// Computes the multiplicative inverse.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element is zero.
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        match self.checked_inverse() {
+            Ok(inverse) => inverse,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Computes the multiplicative inverse, conjugate / (c0² - v·c1²), returning an
+    /// error for zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModularError::NotInvertible` if the element is zero.
+    pub fn checked_inverse(self) -> Result<Self, ModularError> {
+        let norm = self.c0.square() - self.c1.square().mul_by_v();
+        let norm_inverse = norm.checked_inverse()?;
+        Ok(Self::new(self.c0 * norm_inverse, -(self.c1 * norm_inverse)))
+    }
+}
+
+// Zero elements, built through `new` so that the field parameters are checked
+impl<const P: u64, const BETA: u64> Default for Fp2<P, BETA> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<const P: u64, const BETA: u64, const XI0: u64, const XI1: u64> Default
+    for Fp6<P, BETA, XI0, XI1>
+{
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<const P: u64, const BETA: u64, const XI0: u64, const XI1: u64> Default
+    for Fp12<P, BETA, XI0, XI1>
+{
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+// Multiplication by Karatsuba: three Fp products
+impl<const P: u64, const BETA: u64> Mul for Fp2<P, BETA> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        self.mul_const(other)
+    }
+}
+
+// Multiplication by Karatsuba: six Fp2 products
+impl<const P: u64, const BETA: u64, const XI0: u64, const XI1: u64> Mul for Fp6<P, BETA, XI0, XI1> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        let v0 = self.c0 * other.c0;
+        let v1 = self.c1 * other.c1;
+        let v2 = self.c2 * other.c2;
+        let c0 = v0 + Self::XI * ((self.c1 + self.c2) * (other.c1 + other.c2) - v1 - v2);
+        let c1 = (self.c0 + self.c1) * (other.c0 + other.c1) - v0 - v1 + Self::XI * v2;
+        let c2 = (self.c0 + self.c2) * (other.c0 + other.c2) - v0 - v2 + v1;
+        Self::new(c0, c1, c2)
+    }
+}
+
+// Multiplication by Karatsuba: three Fp6 products
+impl<const P: u64, const BETA: u64, const XI0: u64, const XI1: u64> Mul
+    for Fp12<P, BETA, XI0, XI1>
+{
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        let v0 = self.c0 * other.c0;
+        let v1 = self.c1 * other.c1;
+        let c1 = (self.c0 + self.c1) * (other.c0 + other.c1) - v0 - v1;
+        Self::new(v0 + v1.mul_by_v(), c1)
+    }
+}
+
+/// Implements the coefficient-wise operators, and `MulAssign` through `Mul`.
+macro_rules! impl_tower_ops {
+    ($($name:ident<$($param:ident),*> { $($field:ident),* }),* $(,)?) => {
+        $(
+            impl<$(const $param: u64),*> Add for $name<$($param),*> {
+                type Output = Self;
+
+                fn add(self, other: Self) -> Self::Output {
+                    Self::new($(self.$field + other.$field),*)
+                }
+            }
+
+            impl<$(const $param: u64),*> AddAssign for $name<$($param),*> {
+                fn add_assign(&mut self, other: Self) {
+                    *self = *self + other;
+                }
+            }
+
+            impl<$(const $param: u64),*> Sub for $name<$($param),*> {
+                type Output = Self;
+
+                fn sub(self, other: Self) -> Self::Output {
+                    Self::new($(self.$field - other.$field),*)
+                }
+            }
+
+            impl<$(const $param: u64),*> SubAssign for $name<$($param),*> {
+                fn sub_assign(&mut self, other: Self) {
+                    *self = *self - other;
+                }
+            }
+
+            impl<$(const $param: u64),*> MulAssign for $name<$($param),*> {
+                fn mul_assign(&mut self, other: Self) {
+                    *self = *self * other;
+                }
+            }
+
+            impl<$(const $param: u64),*> Neg for $name<$($param),*> {
+                type Output = Self;
+
+                fn neg(self) -> Self::Output {
+                    Self::new($(-self.$field),*)
+                }
+            }
+        )*
+    };
+}
+
+impl_tower_ops!(
+    Fp2<P, BETA> { c0, c1 },
+    Fp6<P, BETA, XI0, XI1> { c0, c1, c2 },
+    Fp12<P, BETA, XI0, XI1> { c0, c1 },
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::ExtFieldContext;
+
+    // 2^63 - 25 ≡ 7 (mod 12), with ξ = 3 + u
+    const Q: u64 = 9_223_372_036_854_775_783;
+    type Q2 = Fp2<Q, { Q - 1 }>;
+    type Q6 = Fp6<Q, { Q - 1 }, 3, 1>;
+    type Q12 = Fp12<Q, { Q - 1 }, 3, 1>;
+
+    // 19 ≡ 7 (mod 12), with ξ = 1 + u; 19^12 - 1 fits in a u64
+    type S6 = Fp6<19, 18, 1, 1>;
+    type S12 = Fp12<19, 18, 1, 1>;
+
+    fn samples<const P: u64, const BETA: u64>(count: usize) -> Vec<Fp2<P, BETA>> {
+        let mut state = 0x0123_4567_89AB_CDEFu64;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(0x5851_F42D_4C95_7F2D)
+                .wrapping_add(0x1405_7B7E_F767_814F);
+            Fp::new(state)
+        };
+        (0..count).map(|_| Fp2::new(next(), next())).collect()
+    }
+
+    fn fp6<const P: u64, const BETA: u64, const XI0: u64, const XI1: u64>(
+        c: &[Fp2<P, BETA>],
+    ) -> Fp6<P, BETA, XI0, XI1> {
+        Fp6::new(c[0], c[1], c[2])
+    }
+
+    fn fp12<const P: u64, const BETA: u64, const XI0: u64, const XI1: u64>(
+        c: &[Fp2<P, BETA>],
+    ) -> Fp12<P, BETA, XI0, XI1> {
+        Fp12::new(fp6(&c[..3]), fp6(&c[3..]))
+    }
+
+    #[test]
+    fn test_fp2_matches_generic_extension() {
+        // GF(Q²) as GF(Q)[x]/(x² + 1)
+        let ctx = ExtFieldContext::<Q, 2>::new(&[1, 0]);
+        let embed = |a: Q2| ctx.element([a.c0(), a.c1()]);
+        let values = samples::<Q, { Q - 1 }>(16);
+        for pair in values.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            assert_eq!(embed(a * b), embed(a) * embed(b));
+            assert_eq!(a.square(), a * a);
+            assert_eq!(embed(a.inverse()), embed(a).inverse());
+            assert_eq!(a.norm(), embed(a).norm());
+            assert_eq!(a.trace(), embed(a).trace());
+            assert_eq!(embed(a.frobenius()), embed(a).frobenius());
+            let root = a.square().sqrt().unwrap();
+            assert!(root == a || root == -a);
+        }
+        assert_eq!(Q2::ZERO.checked_inverse(), Err(ModularError::NotInvertible));
+    }
+
+    #[test]
+    fn test_fp2_sqrt_exhaustive() {
+        fn check<const P: u64, const BETA: u64>() {
+            let mut squares = 0;
+            for c0 in 0..P {
+                for c1 in 0..P {
+                    let a = Fp2::<P, BETA>::new(Fp::new(c0), Fp::new(c1));
+                    assert_eq!(a.is_square(), a.pow(P * P / 2) != -Fp2::ONE);
+                    if let Some(root) = a.sqrt() {
+                        assert_eq!(root.square(), a);
+                        squares += 1;
+                    }
+                    assert_eq!(a.sqrt().is_some(), a.is_square());
+                }
+            }
+            // Zero and half of the units
+            assert_eq!(squares, (P * P).div_ceil(2));
+        }
+        check::<19, 18>(); // u² = -1
+        check::<13, 2>(); // P ≡ 1 (mod 4), u² = 2
+    }
+
+    #[test]
+    fn test_fp6() {
+        let values = samples::<Q, { Q - 1 }>(9);
+        let (a, b, c): (Q6, Q6, Q6) = (fp6(&values[..3]), fp6(&values[3..6]), fp6(&values[6..]));
+        assert_eq!(a * (b + c), a * b + a * c);
+        assert_eq!((a * b) * c, a * (b * c));
+        assert_eq!(a.square(), a * a);
+        assert_eq!(a * a.inverse(), Q6::ONE);
+        assert_eq!(a.frobenius(), a.pow(Q));
+        let v = Q6::new(Q2::ZERO, Q2::ONE, Q2::ZERO);
+        assert_eq!(a.mul_by_v(), a * v);
+        assert_eq!(Q6::ZERO.checked_inverse(), Err(ModularError::NotInvertible));
+
+        // The multiplicative group of GF(19⁶) has order 19⁶ - 1
+        let a: S6 = fp6(&samples::<19, 18>(3));
+        assert_eq!(a.pow(19u64.pow(6) - 1), S6::ONE);
+        assert_eq!(a.frobenius(), a.pow(19u64));
+    }
+
+    #[test]
+    fn test_fp12() {
+        let values = samples::<Q, { Q - 1 }>(18);
+        let (a, b, c): (Q12, Q12, Q12) = (
+            fp12(&values[..6]),
+            fp12(&values[6..12]),
+            fp12(&values[12..]),
+        );
+        assert_eq!(a * (b + c), a * b + a * c);
+        assert_eq!((a * b) * c, a * (b * c));
+        assert_eq!(a.square(), a * a);
+        assert_eq!(a * a.inverse(), Q12::ONE);
+        assert_eq!(a.frobenius(), a.pow(Q));
+        let sixth = (0..6).fold(a, |x, _| x.frobenius());
+        assert_eq!(sixth, a.conjugate());
+        assert_eq!((0..12).fold(a, |x, _| x.frobenius()), a);
+
+        let a: S12 = fp12(&samples::<19, 18>(6));
+        assert_eq!(a.pow(19u64.pow(12) - 1), S12::ONE);
+        assert_eq!(a.frobenius(), a.pow(19u64));
+        // Norm 1 after raising to P⁶ - 1, where the conjugate is the inverse
+        let unitary = a.conjugate() * a.inverse();
+        assert_eq!(unitary.conjugate(), unitary.inverse());
+        assert_eq!(
+            Q12::ZERO.checked_inverse(),
+            Err(ModularError::NotInvertible)
+        );
+    }
+}
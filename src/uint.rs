@@ -0,0 +1,555 @@
+// Fixed-size multi-limb unsigned integers
+
+use crate::wide::mul_add_carry;
+use crate::Exponent;
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Limb count from which `mul_wide` switches from schoolbook to Karatsuba.
+///
+/// Below this size the extra additions of Karatsuba cost more than the
+/// multiplications they save.
+const KARATSUBA_THRESHOLD: usize = 16;
+
+/// An unsigned integer of `64 * LIMBS` bits, stored inline as little-endian limbs.
+///
+/// `Uint` never allocates, so it suits embedded targets and hot paths where
+/// `BigUint` is too slow. Arithmetic is explicit about overflow: the
+/// `overflowing_`, `wrapping_` and `checked_` methods mirror the primitive integers,
+/// and `mul_wide` returns the full double-width product. Modular multiplication and
+/// exponentiation go through `MontgomeryContext<Uint<LIMBS>>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Uint<const LIMBS: usize> {
+    limbs: [u64; LIMBS],
+}
+
+/// A 256-bit unsigned integer.
+pub type U256 = Uint<4>;
+/// A 384-bit unsigned integer.
+pub type U384 = Uint<6>;
+/// A 512-bit unsigned integer.
+pub type U512 = Uint<8>;
+/// A 1024-bit unsigned integer.
+pub type U1024 = Uint<16>;
+
+impl<const LIMBS: usize> Uint<LIMBS> {
+    /// The value 0.
+    pub const ZERO: Self = Self { limbs: [0; LIMBS] };
+
+    /// The value 1.
+    pub const ONE: Self = Self::from_u64(1);
+
+    /// The largest value, 2^BITS - 1.
+    pub const MAX: Self = Self {
+        limbs: [u64::MAX; LIMBS],
+    };
+
+    /// The size of this integer type in bits.
+    pub const BITS: usize = 64 * LIMBS;
+
+    /// Creates a value from a single word.
+    #[must_use]
+    pub const fn from_u64(value: u64) -> Self {
+        let mut limbs = [0; LIMBS];
+        if LIMBS > 0 {
+            limbs[0] = value;
+        }
+        Self { limbs }
+    }
+
+    /// Creates a value from little-endian 64-bit limbs.
+    #[must_use]
+    pub const fn from_words(limbs: [u64; LIMBS]) -> Self {
+        Self { limbs }
+    }
+
+    /// Returns the little-endian 64-bit limbs.
+    #[must_use]
+    pub const fn as_words(&self) -> &[u64; LIMBS] {
+        &self.limbs
+    }
+
+    /// Parses a big-endian hexadecimal string, which may be shorter than the type.
+    ///
+    /// Usable in constants, e.g. for curve parameters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string contains a non-hex character or does not fit in `BITS`.
+    #[must_use]
+    pub const fn from_be_hex(hex: &str) -> Self {
+        let bytes = hex.as_bytes();
+        assert!(bytes.len() <= 16 * LIMBS, "hex string too long for Uint");
+
+        let mut limbs = [0; LIMBS];
+        let mut i = 0;
+        while i < bytes.len() {
+            let digit = match bytes[bytes.len() - 1 - i] {
+                c @ b'0'..=b'9' => c - b'0',
+                c @ b'a'..=b'f' => c - b'a' + 10,
+                c @ b'A'..=b'F' => c - b'A' + 10,
+                _ => panic!("invalid hex digit"),
+            };
+            limbs[i / 16] |= (digit as u64) << (4 * (i % 16));
+            i += 1;
+        }
+        Self { limbs }
+    }
+
+    /// Returns true if the value is 0.
+    #[must_use]
+    pub const fn is_zero(&self) -> bool {
+        let mut i = 0;
+        while i < LIMBS {
+            if self.limbs[i] != 0 {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Returns true if the value is odd.
+    #[must_use]
+    pub const fn is_odd(&self) -> bool {
+        LIMBS > 0 && self.limbs[0] & 1 == 1
+    }
+
+    /// Returns the number of significant bits, 0 for the value 0.
+    #[must_use]
+    pub const fn bits(&self) -> usize {
+        let mut i = LIMBS;
+        while i > 0 {
+            i -= 1;
+            if self.limbs[i] != 0 {
+                return 64 * i + (64 - self.limbs[i].leading_zeros() as usize);
+            }
+        }
+        0
+    }
+
+    /// Returns bit `index`, counting from the least significant bit.
+    #[must_use]
+    pub const fn bit(&self, index: usize) -> bool {
+        index < Self::BITS && (self.limbs[index / 64] >> (index % 64)) & 1 == 1
+    }
+
+    /// Computes self + rhs, returning the wrapped sum and whether it overflowed.
+    #[must_use]
+    pub const fn overflowing_add(&self, rhs: &Self) -> (Self, bool) {
+        let mut limbs = [0; LIMBS];
+        let mut carry = false;
+        let mut i = 0;
+        while i < LIMBS {
+            let (sum, carry_1) = self.limbs[i].overflowing_add(rhs.limbs[i]);
+            let (sum, carry_2) = sum.overflowing_add(carry as u64);
+            limbs[i] = sum;
+            carry = carry_1 | carry_2;
+            i += 1;
+        }
+        (Self { limbs }, carry)
+    }
+
+    /// Computes self - rhs, returning the wrapped difference and whether it underflowed.
+    #[must_use]
+    pub const fn overflowing_sub(&self, rhs: &Self) -> (Self, bool) {
+        let mut limbs = [0; LIMBS];
+        let mut borrow = false;
+        let mut i = 0;
+        while i < LIMBS {
+            let (difference, borrow_1) = self.limbs[i].overflowing_sub(rhs.limbs[i]);
+            let (difference, borrow_2) = difference.overflowing_sub(borrow as u64);
+            limbs[i] = difference;
+            borrow = borrow_1 | borrow_2;
+            i += 1;
+        }
+        (Self { limbs }, borrow)
+    }
+
+    /// Computes self + rhs modulo 2^BITS.
+    #[must_use]
+    pub const fn wrapping_add(&self, rhs: &Self) -> Self {
+        self.overflowing_add(rhs).0
+    }
+
+    /// Computes self - rhs modulo 2^BITS.
+    #[must_use]
+    pub const fn wrapping_sub(&self, rhs: &Self) -> Self {
+        self.overflowing_sub(rhs).0
+    }
+
+    /// Computes self + rhs, returning `None` on overflow.
+    #[must_use]
+    pub const fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        match self.overflowing_add(rhs) {
+            (sum, false) => Some(sum),
+            (_, true) => None,
+        }
+    }
+
+    /// Computes self - rhs, returning `None` on underflow.
+    #[must_use]
+    pub const fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        match self.overflowing_sub(rhs) {
+            (difference, false) => Some(difference),
+            (_, true) => None,
+        }
+    }
+
+    /// Computes (self + rhs) mod modulus for operands already below the modulus.
+    #[must_use]
+    pub fn add_mod(&self, rhs: &Self, modulus: &Self) -> Self {
+        let (sum, carry) = self.overflowing_add(rhs);
+        // The true sum is below 2 * modulus, so one subtraction suffices
+        if carry || sum >= *modulus {
+            sum.wrapping_sub(modulus)
+        } else {
+            sum
+        }
+    }
+
+    /// Computes (self - rhs) mod modulus for operands already below the modulus.
+    #[must_use]
+    pub const fn sub_mod(&self, rhs: &Self, modulus: &Self) -> Self {
+        match self.overflowing_sub(rhs) {
+            (difference, true) => difference.wrapping_add(modulus),
+            (difference, false) => difference,
+        }
+    }
+
+    /// Computes -self mod modulus for an operand already below the modulus.
+    #[must_use]
+    pub const fn neg_mod(&self, modulus: &Self) -> Self {
+        Self::ZERO.sub_mod(self, modulus)
+    }
+
+    /// Computes the full product self * rhs, returned as (high, low) halves.
+    ///
+    /// Uses Karatsuba for large even limb counts and schoolbook otherwise.
+    #[must_use]
+    pub fn mul_wide(&self, rhs: &Self) -> (Self, Self) {
+        let mut product = [[0; LIMBS]; 2];
+        let mut scratch = [[0; LIMBS]; 4];
+        mul_limbs(
+            &self.limbs,
+            &rhs.limbs,
+            product.as_flattened_mut(),
+            scratch.as_flattened_mut(),
+        );
+        let [low, high] = product;
+        (Self::from_words(high), Self::from_words(low))
+    }
+
+    /// Computes the full product self * rhs with schoolbook multiplication.
+    #[must_use]
+    pub fn mul_wide_schoolbook(&self, rhs: &Self) -> (Self, Self) {
+        let mut product = [[0; LIMBS]; 2];
+        mul_schoolbook(&self.limbs, &rhs.limbs, product.as_flattened_mut());
+        let [low, high] = product;
+        (Self::from_words(high), Self::from_words(low))
+    }
+
+    /// Computes the full product self * rhs with Karatsuba multiplication.
+    ///
+    /// Always splits the operands once, then recurses like `mul_wide`. An odd limb
+    /// count cannot be split and falls back to schoolbook.
+    #[must_use]
+    pub fn mul_wide_karatsuba(&self, rhs: &Self) -> (Self, Self) {
+        if LIMBS % 2 == 1 {
+            return self.mul_wide_schoolbook(rhs);
+        }
+        let mut product = [[0; LIMBS]; 2];
+        let mut scratch = [[0; LIMBS]; 4];
+        mul_karatsuba(
+            &self.limbs,
+            &rhs.limbs,
+            product.as_flattened_mut(),
+            scratch.as_flattened_mut(),
+        );
+        let [low, high] = product;
+        (Self::from_words(high), Self::from_words(low))
+    }
+
+    /// Computes self * rhs modulo 2^BITS.
+    #[must_use]
+    pub fn wrapping_mul(&self, rhs: &Self) -> Self {
+        self.mul_wide(rhs).1
+    }
+}
+
+/// Writes the 2n-limb product of two n-limb operands to `out`, choosing the algorithm
+/// by size. `scratch` must hold at least 4n limbs.
+fn mul_limbs(a: &[u64], b: &[u64], out: &mut [u64], scratch: &mut [u64]) {
+    if a.len() >= KARATSUBA_THRESHOLD && a.len().is_multiple_of(2) {
+        mul_karatsuba(a, b, out, scratch);
+    } else {
+        mul_schoolbook(a, b, out);
+    }
+}
+
+/// Writes the 2n-limb product of two n-limb operands to `out`.
+fn mul_schoolbook(a: &[u64], b: &[u64], out: &mut [u64]) {
+    let n = a.len();
+    out.fill(0);
+    for (i, &b_i) in b.iter().enumerate() {
+        let mut carry = 0;
+        for (j, &a_j) in a.iter().enumerate() {
+            (carry, out[i + j]) = mul_add_carry(a_j, b_i, out[i + j], carry);
+        }
+        out[i + n] = carry;
+    }
+}
+
+/// Writes the 2n-limb product of two n-limb operands to `out`, using Karatsuba.
+///
+/// Subtractive variant: with a = a1·B + a0 and b = b1·B + b0, the middle term is
+/// a0·b0 + a1·b1 - (a0 - a1)(b0 - b1), so every sub-product has unsigned half-size
+/// operands. n must be even and `scratch` must hold at least 4n limbs.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // words come bit for bit from the signed sum
+fn mul_karatsuba(a: &[u64], b: &[u64], out: &mut [u64], scratch: &mut [u64]) {
+    let n = a.len();
+    let half = n / 2;
+    let (a0, a1) = a.split_at(half);
+    let (b0, b1) = b.split_at(half);
+
+    // z0 = a0·b0 in the low half of the output, z2 = a1·b1 in the high half
+    let (z0, z2) = out.split_at_mut(n);
+    mul_limbs(a0, b0, z0, scratch);
+    mul_limbs(a1, b1, z2, scratch);
+
+    // d = |a0 - a1| · |b0 - b1|, remembering whether (a0 - a1)(b0 - b1) is negative
+    let (differences, rest) = scratch.split_at_mut(n);
+    let (d, rest) = rest.split_at_mut(n);
+    let (da, db) = differences.split_at_mut(half);
+    let negative = abs_diff(a0, a1, da) != abs_diff(b0, b1, db);
+    mul_limbs(da, db, d, rest);
+
+    // middle = z0 + z2 ∓ d, computed in place over d; it is below 2^(64n + 1),
+    // so the word above d is 0 or 1
+    let mut carry: i128 = 0;
+    for j in 0..n {
+        let term = i128::from(d[j]);
+        let sum = i128::from(out[j]) + i128::from(out[n + j]) + carry;
+        let sum = if negative { sum + term } else { sum - term };
+        d[j] = sum as u64;
+        carry = sum >> 64;
+    }
+
+    // out += middle · B
+    let middle_top = carry as u64;
+    let mut carry = 0;
+    for (o, &m) in out[half..].iter_mut().zip(d.iter()) {
+        let (sum, carry_1) = o.overflowing_add(m);
+        let (sum, carry_2) = sum.overflowing_add(carry);
+        *o = sum;
+        carry = u64::from(carry_1) + u64::from(carry_2);
+    }
+    carry += middle_top;
+    for o in &mut out[half + n..] {
+        if carry == 0 {
+            break;
+        }
+        let (sum, overflow) = o.overflowing_add(carry);
+        *o = sum;
+        carry = u64::from(overflow);
+    }
+}
+
+/// Writes |a - b| to `out` and returns true if a < b.
+fn abs_diff(a: &[u64], b: &[u64], out: &mut [u64]) -> bool {
+    let less = a.iter().rev().lt(b.iter().rev());
+    let (larger, smaller) = if less { (b, a) } else { (a, b) };
+    let mut borrow = false;
+    for ((o, &x), &y) in out.iter_mut().zip(larger).zip(smaller) {
+        let (difference, borrow_1) = x.overflowing_sub(y);
+        let (difference, borrow_2) = difference.overflowing_sub(u64::from(borrow));
+        *o = difference;
+        borrow = borrow_1 | borrow_2;
+    }
+    less
+}
+
+impl<const LIMBS: usize> Default for Uint<LIMBS> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<const LIMBS: usize> From<u64> for Uint<LIMBS> {
+    fn from(value: u64) -> Self {
+        Self::from_u64(value)
+    }
+}
+
+impl<const LIMBS: usize> Ord for Uint<LIMBS> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // The most significant limb decides
+        self.limbs.iter().rev().cmp(other.limbs.iter().rev())
+    }
+}
+
+impl<const LIMBS: usize> PartialOrd for Uint<LIMBS> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const LIMBS: usize> fmt::LowerHex for Uint<LIMBS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for limb in self.limbs.iter().rev() {
+            write!(f, "{limb:016x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<const LIMBS: usize> Exponent for Uint<LIMBS> {
+    fn bits(&self) -> usize {
+        Self::bits(self)
+    }
+
+    fn bit(&self, index: usize) -> bool {
+        Self::bit(self, index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ModularError, MontgomeryContext};
+    use proptest::prelude::*;
+
+    fn to_u128(value: Uint<2>) -> u128 {
+        let [low, high] = *value.as_words();
+        (u128::from(high) << 64) | u128::from(low)
+    }
+
+    #[allow(clippy::cast_possible_truncation)] // splits the value into its two words
+    fn from_u128(value: u128) -> Uint<2> {
+        Uint::from_words([value as u64, (value >> 64) as u64])
+    }
+
+    #[test]
+    fn test_uint_basics() {
+        let x = U256::from_be_hex("10000000000000000");
+        assert_eq!(x.as_words(), &[0, 1, 0, 0]);
+        assert_eq!(x.bits(), 65);
+        assert!(x.bit(64) && !x.bit(63) && !x.bit(1000));
+        assert!(U256::ZERO.is_zero() && U256::ONE.is_odd());
+        assert_eq!(U256::MAX.bits(), U256::BITS);
+        assert!(U256::ONE < x && x < U256::MAX);
+
+        assert_eq!(U256::MAX.overflowing_add(&U256::ONE), (U256::ZERO, true));
+        assert_eq!(U256::ZERO.overflowing_sub(&U256::ONE), (U256::MAX, true));
+        assert_eq!(U256::MAX.checked_add(&U256::ONE), None);
+        assert_eq!(
+            x.checked_sub(&U256::ONE),
+            Some(U256::from_words([u64::MAX, 0, 0, 0]))
+        );
+        assert_eq!(format!("{:x}", Uint::<1>::from(255)), "00000000000000ff");
+    }
+
+    #[test]
+    fn test_uint_mul_wide() {
+        // (2^256 - 1)² = 2^512 - 2^257 + 1
+        let (high, low) = U256::MAX.mul_wide(&U256::MAX);
+        assert_eq!(low, U256::ONE);
+        assert_eq!(high, U256::MAX.wrapping_sub(&U256::ONE));
+    }
+
+    #[test]
+    fn test_uint_karatsuba_matches_schoolbook() {
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..20 {
+            let a = Uint::<32>::from_words(std::array::from_fn(|_| next()));
+            let b = Uint::<32>::from_words(std::array::from_fn(|_| next()));
+            assert_eq!(a.mul_wide_karatsuba(&b), a.mul_wide_schoolbook(&b));
+
+            let a = U1024::from_words(std::array::from_fn(|_| next()));
+            assert_eq!(a.mul_wide_karatsuba(&a), a.mul_wide_schoolbook(&a));
+        }
+        assert_eq!(
+            Uint::<32>::MAX.mul_wide_karatsuba(&Uint::<32>::MAX),
+            Uint::<32>::MAX.mul_wide_schoolbook(&Uint::<32>::MAX)
+        );
+    }
+
+    #[test]
+    fn test_uint_montgomery() {
+        // The P-256 field prime, 2^256 - 2^224 + 2^192 + 2^96 - 1
+        const P256: U256 =
+            U256::from_be_hex("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff");
+        let ctx = MontgomeryContext::new(P256);
+        let x =
+            U256::from_be_hex("6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296");
+        let p_minus_1 = P256.wrapping_sub(&U256::ONE);
+
+        // Fermat: x^(p-1) = 1 and x^p = x
+        assert_eq!(ctx.pow_mod(&x, p_minus_1), U256::ONE);
+        assert_eq!(ctx.pow_mod(&x, P256), x);
+        assert_eq!(ctx.pow_mod(&x, 0u64), U256::ONE);
+
+        // (p - 1)² = 1 mod p, and reduce handles values above the modulus
+        assert_eq!(ctx.mul_mod(&p_minus_1, &p_minus_1), U256::ONE);
+        assert_eq!(ctx.reduce(&U256::MAX), U256::MAX.wrapping_sub(&P256));
+
+        assert_eq!(
+            MontgomeryContext::try_new(U256::from_u64(10)).err(),
+            Some(ModularError::EvenModulus)
+        );
+        assert_eq!(
+            MontgomeryContext::try_new(U256::ZERO).err(),
+            Some(ModularError::ZeroModulus)
+        );
+    }
+
+    #[cfg(feature = "arbitrary-precision")]
+    #[test]
+    fn test_uint_montgomery_matches_biguint() {
+        use num_bigint::BigUint;
+
+        let to_big = |value: &U1024| crate::montgomery::from_limbs(value.as_words());
+        let modulus = U1024::from_words(std::array::from_fn(|i| 0x9E37_79B9_7F4A_7C15 ^ i as u64));
+        let base = U1024::from_words(std::array::from_fn(|i| 0x0123_4567_89AB_CDEF << (i % 8)));
+        let exponent = U1024::from_words(std::array::from_fn(|i| u64::MAX / (i as u64 + 3)));
+
+        let ctx = MontgomeryContext::new(modulus);
+        let expected: BigUint = to_big(&base).modpow(&to_big(&exponent), &to_big(&modulus));
+        assert_eq!(to_big(&ctx.pow_mod(&base, exponent)), expected);
+        assert_eq!(
+            to_big(&ctx.mul_mod(&base, &exponent)),
+            to_big(&base) * to_big(&exponent) % to_big(&modulus)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_uint_montgomery_matches_u128(a: u128, b: u128, m in 0u128..(u128::MAX / 2)) {
+            let m = 2 * m + 1;
+            let ctx = MontgomeryContext::new(from_u128(m));
+            let expected = crate::ModularInt::new(a, m) * crate::ModularInt::new(b, m);
+            prop_assert_eq!(to_u128(ctx.mul_mod(&from_u128(a), &from_u128(b))), expected.value());
+        }
+
+        #[test]
+        fn prop_uint_matches_u128(a: u128, b: u128, m in 1u128..) {
+            let (x, y, modulus) = (from_u128(a), from_u128(b), from_u128(m));
+            prop_assert_eq!(to_u128(x.wrapping_add(&y)), a.wrapping_add(b));
+            prop_assert_eq!(to_u128(x.wrapping_sub(&y)), a.wrapping_sub(b));
+            prop_assert_eq!(to_u128(x.wrapping_mul(&y)), a.wrapping_mul(b));
+            prop_assert_eq!(x.cmp(&y), a.cmp(&b));
+
+            let (a, b) = (a % m, b % m);
+            let (x, y) = (from_u128(a), from_u128(b));
+            let expected = crate::ModularInt::new(a, m);
+            prop_assert_eq!(to_u128(x.add_mod(&y, &modulus)), (expected + crate::ModularInt::new(b, m)).value());
+            prop_assert_eq!(to_u128(x.sub_mod(&y, &modulus)), (expected - crate::ModularInt::new(b, m)).value());
+        }
+    }
+}
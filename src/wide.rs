@@ -0,0 +1,182 @@
+// Double-word arithmetic on u128 for 256-bit intermediate products
+
+/// Computes the full 256-bit product a * b, returned as (high, low) halves.
+#[allow(clippy::cast_possible_truncation)] // splits operands and partial products into halves
+pub const fn mul_wide_u128(a: u128, b: u128) -> (u128, u128) {
+    let (a_lo, a_hi) = (a as u64 as u128, a >> 64);
+    let (b_lo, b_hi) = (b as u64 as u128, b >> 64);
+
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+
+    // Sum of three values below 2^64 each, so it cannot overflow
+    let cross = (lo_lo >> 64) + (hi_lo as u64 as u128) + (lo_hi as u64 as u128);
+
+    let lo = (cross << 64) | (lo_lo as u64 as u128);
+    let hi = hi_hi + (hi_lo >> 64) + (lo_hi >> 64) + (cross >> 64);
+    (hi, lo)
+}
+
+/// Returns the high 128 bits of the 256-bit product a * b.
+pub const fn mul_hi_u128(a: u128, b: u128) -> u128 {
+    mul_wide_u128(a, b).0
+}
+
+/// Adds two 256-bit values given as (high, low), returning the sum and the carry out.
+pub const fn add_wide_u128(a: (u128, u128), b: (u128, u128)) -> ((u128, u128), bool) {
+    let (lo, carry_lo) = a.1.overflowing_add(b.1);
+    let (hi, carry_hi1) = a.0.overflowing_add(b.0);
+    let (hi, carry_hi2) = hi.overflowing_add(carry_lo as u128);
+    ((hi, lo), carry_hi1 | carry_hi2)
+}
+
+/// Subtracts two 256-bit values given as (high, low), wrapping on underflow.
+pub const fn sub_wide_u128(a: (u128, u128), b: (u128, u128)) -> (u128, u128) {
+    let (lo, borrow) = a.1.overflowing_sub(b.1);
+    let hi = a.0.wrapping_sub(b.0).wrapping_sub(borrow as u128);
+    (hi, lo)
+}
+
+/// Computes (high * 2^128 + low) mod modulus for a non-zero modulus.
+pub const fn rem_wide_u128(high: u128, low: u128, modulus: u128) -> u128 {
+    div_rem_wide_u128(high % modulus, low, modulus).1
+}
+
+/// Divides (high * 2^128 + low) by modulus, returning (quotient, remainder).
+///
+/// Requires high < modulus, which guarantees the quotient fits in a u128. Shifts the
+/// low half in one bit at a time, so the running remainder always stays below the
+/// modulus.
+pub const fn div_rem_wide_u128(high: u128, low: u128, modulus: u128) -> (u128, u128) {
+    let mut quotient = 0;
+    let mut remainder = high;
+    let mut bit = 128;
+    while bit > 0 {
+        bit -= 1;
+        // remainder < modulus, so 2 * remainder + 1 < 2 * modulus and one subtraction suffices;
+        // when the doubling overflows the true value exceeds the modulus as well
+        let overflow = remainder >> 127 == 1;
+        remainder = (remainder << 1) | ((low >> bit) & 1);
+        quotient <<= 1;
+        if overflow || remainder >= modulus {
+            remainder = remainder.wrapping_sub(modulus);
+            quotient |= 1;
+        }
+    }
+    (quotient, remainder)
+}
+
+/// Computes a * b + addend + carry as (high, low); the sum always fits in 128 bits.
+///
+/// This is the multiply-accumulate step of every portable multi-limb loop. It stays
+/// a plain `u128` expression so it inlines into those loops; accelerated code paths
+/// pick a whole kernel once per operation instead of dispatching per limb.
+#[inline]
+#[allow(clippy::cast_possible_truncation)] // splits the 128-bit sum into its halves
+pub const fn mul_add_carry(a: u64, b: u64, addend: u64, carry: u64) -> (u64, u64) {
+    let wide = a as u128 * b as u128 + addend as u128 + carry as u128;
+    ((wide >> 64) as u64, wide as u64)
+}
+
+/// Computes the carryless (GF(2)[x]) product of a and b as (high, low).
+///
+/// This is the multiplication step of binary field arithmetic. With hardware
+/// acceleration it goes through `intrinsics::arithmetic::carryless_mul`.
+#[inline]
+#[cfg_attr(
+    not(feature = "hardware-acceleration"),
+    allow(clippy::missing_const_for_fn) // dispatches at run time with the feature
+)]
+pub fn carryless_mul(a: u64, b: u64) -> (u64, u64) {
+    #[cfg(feature = "hardware-acceleration")]
+    {
+        crate::intrinsics::arithmetic::carryless_mul(a, b)
+    }
+
+    #[cfg(not(feature = "hardware-acceleration"))]
+    {
+        carryless_mul_portable(a, b)
+    }
+}
+
+/// Portable carryless multiplication, usable in `const` contexts.
+///
+/// Each bit of b selects a shifted copy of a through a mask, so there are no
+/// data-dependent branches.
+pub const fn carryless_mul_portable(a: u64, b: u64) -> (u64, u64) {
+    let (mut hi, mut lo) = (0u64, 0u64);
+    let mut i = 0;
+    while i < 64 {
+        let mask = 0u64.wrapping_sub((b >> i) & 1);
+        lo ^= (a << i) & mask;
+        // a >> (64 - i), split in two so that i = 0 shifts everything out instead of
+        // overflowing the shift amount
+        hi ^= ((a >> 1) >> (63 - i)) & mask;
+        i += 1;
+    }
+    (hi, lo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_wide_u128() {
+        assert_eq!(mul_wide_u128(u128::MAX, u128::MAX), (u128::MAX - 1, 1));
+        assert_eq!(mul_wide_u128(1 << 64, 1 << 64), (1, 0));
+        assert_eq!(mul_wide_u128(12_345, 67_890), (0, 12_345 * 67_890));
+    }
+
+    #[test]
+    fn test_mul_add_carry() {
+        assert_eq!(
+            mul_add_carry(u64::MAX, u64::MAX, u64::MAX, u64::MAX),
+            (u64::MAX, u64::MAX)
+        );
+        assert_eq!(mul_add_carry(1 << 32, 1 << 32, 5, 7), (1, 12));
+    }
+
+    #[test]
+    fn test_rem_wide_u128() {
+        let m = u128::MAX - 158;
+        // (m - 1)^2 = m^2 - 2m + 1 ≡ 1
+        let (hi, lo) = mul_wide_u128(m - 1, m - 1);
+        assert_eq!(rem_wide_u128(hi, lo, m), 1);
+        assert_eq!(rem_wide_u128(0, 1_000, 7), 1_000 % 7);
+        assert_eq!(rem_wide_u128(1, 0, 1 << 127), 0);
+        assert_eq!(rem_wide_u128(1, 0, 3), 1); // 2^128 ≡ 1 (mod 3)
+    }
+
+    #[test]
+    fn test_div_rem_wide_u128() {
+        assert_eq!(div_rem_wide_u128(0, u128::MAX, u128::MAX), (1, 0));
+        // (2 * 2^128 + 5) / 3 = 0xAAAA...AAAC remainder 1
+        assert_eq!(
+            div_rem_wide_u128(2, 5, 3),
+            (0xAAAA_AAAA_AAAA_AAAA_AAAA_AAAA_AAAA_AAAC, 1)
+        );
+        let (q, r) = div_rem_wide_u128(6, 100, 7);
+        let (hi, lo) = mul_wide_u128(q, 7);
+        assert_eq!(add_wide_u128((hi, lo), (0, r)).0, (6, 100));
+    }
+
+    #[test]
+    fn test_carryless_mul_portable() {
+        assert_eq!(carryless_mul_portable(0b11, 0b11), (0, 0b101));
+        assert_eq!(carryless_mul_portable(1 << 63, 1 << 63), (1 << 62, 0));
+        assert_eq!(
+            carryless_mul_portable(u64::MAX, 1 << 63),
+            (u64::MAX >> 1, 1 << 63)
+        );
+        // (x + 1)^64 = x^64 + 1 in characteristic 2
+        let x_plus_1_pow_32 = (1 << 32) | 1;
+        assert_eq!(
+            carryless_mul_portable(x_plus_1_pow_32, x_plus_1_pow_32),
+            (1, 1)
+        );
+        assert_eq!(carryless_mul(0x87, 0), (0, 0));
+    }
+}